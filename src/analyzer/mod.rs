@@ -0,0 +1,875 @@
+use crate::cache::{self, GraphSnapshot, ParseCache};
+use crate::config::{Config, RulesConfig};
+use crate::error::{PurgeError, Result};
+use crate::frameworks;
+use crate::graph::{DependencyGraph, FileImportGraph, ParsedImport, SymbolUsageGraph, Templates};
+use crate::parser::{self, ParseFailure, ParsedFile};
+use crate::rules::{AnalysisReport, RulesEngine};
+use crate::scanner::{self, FileDiscovery, SkippedFile, WorkspaceScanner};
+use crate::scripts;
+use crate::timing::PhaseTiming;
+use crate::tools;
+use crate::workspaces::{self, LocalPackages, WorkspacePackage};
+use globset::GlobSet;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Entry point used when neither the caller, the config, nor package.json names one
+pub const DEFAULT_ENTRY: &str = "src/index.ts";
+
+/// Print a progress line when progress output is enabled
+macro_rules! progress {
+    ($enabled:expr) => {
+        if $enabled {
+            println!();
+        }
+    };
+    ($enabled:expr, $($arg:tt)*) => {
+        if $enabled {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Scans a project and builds its analysis graphs, configured like the CLI
+///
+/// ```no_run
+/// use sweepr::analyzer::Analyzer;
+/// use sweepr::config::Config;
+///
+/// let report = Analyzer::new(Config::find_and_load()?).run()?;
+/// println!("{} findings", report.findings().len());
+/// # Ok::<(), sweepr::error::PurgeError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Analyzer {
+    config: Config,
+    root: Option<PathBuf>,
+    paths: Vec<PathBuf>,
+    entry: Vec<String>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    follow_symlinks: bool,
+    use_cache: bool,
+    show_progress: bool,
+}
+
+impl Analyzer {
+    /// Analyze the current directory with `config`, using the cache in `.sweepr/cache`
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            root: None,
+            paths: Vec::new(),
+            entry: Vec::new(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            follow_symlinks: false,
+            use_cache: true,
+            show_progress: false,
+        }
+    }
+
+    /// Analyze the project in this directory, relative to the current one
+    pub fn with_root(mut self, root: PathBuf) -> Self {
+        self.root = Some(root);
+        self
+    }
+
+    /// Only scan these directories, relative to the root (default: the whole project)
+    pub fn with_paths(mut self, paths: Vec<PathBuf>) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    /// Entry points to use instead of the config's `entry`
+    pub fn with_entry(mut self, entry: Vec<String>) -> Self {
+        self.entry = entry;
+        self
+    }
+
+    /// Only scan files matching these globs
+    pub fn with_include(mut self, include: Vec<String>) -> Self {
+        self.include = include;
+        self
+    }
+
+    /// Skip files matching these globs, in addition to the config's `ignore`
+    pub fn with_exclude(mut self, exclude: Vec<String>) -> Self {
+        self.exclude = exclude;
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Reuse and update the parses and graphs cached under the root
+    pub fn with_cache(mut self, use_cache: bool) -> Self {
+        self.use_cache = use_cache;
+        self
+    }
+
+    /// Print progress lines to stdout while building
+    pub fn with_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+
+    /// Build the workspace and run the rules enabled in the config
+    pub fn run(&self) -> Result<AnalysisReport> {
+        Ok(self.build()?.analyze())
+    }
+
+    /// Scan the project, parse every file, and build the analysis graphs
+    ///
+    /// Unless the cache is disabled, files unchanged since the last run reuse their cached parse.
+    pub fn build(&self) -> Result<Workspace> {
+        let config = &self.config;
+
+        // Determine entry points; globs match files anywhere in the project
+        let entries = if self.entry.is_empty() {
+            &config.entry
+        } else {
+            &self.entry
+        };
+        let (entry_globs, package_entries): (Vec<String>, Vec<String>) =
+            entries.iter().cloned().partition(|entry| is_glob(entry));
+        let include = if self.include.is_empty() {
+            &config.include
+        } else {
+            &self.include
+        };
+        let ignored_dependencies = scanner::build_globset(&config.ignore_dependencies, "ignoreDependencies")?;
+
+        progress!(self.show_progress, "🚀 Scanning workspace...");
+        let mut phase_start = Instant::now();
+
+        // Scan workspace
+        let current_dir = std::env::current_dir()?.join(self.root.as_deref().unwrap_or(Path::new("")));
+
+        // Roots inside the project keep the project's path prefix, others are made canonical
+        let project_dir = current_dir.canonicalize()?;
+        let roots = self
+            .paths
+            .iter()
+            .map(|path| {
+                let root = current_dir
+                    .join(path)
+                    .canonicalize()
+                    .map_err(|_| PurgeError::FileNotFound(path.display().to_string()))?;
+                Ok(match root.strip_prefix(&project_dir) {
+                    Ok(relative) => current_dir.join(relative),
+                    Err(_) => root,
+                })
+            })
+            .collect::<Result<Vec<PathBuf>>>()?;
+
+        let ignore = config.ignore.iter().chain(&self.exclude).cloned().collect();
+        let scanner = WorkspaceScanner::new(current_dir.clone())
+            .with_roots(roots.clone())
+            .with_ignore(ignore)
+            .with_test_globs(config.tests.clone())
+            .with_story_globs(config.stories.clone())
+            .with_include(include.clone())
+            .with_follow_symlinks(self.follow_symlinks)
+            .with_max_file_size(config.max_file_size)
+            .with_skip_minified(config.skip_minified);
+
+        // Sibling packages passed as roots bring their own package.json files
+        let mut packages = workspaces::discover_packages(&current_dir)?;
+        for root in roots.iter().filter(|root| !root.starts_with(&current_dir)) {
+            packages.extend(workspaces::discover_packages(root)?);
+        }
+
+        // Route and convention files of detected frameworks are entry points too,
+        // and everything auto-imported files export is used
+        let mut detected_frameworks = Vec::new();
+        let mut entry_globs = entry_globs;
+        let mut used_export_globs = Vec::new();
+        // Declared framework packages, with the globs of the files they load
+        let mut framework_packages = Vec::new();
+        for package in &packages {
+            let dependencies = package.dependencies.iter().map(|(name, _, _)| name.as_str());
+            let dir = package.dir.strip_prefix(&current_dir).unwrap_or(&package.dir);
+            for framework in frameworks::detect(dependencies, config.framework.as_deref()) {
+                if !detected_frameworks.contains(&framework.name) {
+                    detected_frameworks.push(framework.name);
+                }
+                let globs: Vec<String> = framework
+                    .entry_globs
+                    .iter()
+                    .map(|glob| dir.join(glob).to_string_lossy().to_string())
+                    .collect();
+                for (name, _, _) in &package.dependencies {
+                    if framework.packages.contains(&name.as_str()) {
+                        framework_packages.push((name.clone(), package.manifest.clone(), globs.clone()));
+                    }
+                }
+                for glob in framework.entry_globs {
+                    let glob = dir.join(glob).to_string_lossy().to_string();
+                    if !framework.used_exports.is_empty() {
+                        let names = framework.used_exports.iter().map(|name| name.to_string()).collect();
+                        used_export_globs.push((glob.clone(), names));
+                    }
+                    entry_globs.push(glob);
+                }
+                for glob in framework.auto_import_globs {
+                    let glob = dir.join(glob).to_string_lossy().to_string();
+                    used_export_globs.push((glob.clone(), vec!["*".to_string()]));
+                    entry_globs.push(glob);
+                }
+            }
+        }
+
+        // Test runners and build tools load their config and the setup and
+        // entry files it names, and read whatever those export
+        for package in &packages {
+            let dir = package.dir.strip_prefix(&current_dir).unwrap_or(&package.dir);
+            for file in tools::loaded_files(package) {
+                let file = dir.join(file).to_string_lossy().to_string();
+                used_export_globs.push((file.clone(), vec!["*".to_string()]));
+                entry_globs.push(file);
+            }
+        }
+
+        // Serverless functions are entry points whose handler export is used
+        for package in &packages {
+            let dir = package.dir.strip_prefix(&current_dir).unwrap_or(&package.dir);
+            for (file, export) in frameworks::serverless::handlers(&package.dir) {
+                let file = dir.join(file).to_string_lossy().to_string();
+                used_export_globs.push((file.clone(), vec![export]));
+                entry_globs.push(file);
+            }
+        }
+
+        // A Cloudflare Worker's entry module, and the exports its bindings name
+        for package in &packages {
+            let dir = package.dir.strip_prefix(&current_dir).unwrap_or(&package.dir);
+            if let Some((main, exports)) = frameworks::wrangler::worker(&package.dir) {
+                let main = dir.join(main).to_string_lossy().to_string();
+                used_export_globs.push((main.clone(), exports));
+                entry_globs.push(main);
+            }
+        }
+
+        // Storybook reads every export of a story
+        for glob in &config.stories {
+            used_export_globs.push((glob.clone(), vec!["*".to_string()]));
+        }
+
+        let mut entry_points = resolve_entry_points(&scanner, &current_dir, &packages, &package_entries);
+        if entry_points.is_empty() && entry_globs.is_empty() {
+            // Let the scanner report the missing default
+            entry_points.push(DEFAULT_ENTRY.to_string());
+        }
+        // Files a package declares with side effects may be imported for them alone
+        let side_effect_globs = packages
+            .iter()
+            .flat_map(|package| {
+                let dir = package.dir.strip_prefix(&current_dir).unwrap_or(&package.dir);
+                package
+                    .side_effects
+                    .iter()
+                    .map(move |glob| dir.join(glob).to_string_lossy().to_string())
+            })
+            .collect();
+        let scanner = scanner
+            .with_entry_globs(entry_globs)
+            .with_side_effect_globs(side_effect_globs)
+            .with_used_export_globs(used_export_globs);
+
+        let discovery = scanner.discover(entry_points.clone())?;
+        let scan_timing = PhaseTiming::new("scan", phase_start.elapsed());
+        phase_start = Instant::now();
+
+        progress!(self.show_progress, "  📄 Found {} files", discovery.files.len());
+        if !discovery.skipped.is_empty() {
+            progress!(
+                self.show_progress,
+                "  ⏭️  Skipped {} minified or oversized files",
+                discovery.skipped.len()
+            );
+        }
+        progress!(self.show_progress, "  🎯 Entry points: {}", discovery.entry_points.len());
+        if !detected_frameworks.is_empty() {
+            progress!(self.show_progress, "  🧩 Frameworks: {}", detected_frameworks.join(", "));
+        }
+        if packages.len() > 1 {
+            progress!(self.show_progress, "  📦 Workspace packages: {}", packages.len());
+        }
+        progress!(self.show_progress);
+
+        progress!(self.show_progress, "🔬 Analyzing code...");
+
+        let mut workspace = Workspace {
+            root: current_dir.clone(),
+            scanner,
+            entry_points,
+            rules: config.rules.clone(),
+            ignored_dependencies,
+            skipped_files: discovery.skipped.clone(),
+            parse_failures: Vec::new(),
+            file_graph: FileImportGraph::new(),
+            symbol_graph: SymbolUsageGraph::new(),
+            dependency_graph: DependencyGraph::new(),
+            local_packages: LocalPackages::new(&packages),
+            packages: packages.clone(),
+            timings: vec![scan_timing],
+        };
+
+        // Graphs from the last run only need the files changed since then
+        let mut hashes = HashMap::new();
+        let mut manifest_hashes = HashMap::new();
+        if self.use_cache {
+            hashes = cache::hash_files(&discovery.files)?;
+            manifest_hashes = cache::hash_files(&manifest_files(&packages))?;
+
+            if let Some(snapshot) = GraphSnapshot::load(&current_dir, &manifest_hashes) {
+                let changed: HashSet<PathBuf> = discovery
+                    .files
+                    .iter()
+                    .filter(|path| snapshot.files.get(*path) != hashes.get(*path))
+                    .cloned()
+                    .collect();
+
+                workspace.file_graph = snapshot.file_graph;
+                workspace.symbol_graph = snapshot.symbol_graph;
+                workspace.dependency_graph = snapshot.dependency_graph;
+                workspace.apply_discovery(&discovery, &changed);
+
+                progress!(
+                    self.show_progress,
+                    "  ✓ Reused analysis graphs ({} files changed)",
+                    changed.len()
+                );
+                save_snapshot(&workspace, hashes, manifest_hashes)?;
+                workspace
+                    .timings
+                    .push(PhaseTiming::new("cached graphs", phase_start.elapsed()));
+                progress!(self.show_progress);
+
+                return Ok(workspace);
+            }
+        }
+
+        // Parse all files
+        let files = discovery.files.clone();
+        let (parsed_files, failures) = if self.use_cache {
+            let mut cache = ParseCache::load(&current_dir);
+            let (parsed_files, cached, failures) = parser::AstAnalyzer::parse_files_cached(files, &mut cache);
+            cache.retain(&discovery.files);
+            cache.save()?;
+
+            progress!(
+                self.show_progress,
+                "  ✓ Parsed {} files ({} unchanged, from cache)",
+                parsed_files.len(),
+                cached
+            );
+            (parsed_files, failures)
+        } else {
+            let (parsed_files, failures) = parser::AstAnalyzer::parse_files_parallel(files);
+            progress!(self.show_progress, "  ✓ Parsed {} files", parsed_files.len());
+            (parsed_files, failures)
+        };
+        if !failures.is_empty() {
+            progress!(self.show_progress, "  ⚠️  {} files failed to parse", failures.len());
+        }
+        workspace.timings.push(PhaseTiming::new("parse", phase_start.elapsed()));
+        phase_start = Instant::now();
+
+        // Load every package's package.json dependencies
+        for package in &packages {
+            for (name, version, kind) in &package.dependencies {
+                workspace.dependency_graph.add_dependency(
+                    workspace.file_graph.interners_mut(),
+                    &package.manifest,
+                    name,
+                    version.clone(),
+                    *kind,
+                );
+            }
+        }
+
+        // Plugins, presets, and the like named in tool config files are used,
+        // as are packages whose binaries the package.json scripts run and
+        // Nx implicit dependencies
+        for package in &packages {
+            let implicit = package
+                .implicit_dependencies
+                .iter()
+                .map(|name| (name.clone(), package.manifest.clone()));
+            let references = tools::referenced_packages(package)
+                .into_iter()
+                .chain(scripts::referenced_packages(package))
+                .chain(implicit);
+            for (name, file) in references {
+                workspace
+                    .dependency_graph
+                    .record_use(workspace.file_graph.interners_mut(), &name, &file);
+            }
+        }
+        // A framework loading route or convention files is in use, even when
+        // nothing imports it, e.g. Nuxt pages relying on auto-imports
+        for (name, manifest, globs) in &framework_packages {
+            let globs = scanner::build_globset(globs, "entry")?;
+            let loads_files = discovery
+                .entry_points
+                .iter()
+                .any(|file| globs.is_match(file.strip_prefix(&current_dir).unwrap_or(file)));
+            if loads_files {
+                workspace
+                    .dependency_graph
+                    .record_use(workspace.file_graph.interners_mut(), name, manifest);
+            }
+        }
+
+        // Add files to graph, leaving out those that failed to parse
+        for file in &discovery.files {
+            if !failures.iter().any(|failure| &failure.path == file) {
+                add_discovered_file(&mut workspace.file_graph, &discovery, file);
+            }
+        }
+        workspace.parse_failures = failures;
+
+        // Process parsed files
+        for parsed_file in &parsed_files {
+            workspace.add_parsed_file(parsed_file);
+        }
+        workspace.mark_ipc_handlers(&discovery.files);
+
+        progress!(self.show_progress, "  ✓ Built analysis graphs");
+        if !packages.is_empty() {
+            progress!(
+                self.show_progress,
+                "  ✓ Loaded {} dependencies",
+                workspace.dependency_graph.dependencies.len()
+            );
+        }
+
+        if self.use_cache {
+            save_snapshot(&workspace, hashes, manifest_hashes)?;
+        }
+        workspace.timings.push(PhaseTiming::new("graph", phase_start.elapsed()));
+
+        progress!(self.show_progress);
+
+        Ok(workspace)
+    }
+
+    /// Build the workspace from the graphs of the last cached run, re-parsing
+    /// only `path`, for a quick check of one file
+    ///
+    /// Nothing is scanned, so other files count as they were at the last
+    /// full run. Without a snapshot built from the current manifests, or with
+    /// the cache disabled, this is a full `build`. Either way `path` must be
+    /// an existing source file inside the project.
+    pub fn build_file(&self, path: &Path) -> Result<Workspace> {
+        let current_dir = std::env::current_dir()?.join(self.root.as_deref().unwrap_or(Path::new("")));
+        if !path.is_file() {
+            return Err(PurgeError::FileNotFound(path.display().to_string()));
+        }
+        if !path.canonicalize()?.starts_with(current_dir.canonicalize()?) {
+            return Err(PurgeError::Config(format!("{} is outside the project", path.display())));
+        }
+        if !scanner::is_source_file(path) {
+            return Err(PurgeError::Config(format!(
+                "{} is not a JavaScript, TypeScript, or component file",
+                path.display()
+            )));
+        }
+        let packages = workspaces::discover_packages(&current_dir)?;
+        let snapshot = if self.use_cache {
+            GraphSnapshot::load(&current_dir, &cache::hash_files(&manifest_files(&packages))?)
+        } else {
+            None
+        };
+        let Some(snapshot) = snapshot else {
+            return self.build();
+        };
+
+        let phase_start = Instant::now();
+        let mut workspace = Workspace {
+            root: current_dir.clone(),
+            scanner: WorkspaceScanner::new(current_dir),
+            entry_points: Vec::new(),
+            rules: self.config.rules.clone(),
+            ignored_dependencies: scanner::build_globset(&self.config.ignore_dependencies, "ignoreDependencies")?,
+            skipped_files: Vec::new(),
+            parse_failures: Vec::new(),
+            file_graph: snapshot.file_graph,
+            symbol_graph: snapshot.symbol_graph,
+            dependency_graph: snapshot.dependency_graph,
+            local_packages: LocalPackages::new(&packages),
+            packages,
+            timings: Vec::new(),
+        };
+
+        let content = std::fs::read(path).map_err(|_| PurgeError::FileNotFound(path.display().to_string()))?;
+        if snapshot.files.get(path) != Some(&cache::content_hash(&content)) {
+            let source = String::from_utf8(content).map_err(|e| PurgeError::ParseError {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            })?;
+            workspace.replace_source(path, &source)?;
+        }
+        progress!(self.show_progress, "  ✓ Re-checked {} against cached graphs\n", path.display());
+        workspace
+            .timings
+            .push(PhaseTiming::new("cached graphs", phase_start.elapsed()));
+
+        Ok(workspace)
+    }
+}
+
+/// package.json files and the tool configs that, like them, feed the
+/// dependency graph
+fn manifest_files(packages: &[WorkspacePackage]) -> Vec<PathBuf> {
+    packages
+        .iter()
+        .flat_map(|package| std::iter::once(package.manifest.clone()).chain(tools::config_files(package)))
+        .collect()
+}
+
+/// The scanned workspace and its analysis graphs
+pub struct Workspace {
+    pub root: PathBuf,
+    pub scanner: WorkspaceScanner,
+    pub entry_points: Vec<String>,
+    pub rules: RulesConfig,
+    /// Dependencies the config says never to report
+    pub ignored_dependencies: GlobSet,
+    pub skipped_files: Vec<SkippedFile>,
+    /// Files that failed to parse, left out of the graphs
+    pub parse_failures: Vec<ParseFailure>,
+    pub file_graph: FileImportGraph,
+    pub symbol_graph: SymbolUsageGraph,
+    pub dependency_graph: DependencyGraph,
+    /// Packages of the project that imports by name link to
+    pub local_packages: LocalPackages,
+    /// Packages of a monorepo, or the single root package
+    pub packages: Vec<WorkspacePackage>,
+    /// How long building the workspace took, phase by phase
+    pub timings: Vec<PhaseTiming>,
+}
+
+impl Workspace {
+    /// Run the rules enabled in the config over the graphs
+    ///
+    /// The report's `timings` start with the phases that built the workspace.
+    pub fn analyze(&self) -> AnalysisReport {
+        let mut report = RulesEngine::analyze(
+            &self.dependency_graph,
+            &self.file_graph,
+            &self.symbol_graph,
+            &self.rules,
+        );
+        report.retain_dependencies(|name| !self.ignored_dependencies.is_match(name));
+        report.skipped_files = self.skipped_files.clone();
+        report.parse_failures = self.parse_failures.clone();
+        report.timings = self.timings.iter().cloned().chain(report.timings).collect();
+        report
+    }
+
+    /// Add a parsed file's imports, exports, and references to the graphs
+    ///
+    /// The file must already be a node of the file graph.
+    fn add_parsed_file(&mut self, parsed_file: &ParsedFile) {
+        let file = self
+            .file_graph
+            .file_id(&parsed_file.path)
+            .expect("parsed files are added to the file graph first");
+
+        // Add imports to file graph
+        for import in &parsed_file.imports {
+            self.file_graph.add_import(import.clone());
+        }
+
+        // Record package imports in the dependency graph
+        for import in &parsed_file.package_imports {
+            self.dependency_graph
+                .record_package_import(self.file_graph.interners_mut(), import);
+        }
+
+        // Imports of workspace packages use their files like relative imports
+        for import in &parsed_file.bare_imports {
+            if let Some(to) = self.local_packages.resolve(&import.specifier) {
+                self.file_graph.add_import(ParsedImport {
+                    to,
+                    is_resolved: true,
+                    ..import.clone()
+                });
+            }
+        }
+
+        self.file_graph.set_ambient_declarations(
+            &parsed_file.path,
+            parsed_file.ambient_modules.clone(),
+            parsed_file.declares_globals,
+            parsed_file.bare_imports.iter().map(|import| import.specifier.clone()).collect(),
+        );
+
+        let interners = self.file_graph.interners_mut();
+
+        // Add exports to symbol graph
+        for export in &parsed_file.exports {
+            self.symbol_graph.add_export(interners, file, export);
+        }
+
+        // Add references to symbol graph
+        for reference in &parsed_file.references {
+            self.symbol_graph.add_reference(interners, file, reference);
+        }
+
+        self.symbol_graph
+            .set_lines(interners, file, parsed_file.lines.clone());
+
+        if !parsed_file.unread_members.is_empty() {
+            self.symbol_graph
+                .set_unread_members(file, parsed_file.unread_members.clone());
+        }
+
+        if !parsed_file.unread_locals.is_empty() {
+            self.symbol_graph
+                .set_unread_locals(file, parsed_file.unread_locals.clone());
+        }
+
+        if !parsed_file.unused_imports.is_empty() {
+            self.symbol_graph
+                .set_unused_imports(file, parsed_file.unused_imports.clone());
+        }
+
+        if !parsed_file.suppressions.is_empty() {
+            self.symbol_graph
+                .set_suppressions(file, parsed_file.suppressions.clone());
+        }
+
+        if !parsed_file.template_names.is_empty() || !parsed_file.template_files.is_empty() {
+            let templates = Templates {
+                names: parsed_file.template_names.clone(),
+                files: parsed_file.template_files.clone(),
+            };
+            self.symbol_graph.set_templates(file, templates);
+        }
+    }
+
+    /// Make entry points of the files among `files` that register Electron
+    /// IPC handlers through `ipcMain`, which the main process may load
+    /// without a static import, e.g. from a directory listing
+    fn mark_ipc_handlers(&mut self, files: &[PathBuf]) {
+        let Some(ipc_main) = self.file_graph.symbol_id("ipcMain") else {
+            return;
+        };
+        for path in files {
+            let references = self
+                .file_graph
+                .file_id(path)
+                .and_then(|file| self.symbol_graph.references.get(&file));
+            if references.is_some_and(|references| references.iter().any(|r| r.symbol == ipc_main)) {
+                self.file_graph.set_entry_point(path);
+            }
+        }
+    }
+
+    /// Drop everything a file contributed to the graphs, keeping its node
+    fn clear_file(&mut self, path: &Path) {
+        self.file_graph.remove_imports_from(path);
+        if let Some(file) = self.file_graph.file_id(path) {
+            self.symbol_graph.remove_file(self.file_graph.interners_mut(), file);
+        }
+        self.dependency_graph
+            .remove_imports_from(self.file_graph.interners_mut(), path);
+    }
+
+    /// Analyze `path` with `source` in place of its content on disk, adding
+    /// it to the graph when it doesn't exist yet
+    pub fn replace_source(&mut self, path: &Path, source: &str) -> Result<()> {
+        let parsed_file = parser::AstAnalyzer::parse_file_source(path.to_path_buf(), source)?;
+        if self.file_graph.contains_file(path) {
+            self.clear_file(path);
+        } else {
+            self.file_graph.add_file(path.to_path_buf(), false);
+        }
+        self.add_parsed_file(&parsed_file);
+        self.mark_ipc_handlers(std::slice::from_ref(&parsed_file.path));
+        Ok(())
+    }
+
+    /// Whether any of `changed` is a package.json or tool config the
+    /// workspace was built from, or a new package.json, which `apply_changes`
+    /// can't account for: the dependencies, packages, and entry points they
+    /// declare take a full build
+    pub fn manifests_changed(&self, changed: &HashSet<PathBuf>) -> bool {
+        let manifests = manifest_files(&self.packages);
+        changed.iter().any(|path| {
+            manifests.contains(path)
+                || (path.file_name().is_some_and(|name| name == "package.json")
+                    && !path.components().any(|component| component.as_os_str() == "node_modules"))
+        })
+    }
+
+    /// Re-discover files and re-parse only the changed ones
+    ///
+    /// Returns whether any file that is part of the analysis was affected.
+    pub fn apply_changes(&mut self, changed: &HashSet<PathBuf>) -> Result<bool> {
+        let discovery = self.scanner.discover(self.entry_points.clone())?;
+        Ok(self.apply_discovery(&discovery, changed))
+    }
+
+    /// Bring the graphs in line with a discovery, re-parsing new and changed files
+    ///
+    /// Files whose imports pointed at a missing file are re-parsed as well
+    /// when files come or go, since their imports may now resolve differently.
+    /// A file that fails to parse is left out of the graphs until it parses
+    /// again. Returns whether anything was affected.
+    fn apply_discovery(&mut self, discovery: &FileDiscovery, changed: &HashSet<PathBuf>) -> bool {
+        let discovered: HashSet<&PathBuf> = discovery.files.iter().collect();
+        self.skipped_files = discovery.skipped.clone();
+
+        let removed: Vec<PathBuf> = self
+            .file_graph
+            .files()
+            .map(|file| file.path.to_path_buf())
+            .filter(|path| !discovered.contains(path))
+            .collect();
+        for path in &removed {
+            self.clear_file(path);
+            self.file_graph.remove_file(path);
+        }
+
+        let added: Vec<&PathBuf> = discovery
+            .files
+            .iter()
+            .filter(|path| !self.file_graph.contains_file(path))
+            .collect();
+
+        let mut reparse: HashSet<PathBuf> = discovery
+            .files
+            .iter()
+            .filter(|path| changed.contains(*path))
+            .chain(added.iter().copied())
+            .cloned()
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            reparse.extend(
+                self.file_graph
+                    .imports()
+                    .filter(|import| !self.file_graph.contains_file(self.file_graph.path(import.to)))
+                    .map(|import| self.file_graph.path(import.from).to_path_buf())
+                    .filter(|path| discovered.contains(path)),
+            );
+        }
+
+        for path in &discovery.files {
+            if reparse.contains(path) {
+                self.clear_file(path);
+            }
+            add_discovered_file(&mut self.file_graph, discovery, path);
+        }
+
+        let (parsed_files, failures) = parser::AstAnalyzer::parse_files_parallel(reparse.iter().cloned().collect());
+        for parsed_file in &parsed_files {
+            self.add_parsed_file(parsed_file);
+        }
+        for failure in &failures {
+            self.file_graph.remove_file(&failure.path);
+        }
+        self.mark_ipc_handlers(&discovery.files);
+
+        // Files not re-parsed keep failing until they change
+        self.parse_failures
+            .retain(|failure| discovered.contains(&failure.path) && !reparse.contains(&failure.path));
+        self.parse_failures.extend(failures);
+
+        !removed.is_empty() || !reparse.is_empty()
+    }
+}
+
+/// Whether an entry is a glob rather than a file path
+fn is_glob(entry: &str) -> bool {
+    entry.contains(['*', '?', '{'])
+}
+
+/// Add a discovered file to the file graph as a test, entry point, or plain
+/// file, noting whether it has side effects
+fn add_discovered_file(file_graph: &mut FileImportGraph, discovery: &FileDiscovery, path: &Path) {
+    if discovery.tests.iter().any(|test| test == path) {
+        file_graph.add_test_file(path.to_path_buf());
+    } else {
+        let is_entry_point = discovery.entry_points.iter().any(|entry| entry == path);
+        file_graph.add_file(path.to_path_buf(), is_entry_point);
+    }
+    file_graph.set_story(path, discovery.stories.iter().any(|story| story == path));
+    file_graph.set_side_effects(path, discovery.side_effects.iter().any(|file| file == path));
+    if let Some(names) = discovery.used_exports.get(path) {
+        file_graph.set_used_exports(path, names.clone());
+    }
+}
+
+/// Persist the workspace's graphs along with the hashes they were built from
+fn save_snapshot(
+    workspace: &Workspace,
+    files: HashMap<PathBuf, String>,
+    manifests: HashMap<PathBuf, String>,
+) -> Result<()> {
+    GraphSnapshot {
+        files,
+        manifests,
+        file_graph: workspace.file_graph.clone(),
+        symbol_graph: workspace.symbol_graph.clone(),
+        dependency_graph: workspace.dependency_graph.clone(),
+    }
+    .save(&workspace.root)
+}
+
+/// Entry points relative to the project root
+///
+/// Given entries apply to every package where they exist, and one that exists
+/// nowhere is kept as-is so the scanner reports it. Without any, each
+/// package's entries come from its package.json fields, falling back to
+/// `DEFAULT_ENTRY` where that exists.
+pub fn resolve_entry_points(
+    scanner: &WorkspaceScanner,
+    root: &Path,
+    packages: &[WorkspacePackage],
+    entries: &[String],
+) -> Vec<String> {
+    let in_package = |package: &WorkspacePackage, entry: &str| {
+        let dir = package.dir.strip_prefix(root).unwrap_or(&package.dir);
+        let candidate = dir.join(entry).to_string_lossy().to_string();
+        scanner.resolve_entry_point(&candidate).is_ok().then_some(candidate)
+    };
+
+    let mut entry_points = Vec::new();
+    if entries.is_empty() {
+        for package in packages {
+            let detected = package
+                .entry_points
+                .iter()
+                .filter_map(|entry| in_package(package, entry));
+            let before = entry_points.len();
+            entry_points.extend(detected);
+            if entry_points.len() == before {
+                entry_points.extend(in_package(package, DEFAULT_ENTRY));
+            }
+        }
+    } else {
+        for entry in entries {
+            let resolved: Vec<String> = packages
+                .iter()
+                .filter_map(|package| in_package(package, entry))
+                .collect();
+
+            if resolved.is_empty() {
+                entry_points.push(entry.clone());
+            } else {
+                entry_points.extend(resolved);
+            }
+        }
+    }
+
+    entry_points
+}
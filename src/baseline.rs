@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use sweepr::error::{PurgeError, Result};
+use sweepr::rules::AnalysisReport;
+
+/// Findings accepted as they are, e.g. when adopting sweepr in a large
+/// project, which later runs don't report again
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub findings: Vec<Entry>,
+}
+
+/// A finding of the baseline, by rule code, path relative to the project
+/// root, and name, so code moving within a file still matches
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Entry {
+    pub code: String,
+    pub path: PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+}
+
+impl Baseline {
+    /// Every finding of `report`, in the project under `root`
+    pub fn from_report(report: &AnalysisReport, root: &Path) -> Self {
+        let findings = report
+            .findings()
+            .into_iter()
+            .map(|finding| Entry {
+                code: finding.code.to_string(),
+                path: relative(&finding.path, root),
+                symbol: finding.symbol,
+            })
+            .collect();
+        Self { findings }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|_| PurgeError::FileNotFound(path.display().to_string()))?;
+        serde_json::from_str(&content)
+            .map_err(|e| PurgeError::Config(format!("Invalid baseline {}: {}", path.display(), e)))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::from)?;
+        std::fs::write(path, json + "\n")?;
+        Ok(())
+    }
+
+    /// Drop the findings of `report`, in the project under `root`, that the
+    /// baseline records, each entry matching at most one finding
+    pub fn apply(&self, report: &mut AnalysisReport, root: &Path) {
+        let mut remaining: HashMap<&Entry, usize> = HashMap::new();
+        for entry in &self.findings {
+            *remaining.entry(entry).or_default() += 1;
+        }
+
+        report.retain_findings(|code, path, symbol, _| {
+            let entry = Entry {
+                code: code.to_string(),
+                path: relative(path, root),
+                symbol: symbol.map(str::to_string),
+            };
+            match remaining.get_mut(&entry) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+    }
+}
+
+fn relative(path: &Path, root: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
@@ -0,0 +1,250 @@
+use crate::error::{PurgeError, Result};
+use crate::graph::{DependencyGraph, FileImportGraph, SymbolUsageGraph};
+use crate::parser::ParsedFile;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Directory, relative to the project root, holding the parse cache
+pub const CACHE_DIR: &str = ".sweepr/cache";
+
+/// Bump whenever `ParsedFile` or the parser's output changes
+const CACHE_VERSION: u32 = 34;
+
+/// Version of the oxc crates in Cargo.toml; a new parser may produce
+/// different results from the same source, so caches from another one are dropped
+pub const OXC_VERSION: &str = "0.33";
+
+/// Parses keyed by file path and content hash, in bincode
+const PARSE_FILE: &str = "parse.bin";
+
+/// Graphs from the last run, in bincode
+const GRAPHS_FILE: &str = "graphs.bin";
+
+/// Parse results from earlier runs, keyed by file path and content hash
+#[derive(Debug, Default)]
+pub struct ParseCache {
+    path: PathBuf,
+    entries: HashMap<PathBuf, CacheEntry>,
+    dirty: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    hash: String,
+    parsed: ParsedFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    sweepr: String,
+    oxc: String,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+/// Whether a cache file was written with the same schema, sweepr, and oxc
+fn is_current(version: u32, sweepr: &str, oxc: &str) -> bool {
+    version == CACHE_VERSION && sweepr == env!("CARGO_PKG_VERSION") && oxc == OXC_VERSION
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    bincode::serialize(value).map_err(|e| PurgeError::Io(std::io::Error::other(e)))
+}
+
+impl ParseCache {
+    /// Load the cache under `root`, starting empty when it is missing,
+    /// unreadable, or written by another version
+    pub fn load(root: &Path) -> Self {
+        let path = cache_dir(root).join(PARSE_FILE);
+
+        let entries = std::fs::read(&path)
+            .ok()
+            .and_then(|content| bincode::deserialize::<CacheFile>(&content).ok())
+            .filter(|file| is_current(file.version, &file.sweepr, &file.oxc))
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            entries,
+            dirty: false,
+        }
+    }
+
+    /// The cached parse of `path`, if its content hash still matches
+    pub fn get(&self, path: &Path, hash: &str) -> Option<&ParsedFile> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.hash == hash)
+            .map(|entry| &entry.parsed)
+    }
+
+    pub fn insert(&mut self, hash: String, parsed: ParsedFile) {
+        self.entries
+            .insert(parsed.path.clone(), CacheEntry { hash, parsed });
+        self.dirty = true;
+    }
+
+    /// Drop entries for files that are no longer part of the project
+    pub fn retain(&mut self, files: &[PathBuf]) {
+        let files: HashSet<&PathBuf> = files.iter().collect();
+        let before = self.entries.len();
+        self.entries.retain(|path, _| files.contains(path));
+        self.dirty |= self.entries.len() != before;
+    }
+
+    /// Write the cache back to disk if anything changed
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let file = CacheFile {
+            version: CACHE_VERSION,
+            sweepr: env!("CARGO_PKG_VERSION").to_string(),
+            oxc: OXC_VERSION.to_string(),
+            entries: self.entries.clone(),
+        };
+        std::fs::write(&self.path, encode(&file)?)?;
+
+        Ok(())
+    }
+}
+
+/// Analysis graphs from an earlier run, with the content hashes of the
+/// files, package.json manifests, and tool configs they were built from
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphSnapshot {
+    pub files: HashMap<PathBuf, String>,
+    pub manifests: HashMap<PathBuf, String>,
+    pub file_graph: FileImportGraph,
+    pub symbol_graph: SymbolUsageGraph,
+    pub dependency_graph: DependencyGraph,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile<T> {
+    version: u32,
+    sweepr: String,
+    oxc: String,
+    snapshot: T,
+}
+
+impl GraphSnapshot {
+    /// Load the snapshot under `root` if it was built from the same manifests
+    ///
+    /// Declared and tool-referenced dependencies come from the manifests, so any
+    /// change to them invalidates the whole snapshot. Changed source files are
+    /// up to the caller.
+    pub fn load(root: &Path, manifests: &HashMap<PathBuf, String>) -> Option<Self> {
+        let content = std::fs::read(Self::path(root)).ok()?;
+        let file: SnapshotFile<Self> = bincode::deserialize(&content).ok()?;
+
+        (is_current(file.version, &file.sweepr, &file.oxc) && &file.snapshot.manifests == manifests)
+            .then_some(file.snapshot)
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = Self::path(root);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let file = SnapshotFile {
+            version: CACHE_VERSION,
+            sweepr: env!("CARGO_PKG_VERSION").to_string(),
+            oxc: OXC_VERSION.to_string(),
+            snapshot: self,
+        };
+        std::fs::write(path, encode(&file)?)?;
+
+        Ok(())
+    }
+
+    fn path(root: &Path) -> PathBuf {
+        cache_dir(root).join(GRAPHS_FILE)
+    }
+}
+
+/// What the cache under a project root holds, for `sweepr cache stats`
+#[derive(Debug, Default)]
+pub struct CacheStats {
+    /// Files with a cached parse
+    pub parsed_files: usize,
+    /// Whether a graph snapshot exists
+    pub has_graphs: bool,
+    /// Whether the cache was written by this version of sweepr; a stale one
+    /// is rebuilt on the next run
+    pub is_current: bool,
+    pub bytes: u64,
+}
+
+/// The start of a `CacheFile`, read without decoding its parses: bincode
+/// writes a map's length ahead of its entries
+#[derive(Deserialize)]
+struct CacheHeader {
+    version: u32,
+    sweepr: String,
+    oxc: String,
+    entry_count: u64,
+}
+
+/// Directory holding the cache of the project at `root`
+pub fn cache_dir(root: &Path) -> PathBuf {
+    root.join(CACHE_DIR)
+}
+
+/// Summarize the cache under `root`, empty when there is none
+pub fn stats(root: &Path) -> CacheStats {
+    let dir = cache_dir(root);
+    let header = std::fs::read(dir.join(PARSE_FILE))
+        .ok()
+        .and_then(|content| bincode::deserialize::<CacheHeader>(&content).ok());
+
+    let bytes = std::fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    CacheStats {
+        parsed_files: header.as_ref().map_or(0, |header| header.entry_count as usize),
+        has_graphs: GraphSnapshot::path(root).is_file(),
+        is_current: header.is_some_and(|header| is_current(header.version, &header.sweepr, &header.oxc)),
+        bytes,
+    }
+}
+
+/// Delete the cache under `root`, returning whether there was one
+pub fn clear(root: &Path) -> Result<bool> {
+    let dir = cache_dir(root);
+    if !dir.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_dir_all(dir)?;
+    Ok(true)
+}
+
+/// Hex digest identifying a file's content
+pub fn content_hash(content: &[u8]) -> String {
+    blake3::hash(content).to_hex().to_string()
+}
+
+/// Content hashes of files, read in parallel
+pub fn hash_files(files: &[PathBuf]) -> Result<HashMap<PathBuf, String>> {
+    files
+        .par_iter()
+        .map(|path| {
+            let content = std::fs::read(path)?;
+            Ok((path.clone(), content_hash(&content)))
+        })
+        .collect()
+}
@@ -0,0 +1,68 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use sweepr::error::{PurgeError, Result};
+
+/// Files under `root` that differ from `git_ref`: committed, staged, and
+/// uncommitted changes, plus untracked files that aren't ignored
+pub fn changed_files(root: &Path, git_ref: &str) -> Result<HashSet<PathBuf>> {
+    let mut files = HashSet::new();
+    for args in [
+        &["diff", "--name-only", "--relative", git_ref][..],
+        &["ls-files", "--others", "--exclude-standard"][..],
+    ] {
+        files.extend(git(root, args)?.lines().map(|line| root.join(line)));
+    }
+    Ok(files)
+}
+
+/// A worktree of the repository checked out at a git ref in a temporary
+/// directory, removed again on drop
+pub struct Checkout {
+    repo: PathBuf,
+    dir: PathBuf,
+    /// The directory of the checkout corresponding to the one it was made from
+    pub root: PathBuf,
+}
+
+impl Checkout {
+    /// Check out `git_ref` of the repository containing `root`
+    pub fn new(root: &Path, git_ref: &str) -> Result<Self> {
+        let prefix = git(root, &["rev-parse", "--show-prefix"])?;
+        let dir = std::env::temp_dir().join(format!("sweepr-since-{}", std::process::id()));
+        let worktree = dir.to_string_lossy();
+        git(root, &["worktree", "add", "--detach", "--quiet", &worktree, git_ref])?;
+
+        Ok(Self {
+            repo: root.to_path_buf(),
+            root: dir.join(prefix.trim_end()),
+            dir,
+        })
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        let worktree = self.dir.to_string_lossy();
+        let _ = git(&self.repo, &["worktree", "remove", "--force", &worktree]);
+    }
+}
+
+/// Run git in `root`, returning its stdout
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .map_err(|e| PurgeError::Config(format!("Could not run git: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(PurgeError::Config(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -1,2 +1,298 @@
-// CLI is defined in main.rs using clap derive macros
-// This module exists for future CLI-related utilities
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use sweepr::reporter::GroupBy;
+use sweepr::rules::{find_rule, SortBy, RULE_CODES, RULE_NAMES};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(name = "sweepr")]
+#[command(about = "Blazing-fast dead code elimination for JavaScript and TypeScript", long_about = None)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Commands,
+}
+
+#[derive(Parser, Debug)]
+pub enum Commands {
+    /// Check for unused code (read-only, no modifications)
+    Check {
+        #[command(flatten)]
+        args: CheckArgs,
+    },
+
+    /// Fix unused code (safe modifications only)
+    Fix {
+        /// Allow dangerous operations (file deletion)
+        #[arg(long, name = "unsafe")]
+        allow_unsafe: bool,
+
+        #[command(flatten)]
+        args: CheckArgs,
+    },
+
+    /// Print the file import graph with entry points and unused files highlighted
+    Graph {
+        /// Graph format
+        #[arg(short, long, value_enum, default_value = "dot")]
+        format: GraphFormat,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+
+        /// Only include files under this directory
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// Only include files this file reaches through its imports
+        #[arg(long, value_name = "FILE")]
+        from: Option<PathBuf>,
+
+        /// Follow at most this many imports from `--from`, or from the entry points
+        #[arg(long, value_name = "N")]
+        depth: Option<usize>,
+
+        /// Write the graph to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Manage the parse and graph cache in .sweepr/cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// Inspect the project and write a starter sweepr.config.json
+    Init {
+        /// Write the config without asking
+        #[arg(short, long)]
+        yes: bool,
+
+        /// Replace an existing sweepr.config.json
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Explain why a file or export is considered used
+    Why {
+        /// A file path, or the name of an exported symbol
+        #[arg(value_name = "FILE_OR_SYMBOL")]
+        target: String,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Only check dependencies, printing depcheck-compatible JSON
+    Depcheck {
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Show the files importing each package.json dependency, and how often
+    Deps {
+        /// Only show this package; repeat for several
+        #[arg(long = "package", value_name = "NAME")]
+        packages: Vec<String>,
+
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Run a Language Server Protocol server on stdio, for editors to show findings live
+    Lsp {
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+
+    /// Keep the project's graphs in memory and answer JSON-RPC requests on stdio
+    Daemon {
+        /// Listen on this Unix socket instead of stdio
+        #[arg(long, value_name = "PATH")]
+        socket: Option<PathBuf>,
+
+        #[command(flatten)]
+        scan: ScanArgs,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum CacheAction {
+    /// Delete the cache, so the next run parses every file
+    Clear,
+
+    /// Show how many files are cached, its size, and whether it is current
+    Stats,
+
+    /// Print the cache directory
+    Path,
+}
+
+/// Options shared by every command that runs the analysis
+#[derive(Args, Debug, Clone)]
+pub struct CheckArgs {
+    /// Deprecated alias for `--format json`
+    #[arg(short, long, hide = true)]
+    pub json: bool,
+
+    /// Report format: cli, json, html, csv, compact, eslint, sarif, ts-prune, or a registered custom format
+    #[arg(short, long)]
+    pub format: Option<String>,
+
+    /// Write the report to a file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Print findings only, without banners, progress, or emoji
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Group findings in the CLI report, with per-group subtotals
+    #[arg(long, value_enum)]
+    pub group_by: Option<GroupBy>,
+
+    /// Order of findings in every format; `rule` keeps each rule's findings
+    /// together, ordered by path
+    #[arg(long, value_enum, default_value_t = SortBy::Rule)]
+    pub sort: SortBy,
+
+    /// Only print the number of findings per rule and the total
+    #[arg(long)]
+    pub summary: bool,
+
+    /// Show the edit `sweepr fix` would apply alongside each finding
+    #[arg(long)]
+    pub show_fixes: bool,
+
+    /// Exit with status 1 when findings of these rules, by name or code, exist
+    /// (default: any finding)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(
+            RULE_NAMES.into_iter().zip(RULE_CODES).map(|(name, code)| PossibleValue::new(name).alias(code))
+        )
+    )]
+    pub fail_on: Vec<String>,
+
+    /// Only report findings in files matching these globs, relative to the
+    /// project root; the whole project is still analyzed
+    #[arg(long, value_name = "GLOB")]
+    pub filter: Vec<String>,
+
+    /// Only report findings in files changed relative to this git ref, e.g.
+    /// `origin/main`; the whole project is still analyzed
+    #[arg(long, value_name = "REF")]
+    pub changed: Option<String>,
+
+    /// Only report findings introduced since this git ref, e.g. `origin/main`,
+    /// by analyzing a checkout of it as well
+    #[arg(long, value_name = "REF")]
+    pub since: Option<String>,
+
+    /// Don't report findings recorded in this file, matched by rule code,
+    /// path, and name, e.g. ones accepted when adopting sweepr
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Record every finding of this run in the `--baseline` file, replacing
+    /// its content, instead of reporting them
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// Only exit with status 1 when more than this many `--fail-on` findings exist
+    #[arg(long, value_name = "N")]
+    pub max_issues: Option<usize>,
+
+    /// Print how long scanning, parsing, graph building, each rule, and
+    /// reporting took, and include the timings in the JSON report
+    #[arg(long, conflicts_with = "watch")]
+    pub timing: bool,
+
+    /// Keep running and re-report whenever source files change
+    #[arg(short, long, conflicts_with = "stdin")]
+    pub watch: bool,
+
+    /// Read the content of `--stdin-path` from stdin, e.g. an unsaved editor
+    /// buffer, and only report findings in that file
+    #[arg(long, requires = "stdin_path")]
+    pub stdin: bool,
+
+    /// Project file whose content `--stdin` provides
+    #[arg(long, value_name = "PATH", requires = "stdin")]
+    pub stdin_path: Option<PathBuf>,
+
+    /// Re-check one file against the graphs cached by the last run, re-parsing
+    /// only it, and only report findings in it
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["stdin", "watch"])]
+    pub file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub scan: ScanArgs,
+}
+
+/// Options controlling which files are scanned and how they are parsed
+#[derive(Args, Debug, Clone)]
+pub struct ScanArgs {
+    /// Directories to scan, relative to the project root (default: the whole project)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+
+    /// Custom entry points
+    #[arg(short, long)]
+    pub entry: Vec<String>,
+
+    /// Parse every file instead of reusing results cached in `.sweepr/cache`
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Only scan files matching these globs, e.g. `packages/web/**`
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<String>,
+
+    /// Skip files matching these globs, in addition to the config `ignore` list
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<String>,
+
+    /// Descend into symlinked directories (each real directory is scanned once)
+    #[arg(long)]
+    pub follow_symlinks: bool,
+
+    /// Threads used to parse files, overriding the config (default: one per CPU)
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub threads: Option<u64>,
+
+    /// Apply a profile of the config file, e.g. `ci`, over its other settings
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+}
+
+impl CheckArgs {
+    /// Whether a finding of the given rule should make the run fail
+    pub fn fails_on(&self, rule: &str) -> bool {
+        self.fail_on.is_empty() || self.fail_on.iter().any(|r| find_rule(r) == Some(rule))
+    }
+
+    /// Name of the report format selected by `--format` or `--json`
+    pub fn format(&self) -> &str {
+        match &self.format {
+            Some(format) => format,
+            None if self.json => "json",
+            None => "cli",
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Graphviz DOT, render with `dot -Tsvg`
+    Dot,
+    /// Mermaid `graph TD`, for embedding in Markdown
+    Mermaid,
+    /// Files and imports as JSON, for scripts and debugging resolution
+    Json,
+}
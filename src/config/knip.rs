@@ -0,0 +1,121 @@
+use super::Config;
+use crate::error::{PurgeError, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Config files knip reads, in the order it looks for them
+const KNIP_FILES: [&str; 2] = ["knip.json", ".knip.json"];
+
+/// The parts of a knip config that have a sweepr equivalent
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KnipConfig {
+    #[serde(default)]
+    entry: Patterns,
+    #[serde(default)]
+    project: Patterns,
+    #[serde(default)]
+    ignore: Patterns,
+    #[serde(default)]
+    ignore_dependencies: Vec<String>,
+    /// Per-package settings keyed by directory, or glob of directories
+    #[serde(default)]
+    workspaces: BTreeMap<String, KnipConfig>,
+}
+
+/// A pattern or list of patterns, as knip accepts either
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Patterns {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Default for Patterns {
+    fn default() -> Self {
+        Patterns::Many(Vec::new())
+    }
+}
+
+impl Patterns {
+    fn iter(&self) -> impl Iterator<Item = &str> {
+        let patterns = match self {
+            Patterns::One(pattern) => std::slice::from_ref(pattern),
+            Patterns::Many(patterns) => patterns.as_slice(),
+        };
+        // A trailing `!` marks knip's production-mode patterns
+        patterns.iter().map(|pattern| pattern.trim_end_matches('!'))
+    }
+}
+
+/// Load the knip config of the project in `root`, mapped onto a sweepr config
+///
+/// Looks in `knip.json`, `.knip.json`, then the `knip` key of package.json.
+pub fn find_and_load(root: &Path) -> Result<Option<Config>> {
+    for name in KNIP_FILES {
+        let path = root.join(name);
+        if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            let knip = serde_json::from_str(&content)
+                .map_err(|e| PurgeError::Config(format!("Invalid {}: {}", path.display(), e)))?;
+            return Ok(Some(to_config(knip)));
+        }
+    }
+
+    let manifest = root.join("package.json");
+    let Ok(content) = std::fs::read_to_string(&manifest) else {
+        return Ok(None);
+    };
+    // An unreadable package.json is reported by workspace discovery instead
+    let Ok(mut package) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Ok(None);
+    };
+    match package.get_mut("knip").map(serde_json::Value::take) {
+        Some(knip) => {
+            let knip = serde_json::from_value(knip).map_err(|e| {
+                PurgeError::Config(format!("Invalid knip config in {}: {}", manifest.display(), e))
+            })?;
+            Ok(Some(to_config(knip)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Map knip's settings onto sweepr's defaults
+///
+/// Entries become entry globs and `project` the include globs, with negated
+/// `project` patterns ignored instead. Workspace settings apply under their
+/// directory.
+fn to_config(knip: KnipConfig) -> Config {
+    let mut config = Config::default();
+    add_patterns(&mut config, "", &knip);
+    for (dir, workspace) in &knip.workspaces {
+        let prefix = match dir.trim_end_matches('/') {
+            "" | "." => String::new(),
+            dir => format!("{}/", dir),
+        };
+        add_patterns(&mut config, &prefix, workspace);
+    }
+    config
+}
+
+fn add_patterns(config: &mut Config, prefix: &str, knip: &KnipConfig) {
+    let prefixed = |pattern: &str| format!("{}{}", prefix, pattern);
+
+    // Negated entries only narrow knip's own default entries
+    config.entry.extend(
+        knip.entry
+            .iter()
+            .filter(|pattern| !pattern.starts_with('!'))
+            .map(prefixed),
+    );
+    for pattern in knip.project.iter() {
+        match pattern.strip_prefix('!') {
+            Some(negated) => config.ignore.push(prefixed(negated)),
+            None => config.include.push(prefixed(pattern)),
+        }
+    }
+    config.ignore.extend(knip.ignore.iter().map(prefixed));
+    config.ignore_dependencies.extend(knip.ignore_dependencies.iter().cloned());
+}
@@ -1,51 +1,226 @@
+mod knip;
+
 use crate::error::{PurgeError, Result};
+use crate::rules::find_rule;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Entry point files, or globs of them
     #[serde(default)]
     pub entry: Vec<String>,
 
     #[serde(default)]
     pub ignore: Vec<String>,
 
+    /// Only scan files matching these globs; every file when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+
+    /// Dependencies never reported as unused or unlisted, by name or glob
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_dependencies: Vec<String>,
+
+    /// Globs of test files, which are entry points whose imports don't count as production use
+    #[serde(default = "default_tests")]
+    pub tests: Vec<String>,
+
+    /// Globs of Storybook stories and config, which are entry points too
+    #[serde(default = "default_stories")]
+    pub stories: Vec<String>,
+
     #[serde(default)]
     pub rules: RulesConfig,
 
     #[serde(default)]
     pub framework: Option<String>,
+
+    /// Files larger than this many bytes are skipped
+    #[serde(default = "default_max_file_size")]
+    pub max_file_size: Option<u64>,
+
+    /// Skip files that look minified or bundled
+    #[serde(default = "default_true")]
+    pub skip_minified: bool,
+
+    /// Threads used to parse files; one per CPU when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<usize>,
+
+    /// Named overrides of the settings above, e.g. a `ci` profile turning
+    /// rules on, selected with `--profile`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Value>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Which rules run; all of them by default
+///
+/// Each rule is keyed by its name or by its code, e.g. `SWP002` for
+/// `unused_exports`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RulesConfig {
-    #[serde(default = "default_true")]
+    #[serde(default = "default_true", alias = "SWP001")]
     pub unused_deps: bool,
 
-    #[serde(default = "default_true")]
+    #[serde(default = "default_true", alias = "SWP002")]
     pub unused_exports: bool,
 
-    #[serde(default = "default_true")]
+    /// Unused interfaces and type aliases, reported apart from runtime exports
+    #[serde(default = "default_true", alias = "SWP003")]
+    pub unused_types: bool,
+
+    /// Exports only their own file uses, reported apart from unused exports;
+    /// when off, they are reported as unused exports or types
+    #[serde(default = "default_true", alias = "SWP013")]
+    pub module_only_exports: bool,
+
+    #[serde(default = "default_true", alias = "SWP004")]
     pub unused_files: bool,
+
+    #[serde(default = "default_true", alias = "SWP005")]
+    pub duplicate_exports: bool,
+
+    #[serde(default = "default_true", alias = "SWP006")]
+    pub unlisted_deps: bool,
+
+    #[serde(default = "default_true", alias = "SWP007")]
+    pub unresolved_imports: bool,
+
+    /// Exports whose only importers are test files
+    #[serde(default = "default_true", alias = "SWP008")]
+    pub test_only_exports: bool,
+
+    /// Exports whose only importers are Storybook stories; off unless strict
+    #[serde(default, alias = "SWP011")]
+    pub story_only_exports: bool,
+
+    /// Unused `peerDependencies`, reported apart from regular dependencies
+    #[serde(default = "default_true", alias = "SWP010")]
+    pub unused_peers: bool,
+
+    /// Unused `@types` packages whose runtime package is no longer declared
+    #[serde(default = "default_true", alias = "SWP009")]
+    pub orphaned_types: bool,
+
+    /// `dependencies` only ever imported with `import type`, which could be
+    /// `devDependencies`
+    #[serde(default = "default_true", alias = "SWP012")]
+    pub type_only_deps: bool,
+
+    /// `#name` and `private` class members their class never reads
+    #[serde(default = "default_true", alias = "SWP014")]
+    pub unused_private_members: bool,
+
+    /// Local variables, functions, and classes nothing reads; off by default
+    #[serde(default, alias = "SWP015")]
+    pub unused_locals: bool,
+
+    /// Import bindings their file never references
+    #[serde(default = "default_true", alias = "SWP016")]
+    pub unused_imports: bool,
+
+    /// Reachable files none of whose exports are used
+    #[serde(default = "default_true", alias = "SWP017")]
+    pub removable_files: bool,
+
+    /// Never report exports tagged `/** @public */`; `@internal` ones stay reportable
+    #[serde(default = "default_true")]
+    pub jsdoc_tags: bool,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            unused_deps: true,
+            unused_exports: true,
+            unused_types: true,
+            module_only_exports: true,
+            unused_files: true,
+            duplicate_exports: true,
+            unlisted_deps: true,
+            unresolved_imports: true,
+            test_only_exports: true,
+            story_only_exports: false,
+            unused_peers: true,
+            orphaned_types: true,
+            type_only_deps: true,
+            unused_private_members: true,
+            unused_locals: false,
+            unused_imports: true,
+            removable_files: true,
+            jsdoc_tags: true,
+        }
+    }
+}
+
+impl RulesConfig {
+    /// Only the enabled rules about package.json dependencies
+    pub fn dependencies_only(&self) -> Self {
+        Self {
+            unused_deps: self.unused_deps,
+            unlisted_deps: self.unlisted_deps,
+            unused_peers: self.unused_peers,
+            orphaned_types: self.orphaned_types,
+            type_only_deps: self.type_only_deps,
+            unused_exports: false,
+            unused_types: false,
+            module_only_exports: false,
+            unused_files: false,
+            duplicate_exports: false,
+            unresolved_imports: false,
+            test_only_exports: false,
+            story_only_exports: false,
+            unused_private_members: false,
+            unused_locals: false,
+            unused_imports: false,
+            removable_files: false,
+            jsdoc_tags: self.jsdoc_tags,
+        }
+    }
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_tests() -> Vec<String> {
+    vec![
+        "**/*.test.*".to_string(),
+        "**/*.spec.*".to_string(),
+        "**/__tests__/**".to_string(),
+    ]
+}
+
+fn default_stories() -> Vec<String> {
+    vec![
+        "**/*.stories.*".to_string(),
+        "**/*.story.*".to_string(),
+        ".storybook/**".to_string(),
+    ]
+}
+
+fn default_max_file_size() -> Option<u64> {
+    Some(1024 * 1024)
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
-            entry: vec!["src/index.ts".to_string()],
-            ignore: vec![
-                "**/*.test.ts".to_string(),
-                "**/*.test.js".to_string(),
-                "**/*.spec.ts".to_string(),
-                "**/*.spec.js".to_string(),
-                "**/node_modules/**".to_string(),
-            ],
+            entry: Vec::new(),
+            ignore: vec!["**/node_modules/**".to_string()],
+            include: Vec::new(),
+            ignore_dependencies: Vec::new(),
+            tests: default_tests(),
+            stories: default_stories(),
             rules: RulesConfig::default(),
             framework: None,
+            max_file_size: default_max_file_size(),
+            skip_minified: true,
+            threads: None,
+            profiles: BTreeMap::new(),
         }
     }
 }
@@ -58,11 +233,15 @@ impl Config {
         }
 
         let content = std::fs::read_to_string(path)
-            .map_err(|e| PurgeError::Io(e))?;
+            .map_err(PurgeError::Io)?;
 
         // Try to parse as JSON
-        if let Ok(config) = serde_json::from_str::<Config>(&content) {
-            return Ok(config);
+        match serde_json::from_str::<Config>(&content) {
+            Ok(config) => return Ok(config),
+            Err(e) if path.extension().is_some_and(|ext| ext == "json") => {
+                return Err(PurgeError::Config(format!("Invalid {}: {}", path.display(), e)));
+            }
+            Err(_) => {}
         }
 
         // If JSON fails, try to extract JSON from a TypeScript config
@@ -71,10 +250,43 @@ impl Config {
         Ok(Self::default())
     }
 
+    /// The config with the settings of profile `name` applied
+    ///
+    /// Objects such as `rules` are merged key by key; any other setting the
+    /// profile gives, lists included, replaces the base one.
+    pub fn with_profile(self, name: &str) -> Result<Self> {
+        let Some(profile) = self.profiles.get(name) else {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            let defined = if known.is_empty() { "no profiles".to_string() } else { known.join(", ") };
+            return Err(PurgeError::Config(format!("Unknown profile '{}': the config defines {}", name, defined)));
+        };
+        if !profile.is_object() {
+            return Err(PurgeError::Config(format!("Profile '{}' must be an object", name)));
+        }
+
+        let mut profile = profile.clone();
+        if let Some(Value::Object(rules)) = profile.get_mut("rules") {
+            // The base rules serialize under their field names, so a code key
+            // such as `SWP002` would otherwise sit next to `unused_exports`
+            *rules = std::mem::take(rules)
+                .into_iter()
+                .map(|(key, value)| match find_rule(&key) {
+                    Some(rule) => (rule.replace('-', "_"), value),
+                    None => (key, value),
+                })
+                .collect();
+        }
+
+        let mut merged = serde_json::to_value(&self)
+            .map_err(|e| PurgeError::Config(format!("Could not apply profile '{}': {}", name, e)))?;
+        merge(&mut merged, &profile);
+        serde_json::from_value(merged).map_err(|e| PurgeError::Config(format!("Invalid profile '{}': {}", name, e)))
+    }
+
     /// Find and load config file from the current directory
     pub fn find_and_load() -> Result<Self> {
         let current_dir = std::env::current_dir()
-            .map_err(|e| PurgeError::Io(e))?;
+            .map_err(PurgeError::Io)?;
 
         // Check for sweepr.config.json
         let json_config = current_dir.join("sweepr.config.json");
@@ -88,6 +300,28 @@ impl Config {
             return Self::load_from_file(&ts_config);
         }
 
+        // Projects migrating from knip keep working from their knip config
+        if let Some(config) = knip::find_and_load(&current_dir)? {
+            return Ok(config);
+        }
+
         Ok(Self::default())
     }
 }
+
+/// Merge `overrides` into `base`, recursing into objects both have
+fn merge(base: &mut Value, overrides: &Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides.clone(),
+    }
+}
@@ -0,0 +1,175 @@
+use crate::explain;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use sweepr::analyzer::Workspace;
+use sweepr::error::Result;
+use sweepr::resolver;
+
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+
+/// Builds the workspace afresh, for when its manifests change
+type Rebuild<'a> = dyn Fn() -> Result<Workspace> + 'a;
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Answer JSON-RPC requests with the workspace kept in memory, one JSON
+/// message per line, until a client sends `shutdown`
+///
+/// Requests are read from stdin, or from each client connecting to `socket`
+/// in turn. Without a socket, the end of stdin also stops the daemon.
+/// Invalidating a manifest replaces the workspace with one from `rebuild`.
+pub fn serve(workspace: &mut Workspace, socket: Option<&Path>, rebuild: &Rebuild<'_>) -> Result<()> {
+    match socket {
+        Some(socket) => serve_socket(workspace, socket, rebuild),
+        None => {
+            let stdout = std::io::stdout();
+            serve_stream(workspace, rebuild, std::io::stdin().lock(), stdout.lock())?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(unix)]
+fn serve_socket(workspace: &mut Workspace, socket: &Path, rebuild: &Rebuild<'_>) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixListener;
+
+    // A socket left behind by a daemon that didn't shut down cleanly
+    if std::fs::metadata(socket).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        std::fs::remove_file(socket)?;
+    }
+    let listener = UnixListener::bind(socket)?;
+    eprintln!("👂 Listening on {}", socket.display());
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let reader = BufReader::new(stream.try_clone()?);
+        // A client hanging up mid-request doesn't stop the daemon
+        match serve_stream(workspace, rebuild, reader, stream) {
+            Ok(true) => break,
+            Ok(false) => {}
+            Err(e) => eprintln!("⚠️  {}", e),
+        }
+    }
+
+    std::fs::remove_file(socket)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_socket(_workspace: &mut Workspace, _socket: &Path, _rebuild: &Rebuild<'_>) -> Result<()> {
+    Err(sweepr::error::PurgeError::Config("--socket is only supported on Unix".to_string()))
+}
+
+/// Answer each request line of `reader` on `writer`, returning whether a
+/// client asked to shut down
+fn serve_stream(
+    workspace: &mut Workspace,
+    rebuild: &Rebuild<'_>,
+    reader: impl BufRead,
+    mut writer: impl Write,
+) -> Result<bool> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (response, shutdown) = respond(workspace, rebuild, &line);
+        if let Some(response) = response {
+            writeln!(writer, "{}", response)?;
+            writer.flush()?;
+        }
+        if shutdown {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Response to one request line, if it wants one, and whether it asked to shut down
+fn respond(workspace: &mut Workspace, rebuild: &Rebuild<'_>, line: &str) -> (Option<Value>, bool) {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => return (Some(error(Value::Null, PARSE_ERROR, e.to_string())), false),
+    };
+    let request: RpcRequest = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(e) => return (Some(error(Value::Null, INVALID_REQUEST, e.to_string())), false),
+    };
+
+    let shutdown = request.method == "shutdown";
+    let result = match request.method.as_str() {
+        "analyze" => analyze(workspace),
+        "why" => why(workspace, &request.params),
+        "invalidate" => invalidate(workspace, rebuild, &request.params),
+        "shutdown" => Ok(Value::Null),
+        method => Err((METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+    };
+
+    let response = request.id.map(|id| match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error(id, code, message),
+    });
+    (response, shutdown)
+}
+
+type MethodResult = std::result::Result<Value, (i64, String)>;
+
+/// The report of the rules enabled in the config
+fn analyze(workspace: &Workspace) -> MethodResult {
+    let mut report = workspace.analyze();
+    // Build timings are from when the daemon started
+    report.timings.clear();
+    serde_json::to_value(report).map_err(|e| (INTERNAL_ERROR, e.to_string()))
+}
+
+/// Why `target`, a file or exported symbol, is kept
+fn why(workspace: &Workspace, params: &Value) -> MethodResult {
+    let target = string_param(params, "target")?;
+    Ok(match explain(workspace, target) {
+        Ok(explanation) => json!({ "found": true, "explanation": explanation }),
+        Err(message) => json!({ "found": false, "explanation": message }),
+    })
+}
+
+/// Re-read `file`, relative to the project root, after it was created,
+/// changed, or deleted
+fn invalidate(workspace: &mut Workspace, rebuild: &Rebuild<'_>, params: &Value) -> MethodResult {
+    let file = string_param(params, "file")?;
+    let changed = HashSet::from([resolver::normalize(&workspace.root.join(file))]);
+    let affected = if workspace.manifests_changed(&changed) {
+        *workspace = rebuild().map_err(|e| (INTERNAL_ERROR, e.to_string()))?;
+        true
+    } else {
+        workspace
+            .apply_changes(&changed)
+            .map_err(|e| (INTERNAL_ERROR, e.to_string()))?
+    };
+    Ok(json!({ "affected": affected }))
+}
+
+fn string_param<'a>(params: &'a Value, name: &str) -> std::result::Result<&'a str, (i64, String)> {
+    params[name]
+        .as_str()
+        .ok_or_else(|| (INVALID_PARAMS, format!("Missing string parameter '{}'", name)))
+}
+
+fn error(id: Value, code: i64, message: String) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
@@ -0,0 +1,72 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use sweepr::analyzer::Workspace;
+use sweepr::graph::DependencyKind;
+use sweepr::rules::AnalysisReport;
+
+/// Dependency findings in the shape of depcheck's `--json` output
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepcheckReport {
+    /// Unused `dependencies`
+    pub dependencies: Vec<String>,
+    /// Unused `devDependencies`
+    pub dev_dependencies: Vec<String>,
+    /// Imported packages no package.json declares, with the files importing them
+    pub missing: BTreeMap<String, BTreeSet<PathBuf>>,
+    /// Every imported package with the files importing it
+    pub using: BTreeMap<String, BTreeSet<PathBuf>>,
+    /// Files that failed to parse, with the parser's message
+    pub invalid_files: BTreeMap<PathBuf, String>,
+    pub invalid_dirs: BTreeMap<String, String>,
+}
+
+impl DepcheckReport {
+    /// Collect the report's dependency findings and the packages the workspace imports
+    pub fn new(workspace: &Workspace, report: &AnalysisReport) -> Self {
+        let mut dependencies = BTreeSet::new();
+        let mut dev_dependencies = BTreeSet::new();
+        // Orphaned `@types` packages are as unused as any other to depcheck
+        for dep in report.unused_dependencies.iter().chain(&report.orphaned_types) {
+            match dep.kind {
+                DependencyKind::DevDependencies => dev_dependencies.insert(dep.name.clone()),
+                _ => dependencies.insert(dep.name.clone()),
+            };
+        }
+
+        let mut missing: BTreeMap<String, BTreeSet<PathBuf>> = BTreeMap::new();
+        for dep in &report.unlisted_dependencies {
+            missing.entry(dep.name.clone()).or_default().insert(dep.file.clone());
+        }
+
+        let mut using = missing.clone();
+        let file_graph = &workspace.file_graph;
+        for info in workspace.dependency_graph.dependencies.values() {
+            if !info.import_locations.is_empty() {
+                using
+                    .entry(file_graph.name(info.name).to_string())
+                    .or_default()
+                    .extend(info.import_locations.iter().map(|&file| file_graph.path(file).to_path_buf()));
+            }
+        }
+
+        Self {
+            dependencies: dependencies.into_iter().collect(),
+            dev_dependencies: dev_dependencies.into_iter().collect(),
+            missing,
+            using,
+            invalid_files: report
+                .parse_failures
+                .iter()
+                .map(|failure| (failure.path.clone(), failure.message.clone()))
+                .collect(),
+            invalid_dirs: BTreeMap::new(),
+        }
+    }
+
+    /// Whether depcheck would fail: something is unused or missing
+    pub fn has_issues(&self) -> bool {
+        !self.dependencies.is_empty() || !self.dev_dependencies.is_empty() || !self.missing.is_empty()
+    }
+}
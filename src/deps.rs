@@ -0,0 +1,98 @@
+use serde::Serialize;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use sweepr::analyzer::Workspace;
+use sweepr::graph::DependencyKind;
+
+/// How a declared package is used: the files importing it and how often
+#[derive(Debug, Serialize)]
+pub struct DependencyUsage {
+    pub name: String,
+    pub version: String,
+    /// package.json declaring the dependency
+    pub manifest: PathBuf,
+    pub kind: DependencyKind,
+    /// Imports across every file
+    pub imports: usize,
+    pub files: Vec<FileUsage>,
+}
+
+/// A file importing a package
+#[derive(Debug, Serialize)]
+pub struct FileUsage {
+    pub path: PathBuf,
+    pub imports: usize,
+    /// Only imported with `import type`, leaving nothing of it at runtime
+    pub type_only: bool,
+}
+
+/// Usage of every dependency the workspace's package.json files declare, or
+/// only of the `packages` named, ordered by manifest and name
+pub fn usage(workspace: &Workspace, packages: &[String]) -> Vec<DependencyUsage> {
+    let file_graph = &workspace.file_graph;
+    let mut usages: Vec<DependencyUsage> = workspace
+        .dependency_graph
+        .dependencies
+        .values()
+        .filter(|info| packages.is_empty() || packages.iter().any(|name| name == file_graph.name(info.name)))
+        .map(|info| {
+            let mut files: Vec<FileUsage> = info
+                .import_locations
+                .iter()
+                .map(|file| FileUsage {
+                    path: file_graph.path(*file).to_path_buf(),
+                    imports: info.import_counts.get(file).copied().unwrap_or(1),
+                    type_only: !info.runtime_locations.contains(file),
+                })
+                .collect();
+            files.sort_by(|a, b| b.imports.cmp(&a.imports).then_with(|| a.path.cmp(&b.path)));
+
+            DependencyUsage {
+                name: file_graph.name(info.name).to_string(),
+                version: info.version.clone(),
+                manifest: file_graph.path(info.manifest).to_path_buf(),
+                kind: info.kind,
+                imports: files.iter().map(|file| file.imports).sum(),
+                files,
+            }
+        })
+        .collect();
+
+    usages.sort_by(|a, b| (&a.manifest, &a.name).cmp(&(&b.manifest, &b.name)));
+    usages
+}
+
+/// One line per dependency with its totals, then one per importing file,
+/// with paths relative to `root`
+pub fn render(usages: &[DependencyUsage], root: &Path) -> String {
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).display().to_string();
+    let mut out = String::new();
+
+    for usage in usages {
+        let _ = write!(
+            out,
+            "{}@{} ({} in {}): ",
+            usage.name,
+            usage.version,
+            usage.kind.field(),
+            relative(&usage.manifest)
+        );
+        let _ = match usage.files.len() {
+            0 => writeln!(out, "not imported"),
+            files => writeln!(
+                out,
+                "{} {} in {} {}",
+                usage.imports,
+                if usage.imports == 1 { "import" } else { "imports" },
+                files,
+                if files == 1 { "file" } else { "files" }
+            ),
+        };
+        for file in &usage.files {
+            let types = if file.type_only { ", types only" } else { "" };
+            let _ = writeln!(out, "  {} ({}{})", relative(&file.path), file.imports, types);
+        }
+    }
+
+    out
+}
@@ -18,4 +18,28 @@ pub enum PurgeError {
     InvalidEntryPoint(String),
 }
 
+/// Exit status when a file of the project could not be read or parsed
+pub const EXIT_PARSE_ERROR: u8 = 2;
+
+/// Exit status for invalid configuration or command-line arguments
+pub const EXIT_CONFIG_ERROR: u8 = 3;
+
+/// Exit status when sweepr itself failed to read or write a file, e.g. the
+/// `--output` report or the cache
+pub const EXIT_IO_ERROR: u8 = 4;
+
+impl PurgeError {
+    /// Exit status reporting this error, so scripts can tell a failed run
+    /// apart from one that found unused code (status 1)
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            PurgeError::Io(_) => EXIT_IO_ERROR,
+            PurgeError::ParseError { .. } => EXIT_PARSE_ERROR,
+            PurgeError::Config(_)
+            | PurgeError::FileNotFound(_)
+            | PurgeError::InvalidEntryPoint(_) => EXIT_CONFIG_ERROR,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, PurgeError>;
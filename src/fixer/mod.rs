@@ -0,0 +1,257 @@
+use crate::rules::{UnusedDependency, UnusedExport, UnusedFile, UnusedImport};
+use crate::source;
+use std::path::PathBuf;
+
+/// Declaration keywords that may sit between `export` and an exported name
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "abstract", "async", "class", "const", "declare", "default", "enum", "function",
+    "function*", "interface", "let", "namespace", "type", "var",
+];
+
+/// A replacement of a byte range in a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub span: (usize, usize),
+    pub replacement: String,
+}
+
+/// A change the fixer would make to resolve one finding
+#[derive(Debug, Clone)]
+pub enum Fix {
+    /// Rewrite parts of a file; edits are sorted and non-overlapping
+    Edit {
+        path: PathBuf,
+        source: String,
+        edits: Vec<Edit>,
+    },
+    /// Remove the whole file (only planned with `--unsafe`)
+    DeleteFile { path: PathBuf },
+}
+
+/// Plans the edits that resolve findings
+pub struct Fixer {
+    allow_unsafe: bool,
+}
+
+impl Fixer {
+    pub fn new(allow_unsafe: bool) -> Self {
+        Self { allow_unsafe }
+    }
+
+    /// Drop the `export` keyword (or the specifier in `export { .. }`), keeping the declaration
+    ///
+    /// This is the whole fix for a module-only export, which its file still uses.
+    pub fn fix_unused_export(&self, export: &UnusedExport) -> Option<Fix> {
+        let (source, edit) = source::with_source(&export.file, |source| {
+            let edit = remove_export(source, export.span)?;
+            Some((source.to_string(), edit))
+        })
+        .ok()??;
+
+        Some(Fix::Edit {
+            path: export.file.clone(),
+            source,
+            edits: vec![edit],
+        })
+    }
+
+    /// Remove the specifier from its `import`, or the whole declaration when
+    /// it is the only one
+    pub fn fix_unused_import(&self, import: &UnusedImport) -> Option<Fix> {
+        let (source, edit) = source::with_source(&import.file, |source| {
+            let edit = match import.statement {
+                Some(statement) => remove_statement(source, statement)?,
+                None => remove_list_item(source, import.span)?,
+            };
+            Some((source.to_string(), edit))
+        })
+        .ok()??;
+
+        Some(Fix::Edit {
+            path: import.file.clone(),
+            source,
+            edits: vec![edit],
+        })
+    }
+
+    /// Delete files that nothing imports, which is only allowed in unsafe mode
+    ///
+    /// That includes files declared with side effects, which a bundler or a
+    /// bare `import './file'` elsewhere may still load.
+    pub fn fix_unused_file(&self, file: &UnusedFile) -> Option<Fix> {
+        if !self.allow_unsafe {
+            return None;
+        }
+
+        Some(Fix::DeleteFile {
+            path: file.path.clone(),
+        })
+    }
+
+    /// Remove the dependency's entry from the package.json declaring it
+    pub fn fix_unused_dependency(&self, dep: &UnusedDependency) -> Option<Fix> {
+        let path = dep.manifest.clone();
+        let source = std::fs::read_to_string(&path).ok()?;
+        let edits = remove_json_entry(&source, dep.kind.field(), &dep.name)?;
+
+        Some(Fix::Edit {
+            path,
+            source,
+            edits,
+        })
+    }
+}
+
+fn remove_export(source: &str, span: (usize, usize)) -> Option<Edit> {
+    let before = source.get(..span.0)?;
+
+    // `export { a, b }`: remove the specifier together with one adjacent comma
+    let trimmed = before.trim_end();
+    if trimmed.ends_with('{') || trimmed.ends_with(',') {
+        return remove_list_item(source, span);
+    }
+
+    // `export function name`, `export default class Name`, ...
+    let export_start = before.rfind("export")?;
+    let between = &before[export_start + "export".len()..];
+    let is_declaration = between.starts_with(char::is_whitespace)
+        && between
+            .split_whitespace()
+            .all(|word| DECLARATION_KEYWORDS.contains(&word));
+    if !is_declaration {
+        return None;
+    }
+
+    let mut end = export_start + "export".len();
+    end += between.len() - between.trim_start().len();
+    if between.split_whitespace().next() == Some("default") {
+        end += "default".len();
+        let rest = &source[end..];
+        end += rest.len() - rest.trim_start().len();
+    }
+
+    Some(Edit {
+        span: (export_start, end),
+        replacement: String::new(),
+    })
+}
+
+/// Remove an item of a comma-separated list, e.g. `b` of `{ a, b }` or `a`
+/// of `import a, { b }`, together with one adjacent comma
+fn remove_list_item(source: &str, span: (usize, usize)) -> Option<Edit> {
+    let after = source.get(span.1..)?;
+    let following = after.len() - after.trim_start().len();
+    if after.trim_start().starts_with(',') {
+        let comma_end = span.1 + following + 1;
+        let rest = &source[comma_end..];
+        let trailing_space = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+        return Some(Edit {
+            span: (span.0, comma_end + trailing_space),
+            replacement: String::new(),
+        });
+    }
+    let trimmed = source.get(..span.0)?.trim_end();
+    if let Some(stripped) = trimmed.strip_suffix(',') {
+        return Some(Edit {
+            span: (stripped.len(), span.1),
+            replacement: String::new(),
+        });
+    }
+    Some(Edit {
+        span,
+        replacement: String::new(),
+    })
+}
+
+/// Remove a statement, with its line when nothing else is on it
+fn remove_statement(source: &str, span: (usize, usize)) -> Option<Edit> {
+    let rest = source.get(span.1..)?;
+    let line_rest = rest.split('\n').next().unwrap_or_default();
+    let end = if line_rest.trim().is_empty() {
+        (span.1 + line_rest.len() + 1).min(source.len())
+    } else {
+        span.1
+    };
+    Some(Edit {
+        span: (span.0, end),
+        replacement: String::new(),
+    })
+}
+
+/// Remove the `name` entry of the object under `field`, e.g. `lodash` of
+/// `dependencies`, leaving scripts or other fields with the same key alone
+fn remove_json_entry(source: &str, field: &str, name: &str) -> Option<Vec<Edit>> {
+    let (start, end) = json_object(source, 0, source.len(), field)?;
+    let (key_start, _) = json_key(source, start, end, name)?;
+
+    let line_start = source[..key_start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[key_start..]
+        .find('\n')
+        .map_or(source.len(), |i| key_start + i + 1);
+
+    let mut edits = Vec::new();
+
+    // Removing the last entry of an object leaves a dangling comma on the previous one
+    if !source[line_start..line_end].trim_end().ends_with(',') {
+        let previous = source[..line_start].trim_end();
+        if previous.ends_with(',') {
+            edits.push(Edit {
+                span: (previous.len() - 1, previous.len()),
+                replacement: String::new(),
+            });
+        }
+    }
+
+    edits.push(Edit {
+        span: (line_start, line_end),
+        replacement: String::new(),
+    });
+
+    Some(edits)
+}
+
+/// Byte range of the object a key maps to within `start..end`, from its `{`
+/// to its `}`
+fn json_object(source: &str, start: usize, end: usize, key: &str) -> Option<(usize, usize)> {
+    let mut from = start;
+    loop {
+        let (key_start, value_start) = json_key(source, from, end, key)?;
+        if source[value_start..].starts_with('{') {
+            return Some((value_start, value_start + object_len(&source[value_start..end])?));
+        }
+        from = key_start + 1;
+    }
+}
+
+/// Start of the first `"key":` within `start..end`, and of the value after it
+fn json_key(source: &str, start: usize, end: usize, key: &str) -> Option<(usize, usize)> {
+    let quoted = format!("\"{}\"", key);
+    source[start..end].match_indices(&quoted).find_map(|(i, _)| {
+        let key_start = start + i;
+        let rest = source[key_start + quoted.len()..].trim_start().strip_prefix(':')?;
+        Some((key_start, source.len() - rest.trim_start().len()))
+    })
+}
+
+/// Length of the object `source` starts with, up to and including its `}`
+fn object_len(source: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in source.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '{' if !in_string => depth += 1,
+            '}' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
@@ -0,0 +1,49 @@
+use std::collections::HashSet;
+
+/// Element and attribute names used in an Angular template
+///
+/// Binding syntax is stripped from attributes, so `[appTooltip]="text"` and
+/// `*appIf="x"` both use their directive's name.
+pub fn template_names(html: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut push = |name: &str| {
+        if !name.is_empty() && !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    };
+
+    for tag in html.split('<').skip(1) {
+        let tag = tag.split('>').next().unwrap_or_default();
+        if tag.starts_with(['/', '!']) {
+            continue;
+        }
+
+        let mut words = tag.split_whitespace();
+        push(words.next().unwrap_or_default());
+        for attribute in words {
+            let name = attribute.split('=').next().unwrap_or_default();
+            push(name.trim_matches(|c| matches!(c, '[' | ']' | '(' | ')' | '*' | '/')));
+        }
+    }
+
+    names
+}
+
+/// Whether a component or directive selector, e.g. `app-card` or
+/// `button[appRipple], [appRipple]`, matches names used in templates
+///
+/// Each alternative matches when its element and attributes are all used,
+/// though not necessarily on the same element.
+pub fn selector_matches(selector: &str, names: &HashSet<String>) -> bool {
+    selector.split(',').any(|alternative| {
+        let alternative = alternative.trim();
+        let (element, attributes) = alternative.split_once('[').unwrap_or((alternative, ""));
+        let element_used = element.is_empty() || names.contains(element);
+        let attributes_used = attributes
+            .split('[')
+            .map(|attribute| attribute.trim_end_matches(']').split('=').next().unwrap_or_default())
+            .filter(|attribute| !attribute.is_empty())
+            .all(|attribute| names.contains(attribute));
+        !alternative.is_empty() && element_used && attributes_used
+    })
+}
@@ -0,0 +1,9 @@
+/// Byte range of an Astro component's frontmatter script, the code between
+/// the `---` fences opening the file
+pub fn frontmatter_range(source: &str) -> Option<(usize, usize)> {
+    let leading = source.len() - source.trim_start().len();
+    let rest = source[leading..].strip_prefix("---")?;
+    let start = leading + 3;
+    let end = rest.find("\n---").map_or(source.len(), |close| start + close + 1);
+    Some((start, end))
+}
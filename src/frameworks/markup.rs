@@ -0,0 +1,52 @@
+/// The source of a component file with everything outside `ranges` blanked
+/// out, so the scripts in those ranges parse at their offsets in the file
+///
+/// Blanking keeps line breaks and replaces every other byte with a space.
+pub fn blank_outside(source: &str, ranges: &[(usize, usize)]) -> String {
+    source
+        .char_indices()
+        .map(|(offset, c)| match c {
+            '\n' | '\r' => c.to_string(),
+            _ if ranges.iter().any(|&(start, end)| (start..end).contains(&offset)) => c.to_string(),
+            _ => " ".repeat(c.len_utf8()),
+        })
+        .collect()
+}
+
+/// Components a template renders, e.g. `Card` for `<Card />` and `Icons`
+/// for `<Icons.Star />`, each with the byte offset of its name; tags inside
+/// the script `ranges` are skipped
+pub fn component_tags(source: &str, ranges: &[(usize, usize)]) -> Vec<(String, usize)> {
+    let mut components = Vec::new();
+    for (offset, _) in source.match_indices('<') {
+        if ranges.iter().any(|&(start, end)| (start..end).contains(&offset)) {
+            continue;
+        }
+        let name: String = source[offset + 1..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '$'))
+            .collect();
+        if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            components.push((name, offset + 1));
+        }
+    }
+    components
+}
+
+/// The `<script>` blocks of a component file, each as the byte range of its
+/// contents and the attributes of its opening tag
+pub fn script_blocks(source: &str) -> Vec<((usize, usize), &str)> {
+    let mut blocks = Vec::new();
+    let mut from = 0;
+    while let Some(open) = source[from..].find("<script") {
+        let tag = from + open;
+        let Some(start) = source[tag..].find('>').map(|end| tag + end + 1) else {
+            break;
+        };
+        let attributes = &source[tag + "<script".len()..start - 1];
+        let end = source[start..].find("</script>").map_or(source.len(), |close| start + close);
+        blocks.push(((start, end), attributes));
+        from = end;
+    }
+    blocks
+}
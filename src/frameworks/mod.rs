@@ -0,0 +1,219 @@
+pub mod angular;
+pub mod astro;
+pub mod markup;
+pub mod serverless;
+pub mod svelte;
+pub mod vue;
+pub mod wrangler;
+
+/// A framework whose routing conventions make files entry points by location
+#[derive(Debug)]
+pub struct Framework {
+    pub name: &'static str,
+    /// Dependencies whose presence in a package.json enables the framework
+    pub packages: &'static [&'static str],
+    /// Globs, relative to the package directory, of files the framework loads itself
+    pub entry_globs: &'static [&'static str],
+    /// Exports the framework reads from those files, so they are never reported unused
+    pub used_exports: &'static [&'static str],
+    /// Globs, relative to the package directory, of files whose exports the
+    /// framework makes available everywhere without an import, so all are used
+    pub auto_import_globs: &'static [&'static str],
+}
+
+/// Frameworks with file-system based routing or convention-loaded modules
+pub const FRAMEWORKS: &[Framework] = &[
+    Framework {
+        name: "next",
+        packages: &["next"],
+        entry_globs: &[
+            "pages/**",
+            "src/pages/**",
+            "app/**",
+            "src/app/**",
+            "middleware.{js,ts}",
+            "src/middleware.{js,ts}",
+            "next.config.{js,mjs,cjs,ts}",
+        ],
+        used_exports: &[
+            "default",
+            // Pages router data fetching and API route config
+            "getServerSideProps",
+            "getStaticProps",
+            "getStaticPaths",
+            "config",
+            // App router metadata and route segment config
+            "generateMetadata",
+            "metadata",
+            "generateViewport",
+            "viewport",
+            "generateStaticParams",
+            "generateImageMetadata",
+            "generateSitemaps",
+            "dynamic",
+            "dynamicParams",
+            "revalidate",
+            "fetchCache",
+            "runtime",
+            "preferredRegion",
+            "maxDuration",
+            // Route handlers
+            "GET",
+            "HEAD",
+            "POST",
+            "PUT",
+            "PATCH",
+            "DELETE",
+            "OPTIONS",
+            "middleware",
+        ],
+        auto_import_globs: &[],
+    },
+    Framework {
+        name: "nuxt",
+        packages: &["nuxt"],
+        entry_globs: &[
+            "app.vue",
+            "error.vue",
+            "pages/**",
+            "layouts/**",
+            "middleware/**",
+            "plugins/**",
+            "server/api/**",
+            "server/routes/**",
+            "server/middleware/**",
+            "server/plugins/**",
+            "modules/*.{js,mjs,ts}",
+            "modules/*/index.{js,mjs,ts}",
+            "nuxt.config.{js,mjs,ts}",
+            "app.config.{js,mjs,ts}",
+        ],
+        used_exports: &["default"],
+        auto_import_globs: &["composables/**", "utils/**", "components/**", "server/utils/**"],
+    },
+    Framework {
+        name: "astro",
+        packages: &["astro"],
+        entry_globs: &[
+            "src/pages/**",
+            "src/middleware.{js,ts}",
+            "src/middleware/index.{js,ts}",
+            "src/content/config.{js,mjs,ts}",
+            "src/content.config.{js,mjs,ts}",
+            "astro.config.{js,mjs,cjs,ts,mts}",
+        ],
+        used_exports: &[
+            "default",
+            "getStaticPaths",
+            "prerender",
+            "partial",
+            // Endpoints, middleware, and content collections
+            "GET",
+            "POST",
+            "PUT",
+            "PATCH",
+            "DELETE",
+            "ALL",
+            "onRequest",
+            "collections",
+        ],
+        auto_import_globs: &[],
+    },
+    Framework {
+        name: "electron",
+        packages: &["electron"],
+        // electron-vite's layout and the build configs of Forge's plugins
+        entry_globs: &[
+            "src/main/index.{js,mjs,cjs,ts}",
+            "src/preload/index.{js,mjs,cjs,ts}",
+            "electron.vite.config.{js,mjs,cjs,ts,mts}",
+            "vite.{main,preload,renderer}.config.{js,mjs,cjs,ts,mts}",
+            "webpack.{main,renderer}.config.{js,ts}",
+            "webpack.{plugins,rules}.{js,ts}",
+        ],
+        used_exports: &["default"],
+        auto_import_globs: &[],
+    },
+    Framework {
+        name: "sveltekit",
+        packages: &["@sveltejs/kit"],
+        entry_globs: &[
+            "src/routes/**/+*",
+            "src/hooks.{js,ts}",
+            "src/hooks.*.{js,ts}",
+            "src/params/*.{js,ts}",
+            "src/service-worker.{js,ts}",
+            "src/service-worker/index.{js,ts}",
+            "svelte.config.js",
+        ],
+        used_exports: &[
+            "default",
+            // Load functions, form actions, and page options
+            "load",
+            "actions",
+            "prerender",
+            "ssr",
+            "csr",
+            "trailingSlash",
+            "config",
+            "entries",
+            // Endpoints
+            "GET",
+            "HEAD",
+            "POST",
+            "PUT",
+            "PATCH",
+            "DELETE",
+            "OPTIONS",
+            "fallback",
+            // Hooks and param matchers
+            "handle",
+            "handleFetch",
+            "handleError",
+            "reroute",
+            "init",
+            "match",
+        ],
+        auto_import_globs: &[],
+    },
+    Framework {
+        name: "remix",
+        packages: &["@remix-run/react", "@remix-run/node", "@remix-run/dev"],
+        entry_globs: &["app/routes/**", "app/root.{js,jsx,ts,tsx}", "app/entry.*"],
+        used_exports: &[
+            "default",
+            // Data loading and mutations
+            "loader",
+            "action",
+            "clientLoader",
+            "clientAction",
+            "shouldRevalidate",
+            // Document and route module conventions
+            "meta",
+            "links",
+            "headers",
+            "handle",
+            "ErrorBoundary",
+        ],
+        auto_import_globs: &[],
+    },
+];
+
+/// Frameworks a package uses, judged by its dependencies or named by the config
+pub fn detect<'a>(
+    dependencies: impl IntoIterator<Item = &'a str>,
+    configured: Option<&str>,
+) -> Vec<&'static Framework> {
+    let dependencies: Vec<&str> = dependencies.into_iter().collect();
+
+    FRAMEWORKS
+        .iter()
+        .filter(|framework| {
+            configured == Some(framework.name)
+                || framework
+                    .packages
+                    .iter()
+                    .any(|package| dependencies.contains(package))
+        })
+        .collect()
+}
@@ -0,0 +1,35 @@
+use crate::resolver;
+use std::path::Path;
+
+/// Serverless Framework configs, in the order it looks for them
+const CONFIG_FILES: [&str; 3] = ["serverless.yml", "serverless.yaml", "serverless.json"];
+
+/// The functions a Serverless Framework config in `dir` deploys, each as
+/// its source file, relative to `dir`, and the export the handler names
+///
+/// `handler: src/handlers/user.create` is the `create` export of
+/// `src/handlers/user.ts`, or whichever extension exists. Handlers in other
+/// languages, or built from variables, resolve to no file and are left out.
+pub fn handlers(dir: &Path) -> Vec<(String, String)> {
+    let Some((config_path, config)) = CONFIG_FILES.iter().find_map(|name| {
+        let path = dir.join(name);
+        let content = std::fs::read_to_string(&path).ok()?;
+        let config: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+        Some((path, config))
+    }) else {
+        return Vec::new();
+    };
+
+    let functions = config.get("functions").and_then(|functions| functions.as_mapping());
+    functions
+        .into_iter()
+        .flat_map(|functions| functions.values())
+        .filter_map(|function| function.get("handler")?.as_str())
+        .filter_map(|handler| {
+            let (module, export) = handler.rsplit_once('.')?;
+            let file = resolver::resolve_relative(&config_path, &format!("./{}", module))?;
+            let relative = file.strip_prefix(dir).ok()?.to_string_lossy().to_string();
+            Some((relative, export.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,23 @@
+use super::markup;
+
+/// A `<script>` block of a Svelte component
+pub struct Script {
+    /// Byte range of the block's contents
+    pub range: (usize, usize),
+    /// Whether it is the module script, `context="module"` or Svelte 5's
+    /// `module`, whose exports are the component file's; the instance
+    /// script's `export let` declares props instead
+    pub is_module: bool,
+}
+
+/// The `<script>` blocks of a Svelte component
+pub fn scripts(source: &str) -> Vec<Script> {
+    markup::script_blocks(source)
+        .into_iter()
+        .map(|(range, attributes)| Script {
+            range,
+            is_module: attributes.contains("context=\"module\"")
+                || attributes.split_whitespace().any(|attribute| attribute == "module"),
+        })
+        .collect()
+}
@@ -0,0 +1,7 @@
+use super::markup;
+
+/// Byte ranges of a Vue single-file component's `<script>` and
+/// `<script setup>` blocks, which together make up its module
+pub fn scripts(source: &str) -> Vec<(usize, usize)> {
+    markup::script_blocks(source).into_iter().map(|(range, _)| range).collect()
+}
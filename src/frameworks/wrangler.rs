@@ -0,0 +1,94 @@
+use std::path::Path;
+
+/// A Cloudflare Worker's entry module, relative to its directory, and the
+/// exports the runtime reads from it: the default handler and the classes
+/// Durable Object and Workflow bindings name
+pub fn worker(dir: &Path) -> Option<(String, Vec<String>)> {
+    let (main, classes) = read_json(&dir.join("wrangler.json"))
+        .or_else(|| read_json(&dir.join("wrangler.jsonc")))
+        .or_else(|| read_toml(&dir.join("wrangler.toml")))?;
+
+    let main = main.trim_start_matches("./").to_string();
+    let mut exports = vec!["default".to_string()];
+    for class in classes {
+        if !exports.contains(&class) {
+            exports.push(class);
+        }
+    }
+    Some((main, exports))
+}
+
+fn read_json(path: &Path) -> Option<(String, Vec<String>)> {
+    let content = std::fs::read_to_string(path).ok()?;
+    // wrangler.jsonc allows line comments
+    let content: String = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let config: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    let main = config.get("main")?.as_str()?.to_string();
+    let mut classes = Vec::new();
+    collect_json_classes(&config, &mut classes);
+    Some((main, classes))
+}
+
+fn collect_json_classes(value: &serde_json::Value, classes: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("class_name", serde_json::Value::String(class)) => classes.push(class.clone()),
+                    ("new_classes" | "new_sqlite_classes", serde_json::Value::Array(names)) => {
+                        classes.extend(names.iter().filter_map(|name| name.as_str()).map(str::to_string))
+                    }
+                    _ => collect_json_classes(value, classes),
+                }
+            }
+        }
+        serde_json::Value::Array(values) => values.iter().for_each(|value| collect_json_classes(value, classes)),
+        _ => {}
+    }
+}
+
+/// Read the few wrangler.toml keys that matter here, wherever they appear:
+/// `main`, the `class_name` of bindings, and migrations' `new_classes`
+fn read_toml(path: &Path) -> Option<(String, Vec<String>)> {
+    let content = std::fs::read_to_string(path).ok()?;
+
+    let mut main = None;
+    let mut classes = Vec::new();
+    for line in content.lines() {
+        if main.is_none() {
+            main = toml_strings(line, "main").into_iter().next();
+        }
+        classes.extend(toml_strings(line, "class_name"));
+        classes.extend(toml_strings(line, "new_classes"));
+        classes.extend(toml_strings(line, "new_sqlite_classes"));
+    }
+
+    Some((main?, classes))
+}
+
+/// The string, or strings of an array, that `key` is set to on a line, as
+/// a plain or inline-table key
+fn toml_strings(line: &str, key: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    for (offset, _) in line.match_indices(key) {
+        let before = line[..offset].trim_end();
+        if !(before.is_empty() || before.ends_with(['{', ','])) {
+            continue;
+        }
+        let Some(value) = line[offset + key.len()..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let value = match value.strip_prefix('[') {
+            Some(array) => array.split(']').next().unwrap_or_default(),
+            None => value.split([',', '}']).next().unwrap_or_default(),
+        };
+        strings.extend(value.split('"').skip(1).step_by(2).map(str::to_string));
+    }
+    strings
+}
@@ -0,0 +1,83 @@
+use super::{FileImportGraph, GraphFilter};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Serialize the import graph as Graphviz DOT
+///
+/// Entry points are green, files unreachable from any entry point are red,
+/// and imports that don't match a discovered file are drawn dashed. Only
+/// files the `filter` includes, and the imports between them, are drawn.
+pub fn to_dot(graph: &FileImportGraph, root: &Path, filter: &GraphFilter) -> String {
+    let reachable = graph.reachable_files();
+    let included = |path: &Path| filter.includes(path);
+    let label = |path: &Path| escape(&path.strip_prefix(root).unwrap_or(path).to_string_lossy());
+
+    let mut dot = String::new();
+    dot.push_str("digraph imports {\n");
+    dot.push_str("  rankdir=LR;\n");
+    dot.push_str(
+        "  node [shape=box, style=\"rounded,filled\", fillcolor=white, fontname=monospace];\n",
+    );
+
+    let mut files: Vec<_> = graph
+        .files()
+        .filter(|file| included(&file.path))
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    for file in files {
+        let attributes = if file.is_entry_point {
+            ", fillcolor=\"#c8f7c5\", penwidth=2"
+        } else if !reachable.contains(&file.id) {
+            ", fillcolor=\"#f9c6c6\""
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            dot,
+            "  \"{}\" [label=\"{}\"{}];",
+            label(&file.path),
+            label(&file.path),
+            attributes
+        );
+    }
+
+    let dangling: BTreeSet<_> = graph
+        .imports()
+        .filter(|edge| included(graph.path(edge.from)) && included(graph.path(edge.to)))
+        .filter(|edge| !graph.contains_file(graph.path(edge.to)))
+        .map(|edge| label(graph.path(edge.to)))
+        .collect();
+    for target in dangling {
+        let _ = writeln!(
+            dot,
+            "  \"{0}\" [label=\"{0}\", style=\"rounded,dashed\"];",
+            target
+        );
+    }
+
+    for edge in graph.imports() {
+        if !included(graph.path(edge.from)) || !included(graph.path(edge.to)) {
+            continue;
+        }
+        let style = if edge.is_type_only {
+            " [style=dashed]"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            dot,
+            "  \"{}\" -> \"{}\"{};",
+            label(graph.path(edge.from)),
+            label(graph.path(edge.to)),
+            style
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,213 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Interned file path, cheap to copy, hash, and compare
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FileId(u32);
+
+/// Interned symbol or package name
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SymbolId(u32);
+
+/// An id handed out by an `Interner`, indexing its slots
+pub trait InternId: Copy {
+    fn from_index(index: usize) -> Self;
+    fn index(self) -> usize;
+}
+
+impl InternId for FileId {
+    fn from_index(index: usize) -> Self {
+        Self(u32::try_from(index).expect("more than u32::MAX files"))
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl InternId for SymbolId {
+    fn from_index(index: usize) -> Self {
+        Self(u32::try_from(index).expect("more than u32::MAX symbol names"))
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Hands out one id per distinct value and resolves ids back to values
+///
+/// Each value is stored once, shared between its slot and the lookup table.
+/// Ids are counted: `intern` and `retain` take a reference, `release` gives
+/// one back, and a value whose last reference is released is dropped, its
+/// id free to be handed out again.
+#[derive(Debug)]
+pub struct Interner<T: ?Sized, I> {
+    slots: Vec<Option<Slot<T>>>,
+    ids: HashMap<Arc<T>, I>,
+    free: Vec<I>,
+}
+
+#[derive(Debug)]
+struct Slot<T: ?Sized> {
+    value: Arc<T>,
+    references: u32,
+}
+
+impl<T: ?Sized, I: Clone> Clone for Interner<T, I> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self
+                .slots
+                .iter()
+                .map(|slot| {
+                    slot.as_ref().map(|slot| Slot {
+                        value: slot.value.clone(),
+                        references: slot.references,
+                    })
+                })
+                .collect(),
+            ids: self.ids.clone(),
+            free: self.free.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized, I> Default for Interner<T, I> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            ids: HashMap::new(),
+            free: Vec::new(),
+        }
+    }
+}
+
+impl<T, I> Interner<T, I>
+where
+    T: Eq + Hash + ?Sized,
+    I: InternId,
+    for<'a> Arc<T>: From<&'a T>,
+{
+    /// The id of `value`, interning it if it is new, with a reference taken
+    pub fn intern(&mut self, value: &T) -> I {
+        if let Some(&id) = self.ids.get(value) {
+            self.retain(id);
+            return id;
+        }
+
+        let value: Arc<T> = Arc::from(value);
+        let slot = Some(Slot {
+            value: value.clone(),
+            references: 1,
+        });
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.slots[id.index()] = slot;
+                id
+            }
+            None => {
+                self.slots.push(slot);
+                I::from_index(self.slots.len() - 1)
+            }
+        };
+        self.ids.insert(value, id);
+        id
+    }
+}
+
+impl<T: Eq + Hash + ?Sized, I: InternId> Interner<T, I> {
+    /// The id of `value`, if it is interned
+    pub fn get(&self, value: &T) -> Option<I> {
+        self.ids.get(value).copied()
+    }
+
+    /// Take another reference to an interned id
+    pub fn retain(&mut self, id: I) {
+        self.slot_mut(id).references += 1;
+    }
+
+    /// Give back a reference, dropping the value with its last one
+    pub fn release(&mut self, id: I) {
+        let slot = self.slot_mut(id);
+        slot.references -= 1;
+        if slot.references == 0 {
+            let slot = self.slots[id.index()].take().expect("interned id");
+            self.ids.remove(&slot.value);
+            self.free.push(id);
+        }
+    }
+
+    pub fn resolve(&self, id: I) -> &T {
+        self.shared(id)
+    }
+
+    /// The interned value itself, to share rather than copy
+    pub fn shared(&self, id: I) -> &Arc<T> {
+        &self.slots[id.index()].as_ref().expect("released id").value
+    }
+
+    /// Number of distinct values interned
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    fn slot_mut(&mut self, id: I) -> &mut Slot<T> {
+        self.slots[id.index()].as_mut().expect("released id")
+    }
+}
+
+/// Slots are written in order, so ids read back unchanged
+impl<T: Serialize + ?Sized, I> Serialize for Interner<T, I> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let slots: Vec<Option<(&T, u32)>> = self
+            .slots
+            .iter()
+            .map(|slot| slot.as_ref().map(|slot| (&*slot.value, slot.references)))
+            .collect();
+        slots.serialize(serializer)
+    }
+}
+
+impl<'de, T, I> Deserialize<'de> for Interner<T, I>
+where
+    T: Eq + Hash + ?Sized,
+    Box<T>: DeserializeOwned,
+    Arc<T>: From<Box<T>>,
+    I: InternId,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let slots: Vec<Option<(Box<T>, u32)>> = Vec::deserialize(deserializer)?;
+        let mut interner = Self::default();
+        for (index, slot) in slots.into_iter().enumerate() {
+            let id = I::from_index(index);
+            interner.slots.push(slot.map(|(value, references)| {
+                let value: Arc<T> = Arc::from(value);
+                interner.ids.insert(value.clone(), id);
+                Slot { value, references }
+            }));
+            if interner.slots[index].is_none() {
+                interner.free.push(id);
+            }
+        }
+        Ok(interner)
+    }
+}
+
+/// The paths and names the graphs of a workspace refer to by id
+///
+/// The file import graph owns them; the symbol and dependency graphs take
+/// references through them for the ids they store.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Interners {
+    pub paths: Interner<Path, FileId>,
+    pub names: Interner<str, SymbolId>,
+}
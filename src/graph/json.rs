@@ -0,0 +1,54 @@
+use super::{FileImportGraph, GraphFilter};
+use serde_json::json;
+use std::path::Path;
+
+/// Serialize the import graph as JSON: `files` with their role, and
+/// `imports` with how they were written and what they resolved to
+///
+/// Paths are relative to `root`. Only files the `filter` includes, and the
+/// imports between them, are listed.
+pub fn to_json(graph: &FileImportGraph, root: &Path, filter: &GraphFilter) -> String {
+    let reachable = graph.reachable_files();
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).to_string_lossy().to_string();
+
+    let mut files: Vec<_> = graph
+        .files()
+        .filter(|file| filter.includes(&file.path))
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let files: Vec<_> = files
+        .into_iter()
+        .map(|file| {
+            json!({
+                "path": relative(&file.path),
+                "entry_point": file.is_entry_point,
+                "test": file.is_test,
+                "reachable": reachable.contains(&file.id),
+            })
+        })
+        .collect();
+
+    let mut imports: Vec<_> = graph
+        .imports()
+        .filter(|edge| filter.includes(graph.path(edge.from)) && filter.includes(graph.path(edge.to)))
+        .collect();
+    imports.sort_by(|a, b| (&a.from, a.span).cmp(&(&b.from, b.span)));
+    let imports: Vec<_> = imports
+        .into_iter()
+        .map(|edge| {
+            json!({
+                "from": relative(graph.path(edge.from)),
+                "to": relative(graph.path(edge.to)),
+                "specifier": edge.specifier,
+                "resolved": edge.is_resolved,
+                "type_only": edge.is_type_only,
+                "symbols": edge.imported_symbols.iter().map(|&name| graph.name(name)).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    let mut rendered = serde_json::to_string_pretty(&json!({ "files": files, "imports": imports }))
+        .unwrap_or_default();
+    rendered.push('\n');
+    rendered
+}
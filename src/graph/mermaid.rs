@@ -0,0 +1,57 @@
+use super::{FileImportGraph, GraphFilter};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Serialize the import graph as a Mermaid `graph TD` diagram
+///
+/// Entry points and unreachable files get their own classes, type-only
+/// imports are dotted. Only files the `filter` includes, and the imports
+/// between them, are drawn.
+pub fn to_mermaid(graph: &FileImportGraph, root: &Path, filter: &GraphFilter) -> String {
+    let reachable = graph.reachable_files();
+    let included = |path: &Path| filter.includes(path);
+
+    // Mermaid ids must be plain identifiers, so paths are only used as labels
+    let mut ids: BTreeMap<&Path, String> = BTreeMap::new();
+    for path in graph
+        .files()
+        .map(|file| &*file.path)
+        .chain(graph.imports().map(|edge| graph.path(edge.to)))
+    {
+        if included(path) {
+            ids.entry(path).or_default();
+        }
+    }
+    for (index, id) in ids.values_mut().enumerate() {
+        *id = format!("n{}", index);
+    }
+
+    let mut mermaid = String::from("graph TD\n");
+    mermaid.push_str("  classDef entry fill:#c8f7c5,stroke:#1a7f37,stroke-width:2px\n");
+    mermaid.push_str("  classDef unused fill:#f9c6c6,stroke:#cf222e\n");
+    mermaid.push_str("  classDef unresolved stroke-dasharray:4 3\n");
+
+    for (path, id) in &ids {
+        let label = path.strip_prefix(root).unwrap_or(path).to_string_lossy();
+        let _ = writeln!(mermaid, "  {}[\"{}\"]", id, label.replace('"', "#quot;"));
+
+        let class = match graph.file(path) {
+            Some(file) if file.is_entry_point => "entry",
+            Some(file) if !reachable.contains(&file.id) => "unused",
+            Some(_) => continue,
+            None => "unresolved",
+        };
+        let _ = writeln!(mermaid, "  class {} {}", id, class);
+    }
+
+    for edge in graph.imports() {
+        let (Some(from), Some(to)) = (ids.get(graph.path(edge.from)), ids.get(graph.path(edge.to))) else {
+            continue;
+        };
+        let arrow = if edge.is_type_only { "-.->" } else { "-->" };
+        let _ = writeln!(mermaid, "  {} {} {}", from, arrow, to);
+    }
+
+    mermaid
+}
@@ -1,101 +1,878 @@
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+pub mod dot;
+pub mod intern;
+pub mod json;
+pub mod mermaid;
+
+pub use intern::{FileId, Interners, SymbolId};
+
+use crate::frameworks::angular;
+use crate::source::LineIndex;
+use petgraph::algo::tarjan_scc;
+use petgraph::stable_graph::{NodeIndex, StableDiGraph};
+use petgraph::visit::{Dfs, EdgeRef, IntoEdgeReferences};
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Represents a single file in the project
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileNode {
-    pub path: PathBuf,
+    /// Assigned by the graph holding the node
+    pub id: FileId,
+    /// The interned path of `id`, shared rather than copied
+    #[serde(skip, default = "unset_path")]
+    pub path: Arc<Path>,
     pub is_entry_point: bool,
+    /// Matches the configured test globs; always an entry point as well
+    #[serde(default)]
+    pub is_test: bool,
+    /// Matches the configured story globs; always an entry point as well
+    #[serde(default)]
+    pub is_story: bool,
+    /// Declared by its package.json's `sideEffects` as having side effects on import
+    #[serde(default)]
+    pub has_side_effects: bool,
+    /// Exports a framework reads from the file itself, e.g. `getServerSideProps`
+    /// of a Next.js page, so they are used without being imported
+    #[serde(default)]
+    pub used_exports: Vec<SymbolId>,
+    /// Modules the file declares with `declare module 'name'`, patterns
+    /// like `*.svg` included
+    #[serde(default)]
+    pub ambient_modules: Vec<String>,
+    /// Declares globals, which any file may rely on without importing it
+    #[serde(default)]
+    pub declares_globals: bool,
+    /// Specifiers the file imports packages by, e.g. `express`, which
+    /// ambient module declarations may describe
+    #[serde(default)]
+    pub bare_specifiers: Vec<String>,
+    /// False for import targets that match no discovered file
+    pub is_discovered: bool,
 }
 
-/// Represents an exported symbol
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Symbol {
+/// An exported symbol, as the parser finds it
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ParsedExport {
     pub name: String,
     pub file: PathBuf,
     pub span: (usize, usize),
+    /// Interfaces, type aliases, and `export type` names, which vanish at runtime
+    #[serde(default)]
+    pub is_type: bool,
+    /// Visibility from a `@public` or `@internal` JSDoc tag on the export
+    #[serde(default)]
+    pub visibility: Option<Visibility>,
+    /// Selector of an Angular component or directive, which templates use
+    /// it by rather than importing it
+    #[serde(default)]
+    pub selector: Option<String>,
+    /// Referenced within its own file apart from being exported
+    #[serde(default)]
+    pub used_in_module: bool,
+    /// Byte offsets of the declaration the export names, when it is in the file
+    #[serde(default)]
+    pub declaration: Option<(usize, usize)>,
 }
 
-/// Import relationship between files
-#[derive(Debug, Clone)]
-pub struct ImportEdge {
+/// An exported symbol of the symbol graph, with its name and file interned
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Symbol {
+    pub name: SymbolId,
+    pub file: FileId,
+    pub span: (usize, usize),
+    pub is_type: bool,
+    pub visibility: Option<Visibility>,
+    pub selector: Option<String>,
+    pub used_in_module: bool,
+    pub declaration: Option<(usize, usize)>,
+}
+
+/// Intended visibility of an export, as declared by its JSDoc tags
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// `@public`: part of the package's API, kept even when unused
+    Public,
+    /// `@internal`: reported like any other export, overriding `@public`
+    Internal,
+}
+
+/// Import relationship between files, as the parser finds it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedImport {
     pub from: PathBuf,
     pub to: PathBuf,
+    /// The specifier as written, e.g. `./utils`
+    pub specifier: String,
+    /// Byte offsets of the specifier in the importing file
+    pub span: (usize, usize),
+    /// False when no file matched the specifier, leaving `to` as its best guess
+    pub is_resolved: bool,
     pub imported_symbols: Vec<String>,
     pub is_type_only: bool,
+    /// For `export .. from`, the `(exported, imported)` name pairs `from`
+    /// forwards out of `to`; `("*", "*")` for `export *` and `("ns", "*")`
+    /// for `export * as ns`
+    #[serde(default)]
+    pub forwarded: Vec<(String, String)>,
 }
 
-/// Symbol reference
-#[derive(Debug, Clone)]
-pub struct SymbolReference {
+/// An import edge of the file graph, with its files and names interned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportEdge {
+    pub from: FileId,
+    pub to: FileId,
+    pub specifier: String,
+    pub span: (usize, usize),
+    pub is_resolved: bool,
+    pub imported_symbols: Vec<SymbolId>,
+    pub is_type_only: bool,
+    pub forwarded: Vec<(SymbolId, SymbolId)>,
+}
+
+impl ImportEdge {
+    /// Every name the edge refers to
+    fn names(&self) -> impl Iterator<Item = SymbolId> + '_ {
+        self.imported_symbols
+            .iter()
+            .copied()
+            .chain(self.forwarded.iter().flat_map(|&(exported, imported)| [exported, imported]))
+    }
+}
+
+/// Import of an npm package by name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageImport {
+    pub name: String,
+    pub file: PathBuf,
+    /// Byte offsets of the import specifier in the source file
+    pub span: (usize, usize),
+    /// `import type`, which leaves nothing of the package at runtime
+    #[serde(default)]
+    pub is_type_only: bool,
+}
+
+/// A class member only its class can access, a `#name` or TypeScript
+/// `private` field or method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassMember {
+    /// The member's name, with the `#` of a private name
+    pub name: String,
+    /// Name of the declaring class, unless it is anonymous
+    pub class: Option<String>,
+    pub file: PathBuf,
+    pub span: (usize, usize),
+}
+
+/// A variable, function, or class declared within a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalBinding {
+    pub name: String,
+    pub file: PathBuf,
+    pub span: (usize, usize),
+}
+
+/// A binding of an `import` declaration within a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportBinding {
+    /// The local name, e.g. `b` in `import { a as b } from './x'`
+    pub name: String,
+    /// The module specifier imported from
+    pub source: String,
+    pub file: PathBuf,
+    /// Byte offsets of the import specifier in the source file
+    pub span: (usize, usize),
+    /// Byte offsets of the whole declaration, when this is its only binding
+    pub statement: Option<(usize, usize)>,
+}
+
+/// A `sweepr-ignore-next-line` or `sweepr-ignore-file` comment, silencing
+/// the findings of the rules it names by code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Suppression {
+    /// The line whose findings are silenced, or every line of the file
+    pub line: Option<usize>,
+    /// Codes of the rules silenced, e.g. `SWP002`; every rule when empty
+    pub codes: Vec<String>,
+}
+
+impl Suppression {
+    /// Whether this silences a finding of the rule `code` on `line`, or on no
+    /// particular line for a finding about the whole file
+    pub fn covers(&self, code: &str, line: Option<usize>) -> bool {
+        (self.line.is_none() || self.line == line) && (self.codes.is_empty() || self.codes.iter().any(|c| c == code))
+    }
+}
+
+/// Reference to a name within a file, as the parser finds it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedReference {
     pub symbol: String,
     pub file: PathBuf,
     pub span: (usize, usize),
 }
 
+/// Reference of the symbol graph, with its name and file interned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolReference {
+    pub symbol: SymbolId,
+    pub file: FileId,
+    pub span: (usize, usize),
+}
+
+/// Placeholder path of a deserialized node, until its graph fills it in
+fn unset_path() -> Arc<Path> {
+    Arc::from(Path::new(""))
+}
+
+/// Which files of the import graph a rendering includes; every file by default
+#[derive(Debug, Clone, Default)]
+pub struct GraphFilter {
+    subtree: Option<PathBuf>,
+    reached: Option<HashSet<PathBuf>>,
+}
+
+impl GraphFilter {
+    /// Only include files under this directory
+    pub fn with_subtree(mut self, dir: PathBuf) -> Self {
+        self.subtree = Some(dir);
+        self
+    }
+
+    /// Only include files the `sources` reach by following at most `depth`
+    /// imports, or any number of them without a depth
+    pub fn with_reach(mut self, graph: &FileImportGraph, sources: &[PathBuf], depth: Option<usize>) -> Self {
+        let mut reached: HashSet<PathBuf> = HashSet::new();
+        let mut queue: VecDeque<(NodeIndex, usize)> = sources
+            .iter()
+            .filter_map(|source| graph.index(source))
+            .map(|index| (index, 0))
+            .collect();
+
+        while let Some((index, distance)) = queue.pop_front() {
+            if !reached.insert(graph.graph[index].path.to_path_buf()) || depth.is_some_and(|depth| distance >= depth) {
+                continue;
+            }
+            queue.extend(graph.graph.neighbors(index).map(|next| (next, distance + 1)));
+        }
+
+        self.reached = Some(reached);
+        self
+    }
+
+    pub fn includes(&self, path: &Path) -> bool {
+        self.subtree.as_ref().is_none_or(|dir| path.starts_with(dir))
+            && self.reached.as_ref().is_none_or(|reached| reached.contains(path))
+    }
+}
+
 /// File Import Graph - tracks how files import each other
-#[derive(Debug, Clone)]
+///
+/// Files are nodes and imports are edges. Import targets that match no
+/// discovered file get a node of their own, marked as not discovered, so
+/// dangling imports stay visible.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "SerializedFileGraph", into = "SerializedFileGraph")]
 pub struct FileImportGraph {
-    pub files: HashMap<PathBuf, FileNode>,
-    pub imports: Vec<ImportEdge>,
+    graph: StableDiGraph<FileNode, ImportEdge>,
+    /// Paths and names of the workspace's graphs; each node and edge holds
+    /// a reference to the ones it uses
+    interners: Interners,
+    nodes: HashMap<FileId, NodeIndex>,
+}
+
+/// Whether an ambient module name, e.g. `express` or `*.svg` with a single
+/// wildcard, describes an import specifier
+fn ambient_module_matches(module: &str, specifier: &str) -> bool {
+    match module.split_once('*') {
+        Some((prefix, suffix)) => {
+            specifier.len() >= prefix.len() + suffix.len()
+                && specifier.starts_with(prefix)
+                && specifier.ends_with(suffix)
+        }
+        None => module == specifier,
+    }
+}
+
+/// Name of a TypeScript `export = value` assignment, the whole export
+/// surface of its module
+pub const EXPORT_ASSIGNMENT: &str = "export=";
+
+/// Exports used somewhere, from `FileImportGraph::used_exports`
+#[derive(Debug, Default)]
+pub struct UsedExports {
+    /// `*`, standing for every export of a file, if any import uses it
+    star: Option<SymbolId>,
+    /// `EXPORT_ASSIGNMENT`, if any file has one
+    assignment: Option<SymbolId>,
+    used: HashSet<(FileId, SymbolId)>,
+}
+
+impl UsedExports {
+    /// Whether the export `name` of `file` is used, by name or through a namespace import
+    ///
+    /// An `export =` assignment is used by any import of its file, whatever
+    /// name it asks for, since every name is looked up on the assigned value.
+    pub fn is_used(&self, file: FileId, name: SymbolId) -> bool {
+        if self.assignment == Some(name) {
+            return self.used.iter().any(|(used_file, _)| *used_file == file);
+        }
+        self.star.is_some_and(|star| self.used.contains(&(file, star))) || self.used.contains(&(file, name))
+    }
+}
+
+/// `FileImportGraph` as plain lists, independent of petgraph's node indices
+#[derive(Serialize, Deserialize)]
+struct SerializedFileGraph {
+    interners: Interners,
+    files: Vec<FileNode>,
+    imports: Vec<ImportEdge>,
+}
+
+impl From<FileImportGraph> for SerializedFileGraph {
+    fn from(graph: FileImportGraph) -> Self {
+        Self {
+            files: graph.graph.node_weights().cloned().collect(),
+            imports: graph.graph.edge_weights().cloned().collect(),
+            interners: graph.interners,
+        }
+    }
+}
+
+/// The interners come back with the ids and references they were written
+/// with, so nodes and edges are restored as they were
+impl From<SerializedFileGraph> for FileImportGraph {
+    fn from(serialized: SerializedFileGraph) -> Self {
+        let mut graph = Self {
+            interners: serialized.interners,
+            ..Self::new()
+        };
+        for mut file in serialized.files {
+            file.path = graph.interners.paths.shared(file.id).clone();
+            let id = file.id;
+            let index = graph.graph.add_node(file);
+            graph.nodes.insert(id, index);
+        }
+        for import in serialized.imports {
+            let (from, to) = (graph.nodes[&import.from], graph.nodes[&import.to]);
+            graph.graph.add_edge(from, to, import);
+        }
+        graph
+    }
 }
 
 impl FileImportGraph {
     pub fn new() -> Self {
         Self {
-            files: HashMap::new(),
-            imports: Vec::new(),
+            graph: StableDiGraph::new(),
+            interners: Interners::default(),
+            nodes: HashMap::new(),
         }
     }
 
+    /// The id of an interned path, e.g. of a file in the graph
+    pub fn file_id(&self, path: &Path) -> Option<FileId> {
+        self.interners.paths.get(path)
+    }
+
+    pub fn path(&self, id: FileId) -> &Path {
+        self.interners.paths.resolve(id)
+    }
+
+    /// The id of an interned name, e.g. one a file imports or exports
+    pub fn symbol_id(&self, name: &str) -> Option<SymbolId> {
+        self.interners.names.get(name)
+    }
+
+    pub fn name(&self, id: SymbolId) -> &str {
+        self.interners.names.resolve(id)
+    }
+
+    pub fn interners(&self) -> &Interners {
+        &self.interners
+    }
+
+    /// The interners, for the other graphs to take and release references
+    /// to the ids they store
+    pub fn interners_mut(&mut self) -> &mut Interners {
+        &mut self.interners
+    }
+
+    /// The node of a path, if it is in the graph
+    fn index(&self, path: &Path) -> Option<NodeIndex> {
+        self.nodes.get(&self.file_id(path)?).copied()
+    }
+
     pub fn add_file(&mut self, path: PathBuf, is_entry_point: bool) {
-        self.files.insert(
-            path.clone(),
-            FileNode {
-                path,
-                is_entry_point,
-            },
-        );
+        let index = self.node(&path);
+        let node = &mut self.graph[index];
+        node.is_discovered = true;
+        node.is_entry_point = is_entry_point;
+        node.is_test = false;
+    }
+
+    /// Make a discovered file an entry point, e.g. once its content shows the
+    /// runtime loads it
+    pub fn set_entry_point(&mut self, path: &Path) {
+        if let Some(index) = self.index(path) {
+            self.graph[index].is_entry_point = true;
+        }
+    }
+
+    /// Add a test file, which is an entry point whose imports only count as test use
+    pub fn add_test_file(&mut self, path: PathBuf) {
+        let index = self.node(&path);
+        let node = &mut self.graph[index];
+        node.is_discovered = true;
+        node.is_entry_point = true;
+        node.is_test = true;
+    }
+
+    /// Record whether a file is a Storybook story
+    pub fn set_story(&mut self, path: &Path, is_story: bool) {
+        if let Some(index) = self.index(path) {
+            self.graph[index].is_story = is_story;
+        }
     }
 
-    pub fn add_import(&mut self, edge: ImportEdge) {
-        self.imports.push(edge);
+    /// Record whether importing a file has side effects, per its package.json
+    pub fn set_side_effects(&mut self, path: &Path, has_side_effects: bool) {
+        if let Some(index) = self.index(path) {
+            self.graph[index].has_side_effects = has_side_effects;
+        }
+    }
+
+    /// Record the exports of a file its framework reads
+    pub fn set_used_exports(&mut self, path: &Path, names: Vec<String>) {
+        if let Some(index) = self.index(path) {
+            let names = names.iter().map(|name| self.interners.names.intern(name)).collect();
+            let previous = std::mem::replace(&mut self.graph[index].used_exports, names);
+            for name in previous {
+                self.interners.names.release(name);
+            }
+        }
+    }
+
+    /// Record a file's ambient declarations and the packages it imports by name
+    pub fn set_ambient_declarations(
+        &mut self,
+        path: &Path,
+        ambient_modules: Vec<String>,
+        declares_globals: bool,
+        bare_specifiers: Vec<String>,
+    ) {
+        if let Some(index) = self.index(path) {
+            let node = &mut self.graph[index];
+            node.ambient_modules = ambient_modules;
+            node.declares_globals = declares_globals;
+            node.bare_specifiers = bare_specifiers;
+        }
+    }
+
+    /// Whether a path is a discovered test file
+    pub fn is_test_file(&self, path: &Path) -> bool {
+        self.index(path)
+            .is_some_and(|index| self.graph[index].is_test)
+    }
+
+    pub fn add_import(&mut self, import: ParsedImport) {
+        let from = self.node(&import.from);
+        let to = self.node(&import.to);
+        let names = &mut self.interners.names;
+        let edge = ImportEdge {
+            from: self.graph[from].id,
+            to: self.graph[to].id,
+            specifier: import.specifier,
+            span: import.span,
+            is_resolved: import.is_resolved,
+            imported_symbols: import.imported_symbols.iter().map(|name| names.intern(name)).collect(),
+            is_type_only: import.is_type_only,
+            forwarded: import
+                .forwarded
+                .iter()
+                .map(|(exported, imported)| (names.intern(exported), names.intern(imported)))
+                .collect(),
+        };
+        self.graph.add_edge(from, to, edge);
+    }
+
+    /// Drop every import edge originating from a file
+    pub fn remove_imports_from(&mut self, path: &Path) {
+        let Some(index) = self.index(path) else {
+            return;
+        };
+
+        let edges: Vec<_> = self.graph.edges(index).map(|edge| edge.id()).collect();
+        for edge in edges {
+            if let Some((_, target)) = self.graph.edge_endpoints(edge) {
+                if let Some(edge) = self.graph.remove_edge(edge) {
+                    for name in edge.names() {
+                        self.interners.names.release(name);
+                    }
+                }
+                self.prune(target);
+            }
+        }
+    }
+
+    /// Drop a file and the imports it makes
+    ///
+    /// A file other files still import stays behind as an undiscovered target.
+    pub fn remove_file(&mut self, path: &Path) {
+        self.remove_imports_from(path);
+        if let Some(index) = self.index(path) {
+            let node = &mut self.graph[index];
+            node.is_discovered = false;
+            node.is_entry_point = false;
+            node.is_test = false;
+            node.is_story = false;
+            for name in node.used_exports.drain(..) {
+                self.interners.names.release(name);
+            }
+            node.ambient_modules.clear();
+            node.declares_globals = false;
+            node.bare_specifiers.clear();
+            self.prune(index);
+        }
+    }
+
+    /// Whether a path is a discovered file
+    pub fn contains_file(&self, path: &Path) -> bool {
+        self.file(path).is_some()
+    }
+
+    pub fn file(&self, path: &Path) -> Option<&FileNode> {
+        self.index(path)
+            .map(|index| &self.graph[index])
+            .filter(|node| node.is_discovered)
+    }
+
+    /// Every discovered file
+    pub fn files(&self) -> impl Iterator<Item = &FileNode> {
+        self.graph
+            .node_weights()
+            .filter(|node| node.is_discovered)
+    }
+
+    /// Every import edge
+    pub fn imports(&self) -> impl Iterator<Item = &ImportEdge> {
+        self.graph.edge_weights()
+    }
+
+    /// Imports made by a file
+    pub fn imports_from(&self, path: &Path) -> impl Iterator<Item = &ImportEdge> {
+        self.index(path)
+            .into_iter()
+            .flat_map(|index| self.graph.edges(index).map(|edge| edge.weight()))
+    }
+
+    /// Imports of a file made by other files
+    pub fn imports_to(&self, path: &Path) -> impl Iterator<Item = &ImportEdge> {
+        self.index(path).into_iter().flat_map(|index| {
+            self.graph
+                .edges_directed(index, Direction::Incoming)
+                .map(|edge| edge.weight())
+        })
     }
 
     /// Find all files reachable from entry points
-    pub fn reachable_files(&self) -> HashSet<PathBuf> {
-        let mut reachable = HashSet::new();
-        let mut stack: Vec<PathBuf> = self
-            .files
-            .values()
-            .filter(|f| f.is_entry_point)
-            .map(|f| f.path.clone())
+    ///
+    /// Files declaring globals are reachable on their own, and a file
+    /// declaring a module, e.g. `declare module 'express'`, once a reachable
+    /// file imports that module.
+    pub fn reachable_files(&self) -> HashSet<FileId> {
+        let mut roots: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&index| {
+                let node = &self.graph[index];
+                node.is_entry_point || (node.declares_globals && node.is_discovered)
+            })
+            .collect();
+        let mut ambient: Vec<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&index| !self.graph[index].ambient_modules.is_empty())
             .collect();
 
-        while let Some(current) = stack.pop() {
-            if reachable.contains(&current) {
+        let mut reachable = HashSet::new();
+        let mut dfs = Dfs::empty(&self.graph);
+        while !roots.is_empty() {
+            for root in roots.drain(..) {
+                dfs.move_to(root);
+                while let Some(index) = dfs.next(&self.graph) {
+                    reachable.insert(self.graph[index].id);
+                }
+            }
+
+            // Modules imported so far, by package or by relative path
+            let imported: HashSet<&str> = self
+                .graph
+                .node_indices()
+                .filter(|&index| reachable.contains(&self.graph[index].id))
+                .flat_map(|index| {
+                    let node = &self.graph[index];
+                    let relative = self.graph.edges(index).map(|edge| edge.weight().specifier.as_str());
+                    node.bare_specifiers.iter().map(String::as_str).chain(relative)
+                })
+                .collect();
+            (roots, ambient) = ambient.into_iter().partition(|&index| {
+                !reachable.contains(&self.graph[index].id)
+                    && self.graph[index]
+                        .ambient_modules
+                        .iter()
+                        .any(|module| imported.iter().any(|specifier| ambient_module_matches(module, specifier)))
+            });
+        }
+
+        reachable
+    }
+
+    /// Every `(file, export name)` that is used, with `*` standing for all of a file's exports
+    ///
+    /// Imports mark the names they import from a file as used. Usage then
+    /// follows re-exports (`export { a } from`, `export * from`) back to the
+    /// files that define the names, so a barrel only keeps alive what its
+    /// importers actually use.
+    pub fn used_exports(&self) -> UsedExports {
+        self.used_exports_by(|_| true)
+    }
+
+    /// Like `used_exports`, counting only the imports of files `importer` accepts
+    pub fn used_exports_by(&self, importer: impl Fn(&FileNode) -> bool) -> UsedExports {
+        let star = self.symbol_id("*");
+
+        // Exports a framework reads are used like imported ones
+        let mut pending: Vec<(NodeIndex, SymbolId)> = Vec::new();
+        for index in self.graph.node_indices() {
+            for &name in &self.graph[index].used_exports {
+                pending.push((index, name));
+            }
+        }
+        for edge in self.graph.edge_references() {
+            if edge.weight().forwarded.is_empty() && importer(&self.graph[edge.source()]) {
+                for &name in &edge.weight().imported_symbols {
+                    pending.push((edge.target(), name));
+                }
+            }
+        }
+
+        let mut used = HashSet::new();
+        while let Some((index, name)) = pending.pop() {
+            // Re-export cycles end here
+            if !used.insert((self.graph[index].id, name)) {
                 continue;
             }
 
-            reachable.insert(current.clone());
+            for edge in self.graph.edges(index) {
+                for &(exported, imported) in &edge.weight().forwarded {
+                    let target = if Some(name) == star || exported == name {
+                        imported
+                    } else if Some(exported) == star {
+                        // The name may come through `export *`
+                        name
+                    } else {
+                        continue;
+                    };
+                    pending.push((edge.target(), target));
+                }
+            }
+        }
+
+        UsedExports {
+            star,
+            assignment: self.symbol_id(EXPORT_ASSIGNMENT),
+            used,
+        }
+    }
+
+    /// Shortest chain of imports from an entry point to a file, starting at the entry point
+    pub fn import_chain(&self, target: &Path) -> Option<Vec<PathBuf>> {
+        let target = self.index(target)?;
 
-            // Find all files imported by this file
-            for edge in &self.imports {
-                if edge.from == current {
-                    stack.push(edge.to.clone());
+        let mut previous: HashMap<NodeIndex, Option<NodeIndex>> = HashMap::new();
+        let mut queue: VecDeque<NodeIndex> = self
+            .graph
+            .node_indices()
+            .filter(|&index| self.graph[index].is_entry_point)
+            .collect();
+        for &entry_point in &queue {
+            previous.insert(entry_point, None);
+        }
+
+        while let Some(current) = queue.pop_front() {
+            if current == target {
+                let mut chain = vec![self.graph[current].path.to_path_buf()];
+                let mut index = current;
+                while let Some(Some(parent)) = previous.get(&index) {
+                    chain.push(self.graph[*parent].path.to_path_buf());
+                    index = *parent;
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+
+            for next in self.graph.neighbors(current) {
+                if let std::collections::hash_map::Entry::Vacant(entry) = previous.entry(next) {
+                    entry.insert(Some(current));
+                    queue.push_back(next);
                 }
             }
         }
 
-        reachable
+        None
+    }
+
+    /// Import chains that keep an export alive, each ending at the import that uses it
+    ///
+    /// A chain has more than one edge when the export is reached through
+    /// re-exports: `[re-export, .., import]`.
+    pub fn usage_chains(&self, file: &Path, name: &str) -> Vec<Vec<&ImportEdge>> {
+        let mut chains = Vec::new();
+        let mut visited = HashSet::new();
+        if let Some(index) = self.index(file) {
+            let name = self.symbol_id(name);
+            self.collect_usage_chains(index, name, &mut Vec::new(), &mut visited, &mut chains);
+        }
+        chains
+    }
+
+    /// `name` is `None` for a name no import mentions, which only `*` uses
+    fn collect_usage_chains<'a>(
+        &'a self,
+        index: NodeIndex,
+        name: Option<SymbolId>,
+        chain: &mut Vec<&'a ImportEdge>,
+        visited: &mut HashSet<(NodeIndex, Option<SymbolId>)>,
+        chains: &mut Vec<Vec<&'a ImportEdge>>,
+    ) {
+        if !visited.insert((index, name)) {
+            return;
+        }
+        let star = self.symbol_id("*");
+
+        for edge in self.graph.edges_directed(index, Direction::Incoming) {
+            let importer = edge.source();
+            let edge = edge.weight();
+            chain.push(edge);
+
+            if edge.forwarded.is_empty() {
+                if edge.imported_symbols.iter().any(|&symbol| Some(symbol) == name || Some(symbol) == star) {
+                    chains.push(chain.clone());
+                }
+            } else {
+                for &(exported, imported) in &edge.forwarded {
+                    // `export * as ns` forwards every name under `ns`
+                    if Some(imported) == name || (Some(imported) == star && Some(exported) != star) {
+                        self.collect_usage_chains(importer, Some(exported), chain, visited, chains);
+                    } else if Some(exported) == star {
+                        self.collect_usage_chains(importer, name, chain, visited, chains);
+                    }
+                }
+            }
+
+            chain.pop();
+        }
+    }
+
+    /// Groups of files that import each other in a cycle
+    pub fn import_cycles(&self) -> Vec<Vec<PathBuf>> {
+        tarjan_scc(&self.graph)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || self.graph.contains_edge(component[0], component[0])
+            })
+            .map(|component| {
+                let mut paths: Vec<PathBuf> = component
+                    .into_iter()
+                    .map(|index| self.graph[index].path.to_path_buf())
+                    .collect();
+                paths.sort();
+                paths
+            })
+            .collect()
+    }
+
+    /// The node for a path, added as an undiscovered target if missing
+    ///
+    /// A node holds a reference to its path until it is pruned.
+    fn node(&mut self, path: &Path) -> NodeIndex {
+        if let Some(index) = self.index(path) {
+            return index;
+        }
+
+        let id = self.interners.paths.intern(path);
+        let index = self.graph.add_node(FileNode {
+            id,
+            path: self.interners.paths.shared(id).clone(),
+            is_entry_point: false,
+            is_test: false,
+            is_story: false,
+            has_side_effects: false,
+            used_exports: Vec::new(),
+            ambient_modules: Vec::new(),
+            declares_globals: false,
+            bare_specifiers: Vec::new(),
+            is_discovered: false,
+        });
+        self.nodes.insert(id, index);
+        index
+    }
+
+    /// Remove an undiscovered node once nothing refers to it any more
+    fn prune(&mut self, index: NodeIndex) {
+        let node = &self.graph[index];
+        let is_referenced = self
+            .graph
+            .edges_directed(index, Direction::Incoming)
+            .next()
+            .is_some();
+
+        if !node.is_discovered && !is_referenced {
+            let id = node.id;
+            self.graph.remove_node(index);
+            self.nodes.remove(&id);
+            self.interners.paths.release(id);
+        }
     }
 }
 
 /// Symbol Usage Graph - tracks exports and their references
-#[derive(Debug, Clone)]
+///
+/// Files are keyed by their id in the file graph; a file's line table holds
+/// a reference to its path, and its exports and references to their names.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SymbolUsageGraph {
-    pub exports: HashMap<PathBuf, Vec<Symbol>>,
-    pub references: HashMap<PathBuf, Vec<SymbolReference>>,
+    pub exports: HashMap<FileId, Vec<Symbol>>,
+    pub references: HashMap<FileId, Vec<SymbolReference>>,
+    /// Line table of every parsed file, to locate findings by their spans
+    pub lines: HashMap<FileId, LineIndex>,
+    /// Angular templates of each component file
+    pub templates: HashMap<FileId, Templates>,
+    /// Private class members of each file that their class never reads
+    #[serde(default)]
+    pub unread_members: HashMap<FileId, Vec<ClassMember>>,
+    /// Local bindings of each file that nothing reads
+    #[serde(default)]
+    pub unread_locals: HashMap<FileId, Vec<LocalBinding>>,
+    /// Import bindings of each file that nothing references
+    #[serde(default)]
+    pub unused_imports: HashMap<FileId, Vec<ImportBinding>>,
+    /// `sweepr-ignore` comments of each file
+    #[serde(default)]
+    pub suppressions: HashMap<FileId, Vec<Suppression>>,
+}
+
+/// Templates of an Angular component file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Templates {
+    /// Element and attribute names its inline templates use
+    pub names: Vec<String>,
+    /// Template files its `templateUrl`s name
+    pub files: Vec<PathBuf>,
 }
 
 impl SymbolUsageGraph {
@@ -103,98 +880,411 @@ impl SymbolUsageGraph {
         Self {
             exports: HashMap::new(),
             references: HashMap::new(),
+            lines: HashMap::new(),
+            templates: HashMap::new(),
+            unread_members: HashMap::new(),
+            unread_locals: HashMap::new(),
+            unused_imports: HashMap::new(),
+            suppressions: HashMap::new(),
         }
     }
 
-    pub fn add_export(&mut self, file: PathBuf, symbol: Symbol) {
-        self.exports
-            .entry(file)
-            .or_insert_with(Vec::new)
-            .push(symbol);
+    pub fn set_lines(&mut self, interners: &mut Interners, file: FileId, lines: LineIndex) {
+        if self.lines.insert(file, lines).is_none() {
+            interners.paths.retain(file);
+        }
     }
 
-    pub fn add_reference(&mut self, file: PathBuf, reference: SymbolReference) {
-        self.references
-            .entry(file)
-            .or_insert_with(Vec::new)
-            .push(reference);
+    pub fn set_templates(&mut self, file: FileId, templates: Templates) {
+        self.templates.insert(file, templates);
     }
 
-    /// Find unused exports in a file
-    pub fn unused_exports_in_file(&self, file: &PathBuf) -> Vec<&Symbol> {
-        let exports = self.exports.get(file);
-        let mut unused = Vec::new();
+    pub fn set_unread_members(&mut self, file: FileId, members: Vec<ClassMember>) {
+        self.unread_members.insert(file, members);
+    }
 
-        if let Some(exports) = exports {
-            for export in exports {
-                let mut is_used = false;
+    pub fn set_unread_locals(&mut self, file: FileId, locals: Vec<LocalBinding>) {
+        self.unread_locals.insert(file, locals);
+    }
 
-                // Check all references across all files
-                for (_ref_file, refs) in &self.references {
-                    for reference in refs {
-                        if reference.symbol == export.name {
-                            is_used = true;
-                            break;
-                        }
-                    }
-                    if is_used {
-                        break;
-                    }
-                }
+    pub fn set_unused_imports(&mut self, file: FileId, imports: Vec<ImportBinding>) {
+        self.unused_imports.insert(file, imports);
+    }
 
-                if !is_used {
-                    unused.push(export);
+    pub fn set_suppressions(&mut self, file: FileId, suppressions: Vec<Suppression>) {
+        self.suppressions.insert(file, suppressions);
+    }
+
+    /// Whether a comment in `file` silences a finding of the rule `code` on `line`
+    pub fn is_suppressed(&self, file: FileId, code: &str, line: Option<usize>) -> bool {
+        self.suppressions
+            .get(&file)
+            .is_some_and(|suppressions| suppressions.iter().any(|suppression| suppression.covers(code, line)))
+    }
+
+    /// Element and attribute names used across all templates
+    ///
+    /// Template files are read on every call, so editing one needs no
+    /// re-parse of its component.
+    pub fn template_names(&self) -> HashSet<String> {
+        let mut names = HashSet::new();
+        for templates in self.templates.values() {
+            names.extend(templates.names.iter().cloned());
+            for file in &templates.files {
+                if let Ok(html) = std::fs::read_to_string(file) {
+                    names.extend(angular::template_names(&html));
                 }
             }
         }
+        names
+    }
+
+    /// 1-based line and column of a byte offset in a parsed file, or 1:1 for
+    /// a file that wasn't parsed
+    pub fn line_col(&self, file: FileId, offset: usize) -> (usize, usize) {
+        self.lines
+            .get(&file)
+            .map_or((1, 1), |lines| lines.line_col(offset))
+    }
+
+    pub fn add_export(&mut self, interners: &mut Interners, file: FileId, export: &ParsedExport) {
+        let symbol = Symbol {
+            name: interners.names.intern(&export.name),
+            file,
+            span: export.span,
+            is_type: export.is_type,
+            visibility: export.visibility,
+            selector: export.selector.clone(),
+            used_in_module: export.used_in_module,
+            declaration: export.declaration,
+        };
+        self.exports.entry(file).or_default().push(symbol);
+    }
 
-        unused
+    pub fn add_reference(&mut self, interners: &mut Interners, file: FileId, reference: &ParsedReference) {
+        let reference = SymbolReference {
+            symbol: interners.names.intern(&reference.symbol),
+            file,
+            span: reference.span,
+        };
+        self.references.entry(file).or_default().push(reference);
+    }
+
+    /// Drop all exports, references, line table, templates, unread members
+    /// and locals, and suppressions of a file, releasing the ids they held
+    pub fn remove_file(&mut self, interners: &mut Interners, file: FileId) {
+        for export in self.exports.remove(&file).into_iter().flatten() {
+            interners.names.release(export.name);
+        }
+        for reference in self.references.remove(&file).into_iter().flatten() {
+            interners.names.release(reference.symbol);
+        }
+        if self.lines.remove(&file).is_some() {
+            interners.paths.release(file);
+        }
+        self.templates.remove(&file);
+        self.unread_members.remove(&file);
+        self.unread_locals.remove(&file);
+        self.unused_imports.remove(&file);
+        self.suppressions.remove(&file);
+    }
+
+    /// Find unused exports in a file, given `FileImportGraph::used_exports`
+    pub fn unused_exports_in_file(&self, file: FileId, used: &UsedExports) -> Vec<&Symbol> {
+        let Some(exports) = self.exports.get(&file) else {
+            return Vec::new();
+        };
+
+        exports
+            .iter()
+            .filter(|export| !used.is_used(file, export.name))
+            .collect()
     }
 }
 
 /// Dependency Graph - tracks npm package usage
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "SerializedDependencyGraph", into = "SerializedDependencyGraph")]
 pub struct DependencyGraph {
-    pub dependencies: HashMap<String, PackageInfo>,
+    /// Declared packages keyed by the manifest declaring them and the package name
+    pub dependencies: HashMap<(FileId, SymbolId), PackageInfo>,
+    /// Package imports no package.json above the importing file declares
+    pub unlisted: Vec<PackageImport>,
 }
 
-#[derive(Debug, Clone)]
+/// package.json field a dependency is declared in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DependencyKind {
+    Dependencies,
+    DevDependencies,
+    PeerDependencies,
+    OptionalDependencies,
+}
+
+impl DependencyKind {
+    /// Every kind, in the order their fields are read
+    pub const ALL: [DependencyKind; 4] = [
+        DependencyKind::Dependencies,
+        DependencyKind::DevDependencies,
+        DependencyKind::PeerDependencies,
+        DependencyKind::OptionalDependencies,
+    ];
+
+    /// Name of the package.json field
+    pub fn field(self) -> &'static str {
+        match self {
+            DependencyKind::Dependencies => "dependencies",
+            DependencyKind::DevDependencies => "devDependencies",
+            DependencyKind::PeerDependencies => "peerDependencies",
+            DependencyKind::OptionalDependencies => "optionalDependencies",
+        }
+    }
+}
+
+/// A declared package, holding references to its name, its manifest, and
+/// each of its `import_locations`
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageInfo {
-    pub name: String,
+    pub name: SymbolId,
     pub version: String,
-    pub import_locations: Vec<PathBuf>,
+    /// package.json declaring the dependency
+    pub manifest: FileId,
+    pub kind: DependencyKind,
+    pub import_locations: Vec<FileId>,
+    /// Files using the package at runtime, rather than only its types
+    #[serde(default)]
+    pub runtime_locations: Vec<FileId>,
+    /// Number of imports of the package by each file in `import_locations`
+    #[serde(default)]
+    pub import_counts: HashMap<FileId, usize>,
     pub is_used: bool,
 }
 
+/// `DependencyGraph` with its packages as a list, since JSON keys must be strings
+#[derive(Serialize, Deserialize)]
+struct SerializedDependencyGraph {
+    packages: Vec<PackageInfo>,
+    unlisted: Vec<PackageImport>,
+}
+
+impl From<DependencyGraph> for SerializedDependencyGraph {
+    fn from(graph: DependencyGraph) -> Self {
+        Self {
+            packages: graph.dependencies.into_values().collect(),
+            unlisted: graph.unlisted,
+        }
+    }
+}
+
+impl From<SerializedDependencyGraph> for DependencyGraph {
+    fn from(serialized: SerializedDependencyGraph) -> Self {
+        let dependencies = serialized
+            .packages
+            .into_iter()
+            .map(|info| ((info.manifest, info.name), info))
+            .collect();
+        Self {
+            dependencies,
+            unlisted: serialized.unlisted,
+        }
+    }
+}
+
 impl DependencyGraph {
     pub fn new() -> Self {
         Self {
             dependencies: HashMap::new(),
+            unlisted: Vec::new(),
         }
     }
 
-    pub fn add_dependency(&mut self, name: String, version: String) {
-        self.dependencies.entry(name.clone()).or_insert_with(|| {
+    /// Declare a dependency; the first declaration of a name in a manifest wins
+    pub fn add_dependency(
+        &mut self,
+        interners: &mut Interners,
+        manifest: &Path,
+        name: &str,
+        version: String,
+        kind: DependencyKind,
+    ) {
+        if Self::key(interners, manifest, name).is_some_and(|key| self.dependencies.contains_key(&key)) {
+            return;
+        }
+
+        let manifest = interners.paths.intern(manifest);
+        let name = interners.names.intern(name);
+        self.dependencies.insert(
+            (manifest, name),
             PackageInfo {
-                name: name.clone(),
+                name,
                 version,
+                manifest,
+                kind,
                 import_locations: Vec::new(),
+                runtime_locations: Vec::new(),
+                import_counts: HashMap::new(),
                 is_used: false,
+            },
+        );
+    }
+
+    /// The key of a package declared in `manifest`, if both are interned
+    fn key(interners: &Interners, manifest: &Path, name: &str) -> Option<(FileId, SymbolId)> {
+        Some((interners.paths.get(manifest)?, interners.names.get(name)?))
+    }
+
+    /// Record a use of a package outside source imports, such as in a tool
+    /// config or a script, which uses its `@types` package as well
+    pub fn record_use(&mut self, interners: &mut Interners, package: &str, file: &Path) {
+        self.record_import(interners, package, file, false);
+        self.record_import(interners, &types_package(package), file, false);
+    }
+
+    /// Record a source file's import of a package, or keep it as unlisted when
+    /// neither the package nor its `@types` package is declared
+    pub fn record_package_import(&mut self, interners: &mut Interners, import: &PackageImport) {
+        let declared = self.record_import(interners, &import.name, &import.file, import.is_type_only);
+        let typed = self.record_import(interners, &types_package(&import.name), &import.file, true);
+        if !declared && !typed {
+            self.unlisted.push(import.clone());
+        }
+    }
+
+    /// Record an import of `package` from `file`, attributed to the nearest
+    /// package.json above the file that declares it
+    ///
+    /// Returns whether any such package.json exists.
+    pub fn record_import(
+        &mut self,
+        interners: &mut Interners,
+        package: &str,
+        file: &Path,
+        is_type_only: bool,
+    ) -> bool {
+        let key = file
+            .ancestors()
+            .skip(1)
+            .filter_map(|dir| Self::key(interners, &dir.join("package.json"), package))
+            .find(|key| self.dependencies.contains_key(key));
+        let Some(dep) = key.and_then(|key| self.dependencies.get_mut(&key)) else {
+            return false;
+        };
+
+        let file = match interners.paths.get(file) {
+            Some(id) if dep.import_locations.contains(&id) => id,
+            _ => {
+                let id = interners.paths.intern(file);
+                dep.import_locations.push(id);
+                id
             }
-        });
+        };
+        if !is_type_only && !dep.runtime_locations.contains(&file) {
+            dep.runtime_locations.push(file);
+        }
+        *dep.import_counts.entry(file).or_default() += 1;
+        dep.is_used = true;
+        true
     }
 
-    pub fn record_import(&mut self, package: &str, file: PathBuf) {
-        if let Some(dep) = self.dependencies.get_mut(package) {
-            dep.import_locations.push(file);
-            dep.is_used = true;
+    /// Forget the imports recorded for a file
+    pub fn remove_imports_from(&mut self, interners: &mut Interners, file: &Path) {
+        if let Some(id) = interners.paths.get(file) {
+            for dep in self.dependencies.values_mut() {
+                if dep.import_locations.contains(&id) {
+                    dep.import_locations.retain(|&location| location != id);
+                    dep.runtime_locations.retain(|&location| location != id);
+                    dep.import_counts.remove(&id);
+                    dep.is_used = !dep.import_locations.is_empty();
+                    interners.paths.release(id);
+                }
+            }
         }
+        self.unlisted.retain(|import| import.file != file);
     }
 
-    pub fn unused_dependencies(&self) -> Vec<&PackageInfo> {
+    /// Unused `dependencies` and `devDependencies`; peer and optional ones are
+    /// often provided for, or used by, other packages
+    ///
+    /// `@types` packages are used whenever their runtime package is, and
+    /// orphaned ones are left to `orphaned_types`.
+    pub fn unused_dependencies(&self, interners: &Interners) -> Vec<&PackageInfo> {
         self.dependencies
             .values()
-            .filter(|dep| !dep.is_used)
+            .filter(|dep| self.is_unused(dep) && !self.is_orphaned_types(interners, dep))
             .collect()
     }
+
+    /// Unused `@types` packages whose runtime package their package.json no
+    /// longer declares
+    pub fn orphaned_types(&self, interners: &Interners) -> Vec<&PackageInfo> {
+        self.dependencies
+            .values()
+            .filter(|dep| self.is_unused(dep) && self.is_orphaned_types(interners, dep))
+            .collect()
+    }
+
+    /// Unused `peerDependencies`, which hosts are asked to install for nothing
+    pub fn unused_peer_dependencies(&self) -> Vec<&PackageInfo> {
+        self.dependencies
+            .values()
+            .filter(|dep| !dep.is_used && dep.kind == DependencyKind::PeerDependencies)
+            .collect()
+    }
+
+    /// Used `dependencies` that every file imports with `import type` only,
+    /// so they could be `devDependencies`; `@types` packages are left out
+    pub fn type_only_dependencies(&self, interners: &Interners) -> Vec<&PackageInfo> {
+        self.dependencies
+            .values()
+            .filter(|dep| {
+                dep.is_used
+                    && dep.runtime_locations.is_empty()
+                    && dep.kind == DependencyKind::Dependencies
+                    && !interners.names.resolve(dep.name).starts_with("@types/")
+            })
+            .collect()
+    }
+
+    fn is_unused(&self, dep: &PackageInfo) -> bool {
+        !dep.is_used
+            && matches!(
+                dep.kind,
+                DependencyKind::Dependencies | DependencyKind::DevDependencies
+            )
+    }
+
+    fn is_orphaned_types(&self, interners: &Interners, dep: &PackageInfo) -> bool {
+        runtime_package(interners.names.resolve(dep.name)).is_some_and(|runtime| {
+            !interners
+                .names
+                .get(&runtime)
+                .is_some_and(|runtime| self.dependencies.contains_key(&(dep.manifest, runtime)))
+        })
+    }
+}
+
+/// Type packages describing the runtime environment rather than a package
+const ENVIRONMENT_TYPES: &[&str] = &["@types/node", "@types/bun", "@types/deno", "@types/web"];
+
+/// The package a DefinitelyTyped package describes, e.g. `@babel/core` for
+/// `@types/babel__core`; `None` for other packages and environment types
+fn runtime_package(types: &str) -> Option<String> {
+    if ENVIRONMENT_TYPES.contains(&types) {
+        return None;
+    }
+
+    let name = types.strip_prefix("@types/")?;
+    Some(match name.split_once("__") {
+        Some((scope, name)) => format!("@{}/{}", scope, name),
+        None => name.to_string(),
+    })
+}
+
+/// The DefinitelyTyped package for a package, e.g. `@types/babel__core` for `@babel/core`
+fn types_package(package: &str) -> String {
+    match package.strip_prefix('@') {
+        Some(scoped) => format!("@types/{}", scoped.replacen('/', "__", 1)),
+        None => format!("@types/{}", package),
+    }
 }
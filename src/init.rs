@@ -0,0 +1,151 @@
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use sweepr::analyzer::{resolve_entry_points, DEFAULT_ENTRY};
+use sweepr::config::Config;
+use sweepr::error::{PurgeError, Result};
+use sweepr::frameworks;
+use sweepr::scanner::WorkspaceScanner;
+use sweepr::workspaces::{self, WorkspacePackage};
+
+/// File written by `sweepr init`, the first one `Config::find_and_load` looks for
+pub const CONFIG_FILE: &str = "sweepr.config.json";
+
+/// Frameworks without routing conventions, named for the `framework` field
+const UI_FRAMEWORKS: &[(&str, &str)] = &[
+    ("react", "react"),
+    ("vue", "vue"),
+    ("@angular/core", "angular"),
+    ("svelte", "svelte"),
+];
+
+/// Test runners and the test file globs they add to the defaults
+const TEST_RUNNERS: &[(&str, &[&str])] = &[
+    ("vitest", &[]),
+    ("jest", &[]),
+    ("mocha", &["test/**"]),
+    ("@playwright/test", &["e2e/**"]),
+    ("cypress", &["cypress/**"]),
+];
+
+/// Files tried as entry points when no package.json names any
+const ENTRY_CANDIDATES: &[&str] = &[
+    DEFAULT_ENTRY,
+    "src/index.tsx",
+    "src/index.js",
+    "src/main.ts",
+    "src/main.tsx",
+    "src/main.js",
+    "index.ts",
+    "index.js",
+];
+
+/// What `init` found out about the project
+struct Detected {
+    framework: Option<String>,
+    test_runners: Vec<&'static str>,
+    config: Config,
+}
+
+/// Inspect the project in `root` and write a starter `sweepr.config.json`
+///
+/// Asks before writing when run in a terminal, unless `yes` is set, and
+/// never replaces an existing config unless `force` is set.
+pub fn init(root: &Path, yes: bool, force: bool) -> Result<()> {
+    let path = root.join(CONFIG_FILE);
+    if path.exists() && !force {
+        return Err(PurgeError::Config(format!(
+            "{} already exists; pass --force to replace it",
+            CONFIG_FILE
+        )));
+    }
+
+    let detected = detect(root)?;
+    println!("🔎 Inspected {}", root.display());
+    println!("  🧩 Framework: {}", detected.framework.as_deref().unwrap_or("none detected"));
+    match detected.test_runners.is_empty() {
+        true => println!("  🧪 Test runner: none detected"),
+        false => println!("  🧪 Test runner: {}", detected.test_runners.join(", ")),
+    }
+    match detected.config.entry.is_empty() {
+        true => println!("  🎯 Entry points: none found, add them to `entry`"),
+        false => println!("  🎯 Entry points: {}", detected.config.entry.join(", ")),
+    }
+    println!();
+
+    let content = serde_json::to_string_pretty(&detected.config)
+        .map_err(|e| PurgeError::Config(e.to_string()))?;
+    println!("{}\n", content);
+
+    if !yes && io::stdin().is_terminal() && !confirm(&format!("Write {}?", CONFIG_FILE))? {
+        println!("Nothing written");
+        return Ok(());
+    }
+
+    std::fs::write(&path, content + "\n")?;
+    println!("✅ Wrote {}", CONFIG_FILE);
+    Ok(())
+}
+
+fn detect(root: &Path) -> Result<Detected> {
+    let packages = workspaces::discover_packages(root)?;
+    let dependencies: Vec<&str> = packages
+        .iter()
+        .flat_map(|package| package.dependencies.iter().map(|(name, _, _)| name.as_str()))
+        .collect();
+
+    // Routing frameworks first, since their conventions also add entry points
+    let framework = frameworks::detect(dependencies.iter().copied(), None)
+        .first()
+        .map(|framework| framework.name)
+        .or_else(|| {
+            UI_FRAMEWORKS
+                .iter()
+                .find(|(package, _)| dependencies.contains(package))
+                .map(|(_, name)| *name)
+        })
+        .map(str::to_string);
+
+    let mut config = Config::default();
+    let mut test_runners = Vec::new();
+    for (runner, globs) in TEST_RUNNERS {
+        if dependencies.contains(runner) {
+            test_runners.push(*runner);
+            config.tests.extend(globs.iter().map(|glob| glob.to_string()));
+        }
+    }
+
+    config.entry = entry_points(root, &packages);
+    config.framework = framework.clone();
+
+    Ok(Detected {
+        framework,
+        test_runners,
+        config,
+    })
+}
+
+/// Entry points named by package.json files, or the first common entry file found
+fn entry_points(root: &Path, packages: &[WorkspacePackage]) -> Vec<String> {
+    let scanner = WorkspaceScanner::new(root.to_path_buf());
+    let detected = resolve_entry_points(&scanner, root, packages, &[]);
+    if !detected.is_empty() {
+        return detected;
+    }
+
+    ENTRY_CANDIDATES
+        .iter()
+        .find(|candidate| root.join(candidate).is_file())
+        .map(|candidate| vec![candidate.to_string()])
+        .unwrap_or_default()
+}
+
+/// Ask a yes/no question on the terminal, defaulting to yes
+fn confirm(question: &str) -> Result<bool> {
+    print!("{} [Y/n] ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    let answer = answer.trim().to_lowercase();
+    Ok(answer.is_empty() || answer == "y" || answer == "yes")
+}
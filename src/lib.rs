@@ -0,0 +1,25 @@
+//! Sweepr finds unused files, exports, and dependencies in JavaScript and
+//! TypeScript projects.
+//!
+//! The `sweepr` binary is a thin CLI over these modules; other tools can use
+//! them directly. [`analyzer::Analyzer`] runs the same analysis as
+//! `sweepr check` and returns the report, and [`reporter::ReporterRegistry`]
+//! takes additional report formats.
+
+pub mod analyzer;
+pub mod cache;
+pub mod config;
+pub mod error;
+pub mod fixer;
+pub mod frameworks;
+pub mod graph;
+pub mod parser;
+pub mod reporter;
+pub mod resolver;
+pub mod rules;
+pub mod scanner;
+pub mod scripts;
+pub mod source;
+pub mod timing;
+pub mod tools;
+pub mod workspaces;
@@ -0,0 +1,413 @@
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidChangeWatchedFiles, DidCloseTextDocument, DidOpenTextDocument,
+    DidSaveTextDocument, Notification as _, PublishDiagnostics,
+};
+use lsp_types::request::{RegisterCapability, Request as _};
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, DiagnosticTag, DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams, DidChangeWatchedFilesRegistrationOptions,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    FileSystemWatcher, GlobPattern, InitializeParams, NumberOrString, Position,
+    PublishDiagnosticsParams, Range, Registration, RegistrationParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url,
+};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use sweepr::analyzer::Workspace;
+use sweepr::error::{PurgeError, Result};
+use sweepr::rules::{rule_code, AnalysisReport, Finding};
+use sweepr::source::LineIndex;
+
+/// Serve the Language Server Protocol on stdin and stdout until the client exits
+///
+/// `build` runs from the folder the client opened, once it has said which.
+/// Unused files are reported across the workspace; findings within a file,
+/// such as unused exports and unresolved imports, for open documents.
+pub fn serve(build: impl FnOnce() -> Result<Workspace>) -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::FULL),
+            save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+            ..Default::default()
+        })),
+        ..Default::default()
+    };
+    let params = connection
+        .initialize(serde_json::to_value(capabilities).map_err(protocol_error)?)
+        .map_err(protocol_error)?;
+    let params: InitializeParams = from_value(params)?;
+
+    if let Some(root) = root_path(&params) {
+        std::env::set_current_dir(root)?;
+    }
+    let workspace = build()?;
+    let report = workspace.analyze();
+
+    let mut server = Server {
+        connection,
+        workspace,
+        report,
+        stale: false,
+        documents: HashMap::new(),
+        published: HashMap::new(),
+    };
+    if watches_files(&params) {
+        server.register_file_watcher()?;
+    }
+    server.publish()?;
+    server.run()?;
+
+    io_threads.join()?;
+    Ok(())
+}
+
+struct Server {
+    connection: Connection,
+    workspace: Workspace,
+    /// Findings as of the last analysis
+    report: AnalysisReport,
+    /// Whether the graphs changed since `report`
+    stale: bool,
+    /// Text of the open documents, which may not be saved yet
+    documents: HashMap<PathBuf, String>,
+    /// Diagnostics last sent per file
+    published: HashMap<Url, Vec<Diagnostic>>,
+}
+
+impl Server {
+    fn run(mut self) -> Result<()> {
+        let receiver = self.connection.receiver.clone();
+        while let Ok(message) = receiver.recv() {
+            // Edits arrive faster than analysis runs, so everything queued is
+            // handled before analyzing again
+            let mut changed = false;
+            for message in std::iter::once(message).chain(receiver.try_iter()) {
+                match message {
+                    Message::Request(request) => {
+                        if self.connection.handle_shutdown(&request).map_err(protocol_error)? {
+                            return Ok(());
+                        }
+                        self.reply_unsupported(request)?;
+                    }
+                    Message::Notification(notification) => changed |= self.notify(notification)?,
+                    Message::Response(_) => {}
+                }
+            }
+
+            if changed {
+                if self.stale {
+                    self.report = self.workspace.analyze();
+                    self.stale = false;
+                }
+                self.publish()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Handle a notification, returning whether diagnostics may have changed
+    fn notify(&mut self, notification: Notification) -> Result<bool> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = from_value(notification.params)?;
+                let Ok(path) = params.text_document.uri.to_file_path() else {
+                    return Ok(false);
+                };
+                self.documents.insert(path, params.text_document.text);
+                Ok(true)
+            }
+            DidChangeTextDocument::METHOD => {
+                let mut params: DidChangeTextDocumentParams = from_value(notification.params)?;
+                // With full sync, the last change holds the whole document
+                let (Ok(path), Some(change)) =
+                    (params.text_document.uri.to_file_path(), params.content_changes.pop())
+                else {
+                    return Ok(false);
+                };
+                self.documents.insert(path.clone(), change.text);
+                Ok(self.update(&path))
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams = from_value(notification.params)?;
+                let Ok(path) = params.text_document.uri.to_file_path() else {
+                    return Ok(false);
+                };
+                self.apply_changes(HashSet::from([path]))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = from_value(notification.params)?;
+                let Ok(path) = params.text_document.uri.to_file_path() else {
+                    return Ok(false);
+                };
+                // Unsaved edits are dropped along with the document
+                self.documents.remove(&path);
+                self.apply_changes(HashSet::from([path]))?;
+                Ok(true)
+            }
+            DidChangeWatchedFiles::METHOD => {
+                let params: DidChangeWatchedFilesParams = from_value(notification.params)?;
+                let changed = params
+                    .changes
+                    .iter()
+                    .filter_map(|change| change.uri.to_file_path().ok())
+                    .collect();
+                self.apply_changes(changed)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Analyze an open document's text in place of the file on disk
+    ///
+    /// Returns whether the graphs changed. Documents outside the analysis,
+    /// e.g. ignored files, stay out of it.
+    fn update(&mut self, path: &Path) -> bool {
+        if !self.workspace.file_graph.contains_file(path) {
+            return false;
+        }
+
+        match self.workspace.replace_source(path, &self.documents[path]) {
+            Ok(()) => {
+                self.stale = true;
+                true
+            }
+            // A document that doesn't parse mid-edit keeps its last findings
+            Err(e) => {
+                eprintln!("⚠️  {}", e);
+                false
+            }
+        }
+    }
+
+    /// Re-read changed files from disk, keeping the text of open documents
+    fn apply_changes(&mut self, changed: HashSet<PathBuf>) -> Result<bool> {
+        let mut affected = self.workspace.apply_changes(&changed)?;
+        self.stale |= affected;
+        for path in &changed {
+            if self.documents.contains_key(path) {
+                affected |= self.update(path);
+            }
+        }
+        Ok(affected)
+    }
+
+    /// Send the diagnostics of every file whose findings changed since they
+    /// were last sent
+    fn publish(&mut self) -> Result<()> {
+        let mut diagnostics = diagnostics(&self.report, &self.documents);
+        for uri in self.published.keys() {
+            diagnostics.entry(uri.clone()).or_default();
+        }
+
+        for (uri, diagnostics) in diagnostics {
+            if self.published.get(&uri).map_or(diagnostics.is_empty(), |sent| *sent == diagnostics) {
+                continue;
+            }
+
+            let params = PublishDiagnosticsParams {
+                uri: uri.clone(),
+                diagnostics: diagnostics.clone(),
+                version: None,
+            };
+            self.send(Notification::new(PublishDiagnostics::METHOD.to_string(), params))?;
+            if diagnostics.is_empty() {
+                self.published.remove(&uri);
+            } else {
+                self.published.insert(uri, diagnostics);
+            }
+        }
+        Ok(())
+    }
+
+    /// Ask the client to report created, changed, and deleted files, which
+    /// can make other files used or unused
+    fn register_file_watcher(&self) -> Result<()> {
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: vec![FileSystemWatcher {
+                glob_pattern: GlobPattern::String("**/*".to_string()),
+                kind: None,
+            }],
+        };
+        let params = RegistrationParams {
+            registrations: vec![Registration {
+                id: "sweepr-watched-files".to_string(),
+                method: DidChangeWatchedFiles::METHOD.to_string(),
+                register_options: Some(serde_json::to_value(options).map_err(protocol_error)?),
+            }],
+        };
+        let id = RequestId::from("sweepr-register-watcher".to_string());
+        self.send(Request::new(id, RegisterCapability::METHOD.to_string(), params))
+    }
+
+    fn reply_unsupported(&self, request: Request) -> Result<()> {
+        let message = format!("Unsupported request: {}", request.method);
+        self.send(Response::new_err(request.id, ErrorCode::MethodNotFound as i32, message))
+    }
+
+    fn send(&self, message: impl Into<Message>) -> Result<()> {
+        self.connection.sender.send(message.into()).map_err(protocol_error)
+    }
+}
+
+/// Diagnostics per file: unused and removable files across the workspace, and the findings
+/// located within open documents
+fn diagnostics(
+    report: &AnalysisReport,
+    documents: &HashMap<PathBuf, String>,
+) -> HashMap<Url, Vec<Diagnostic>> {
+    let mut diagnostics: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+    for file in &report.unused_files {
+        if let Ok(uri) = Url::from_file_path(&file.path) {
+            diagnostics
+                .entry(uri)
+                .or_default()
+                .push(diagnostic("unused-files", None, Range::default()));
+        }
+    }
+    for file in &report.removable_files {
+        if let Ok(uri) = Url::from_file_path(&file.path) {
+            diagnostics
+                .entry(uri)
+                .or_default()
+                .push(diagnostic("removable-files", None, Range::default()));
+        }
+    }
+
+    let exports = report
+        .unused_exports
+        .iter()
+        .map(|export| ("unused-exports", &export.file, &export.name, export.span));
+    let types = report
+        .unused_types
+        .iter()
+        .map(|export| ("unused-types", &export.file, &export.name, export.span));
+    let module_only = report
+        .module_only_exports
+        .iter()
+        .map(|export| ("module-only-exports", &export.file, &export.name, export.span));
+    let private_members = report
+        .unused_private_members
+        .iter()
+        .map(|member| ("unused-private-members", &member.file, &member.name, member.span));
+    let locals = report
+        .unused_locals
+        .iter()
+        .map(|local| ("unused-locals", &local.file, &local.name, local.span));
+    let imports = report
+        .unused_imports
+        .iter()
+        .map(|import| ("unused-imports", &import.file, &import.name, import.span));
+    let test_only = report
+        .test_only_exports
+        .iter()
+        .map(|export| ("test-only-exports", &export.file, &export.name, export.span));
+    let story_only = report
+        .story_only_exports
+        .iter()
+        .map(|export| ("story-only-exports", &export.file, &export.name, export.span));
+    let unlisted = report
+        .unlisted_dependencies
+        .iter()
+        .map(|dep| ("unlisted-deps", &dep.file, &dep.name, dep.span));
+    let unresolved = report
+        .unresolved_imports
+        .iter()
+        .map(|import| ("unresolved-imports", &import.file, &import.specifier, import.span));
+
+    let indexes: HashMap<&PathBuf, LineIndex> = documents
+        .iter()
+        .map(|(path, source)| (path, LineIndex::new(source)))
+        .collect();
+    for (rule, file, symbol, (start, end)) in exports
+        .chain(types)
+        .chain(module_only)
+        .chain(private_members)
+        .chain(locals)
+        .chain(imports)
+        .chain(test_only)
+        .chain(story_only)
+        .chain(unlisted)
+        .chain(unresolved)
+    {
+        let Some(index) = indexes.get(file) else {
+            continue;
+        };
+        let Ok(uri) = Url::from_file_path(file) else {
+            continue;
+        };
+
+        let position = |offset| {
+            let (line, character) = index.utf16_position(offset);
+            Position::new(line as u32, character as u32)
+        };
+        let range = Range::new(position(start), position(end));
+        diagnostics
+            .entry(uri)
+            .or_default()
+            .push(diagnostic(rule, Some(symbol), range));
+    }
+
+    diagnostics
+}
+
+fn diagnostic(rule: &'static str, symbol: Option<&str>, range: Range) -> Diagnostic {
+    let finding = Finding {
+        rule,
+        code: rule_code(rule),
+        path: PathBuf::new(),
+        symbol: symbol.map(str::to_string),
+        line: None,
+        column: None,
+    };
+    // Editors fade out code that can be removed; a module-only export's
+    // declaration stays
+    let unnecessary = !matches!(rule, "unlisted-deps" | "unresolved-imports" | "module-only-exports");
+
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(finding.code.to_string())),
+        source: Some("sweepr".to_string()),
+        message: finding.message(),
+        tags: unnecessary.then(|| vec![DiagnosticTag::UNNECESSARY]),
+        ..Default::default()
+    }
+}
+
+/// Folder the client opened, when it is on disk
+fn root_path(params: &InitializeParams) -> Option<PathBuf> {
+    #[allow(deprecated)]
+    let root_uri = params.root_uri.as_ref();
+    params
+        .workspace_folders
+        .as_ref()
+        .and_then(|folders| folders.first())
+        .map(|folder| &folder.uri)
+        .or(root_uri)
+        .and_then(|uri| uri.to_file_path().ok())
+}
+
+/// Whether the client can report file changes once asked to
+fn watches_files(params: &InitializeParams) -> bool {
+    params
+        .capabilities
+        .workspace
+        .as_ref()
+        .and_then(|workspace| workspace.did_change_watched_files)
+        .and_then(|watched_files| watched_files.dynamic_registration)
+        .unwrap_or(false)
+}
+
+fn from_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T> {
+    serde_json::from_value(value).map_err(protocol_error)
+}
+
+fn protocol_error(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> PurgeError {
+    PurgeError::Io(std::io::Error::other(e))
+}
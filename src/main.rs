@@ -1,231 +1,559 @@
+mod baseline;
+mod changed;
 mod cli;
-mod config;
-mod error;
-mod graph;
-mod parser;
-mod reporter;
-mod rules;
-mod scanner;
-
-use crate::config::Config;
-use crate::error::{PurgeError, Result};
-use crate::graph::{DependencyGraph, FileImportGraph, SymbolUsageGraph};
-use crate::reporter::{CliReporter, JsonReporter, Reporter};
-use crate::rules::RulesEngine;
-use crate::scanner::WorkspaceScanner;
+mod daemon;
+mod depcheck;
+mod deps;
+mod init;
+mod lsp;
+mod watch;
+
+use crate::baseline::Baseline;
+use crate::cli::{CacheAction, CheckArgs, Cli, Commands, GraphFormat, ScanArgs};
 use clap::Parser;
+use sweepr::analyzer::{Analyzer, Workspace};
+use sweepr::cache;
+use sweepr::config::Config;
+use sweepr::error::{PurgeError, Result, EXIT_CONFIG_ERROR, EXIT_PARSE_ERROR};
+use sweepr::graph::{self, GraphFilter};
+use sweepr::reporter::{ReportContext, ReportOptions, ReporterRegistry};
+use sweepr::resolver;
+use sweepr::rules::AnalysisReport;
+use sweepr::scanner;
+use sweepr::source;
+use sweepr::timing::{self, PhaseTiming};
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::time::Instant;
 
-#[derive(Parser, Debug)]
-#[command(name = "sweepr")]
-#[command(about = "Blazing-fast dead code elimination for JavaScript and TypeScript", long_about = None)]
-struct Cli {
-    #[command(subcommand)]
-    command: Commands,
-}
-
-#[derive(Parser, Debug)]
-enum Commands {
-    /// Check for unused code (read-only, no modifications)
-    Check {
-        /// Output results in JSON format
-        #[arg(short, long)]
-        json: bool,
-
-        /// Custom entry points
-        #[arg(short, long)]
-        entry: Vec<String>,
-    },
-
-    /// Fix unused code (safe modifications only)
-    Fix {
-        /// Allow dangerous operations (file deletion)
-        #[arg(long, name = "unsafe")]
-        allow_unsafe: bool,
-
-        /// Output results in JSON format
-        #[arg(short, long)]
-        json: bool,
-
-        /// Custom entry points
-        #[arg(short, long)]
-        entry: Vec<String>,
-    },
-}
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            // `--help` and `--version` also arrive here, printed to stdout
+            return match e.use_stderr() {
+                true => ExitCode::from(EXIT_CONFIG_ERROR),
+                false => ExitCode::SUCCESS,
+            };
+        }
+    };
 
     // Initialize logging
     tracing_subscriber::fmt::init();
 
-    match cli.command {
-        Commands::Check { json, entry } => {
-            run_check(json, entry)?;
+    match run(cli) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(e.exit_code())
         }
-        Commands::Fix { allow_unsafe: _, json, entry } => {
-            run_check(json, entry)?;
+    }
+}
+
+/// Run the selected command: exit status 0 when clean and 1 when findings
+/// fail the run; errors map to their own statuses
+fn run(cli: Cli) -> Result<ExitCode> {
+    match cli.command {
+        Commands::Check { args } => run_check(&args, false),
+        Commands::Fix { allow_unsafe, args } => {
+            let code = run_check(&args, allow_unsafe)?;
             // TODO: Implement fix functionality
             eprintln!("⚠️  Fix functionality is not yet implemented");
+            Ok(code)
+        }
+        Commands::Graph {
+            format,
+            scan,
+            path,
+            from,
+            depth,
+            output,
+        } => run_graph(&scan, format, path.as_deref(), from.as_deref(), depth, output.as_deref()),
+        Commands::Cache { action } => {
+            run_cache(action)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Commands::Init { yes, force } => {
+            init::init(&std::env::current_dir()?, yes, force)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Commands::Why { target, scan } => run_why(&target, &scan),
+        Commands::Depcheck { scan } => run_depcheck(&scan),
+        Commands::Deps { packages, json, scan } => run_deps(&packages, json, &scan),
+        Commands::Lsp { scan } => {
+            source::disable_mmap();
+            // Stdout carries the protocol, so no progress output
+            lsp::serve(|| build_workspace(&scan, false))?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Commands::Daemon { socket, scan } => {
+            source::disable_mmap();
+            let analyzer = analyzer(&scan, false)?;
+            let mut workspace = analyzer.build()?;
+            daemon::serve(&mut workspace, socket.as_deref(), &|| analyzer.build())?;
+            Ok(ExitCode::SUCCESS)
         }
     }
+}
 
-    Ok(())
+/// Print a progress line when progress output is enabled
+macro_rules! progress {
+    ($enabled:expr) => {
+        if $enabled {
+            println!();
+        }
+    };
+    ($enabled:expr, $($arg:tt)*) => {
+        if $enabled {
+            println!($($arg)*);
+        }
+    };
 }
 
-fn run_check(json: bool, entry_points: Vec<String>) -> Result<()> {
+/// Run the analysis and report it, returning failure when `--fail-on` findings
+/// exist beyond the `--max-issues` budget, or `EXIT_PARSE_ERROR` when files
+/// failed to parse
+fn run_check(args: &CheckArgs, allow_unsafe: bool) -> Result<ExitCode> {
     let start = Instant::now();
+    let format = args.format();
+    if args.watch {
+        source::disable_mmap();
+    }
 
-    // Load configuration
-    let config = Config::find_and_load()?;
+    let registry = ReporterRegistry::default();
+    if !registry.contains(format) {
+        let available: Vec<&str> = registry.names().collect();
+        return Err(PurgeError::Config(format!(
+            "Unknown report format '{}' (available: {})",
+            format,
+            available.join(", ")
+        )));
+    }
 
-    // Determine entry points
-    let entry_points = if entry_points.is_empty() {
-        config.entry
-    } else {
-        entry_points
+    // Machine-readable reports written to stdout must not be mixed with progress output
+    let show_progress = !args.quiet && (format == "cli" || args.output.is_some());
+
+    if let Some(path) = args.stdin_path.as_ref().filter(|path| !scanner::is_source_file(path)) {
+        return Err(PurgeError::Config(format!(
+            "--stdin-path {} is not a JavaScript, TypeScript, or component file",
+            path.display()
+        )));
+    }
+
+    let analyzer = analyzer(&args.scan, show_progress)?;
+    let mut workspace = match &args.file {
+        Some(_) => {
+            let path = focused_file(args, &std::env::current_dir()?).expect("--file is set");
+            analyzer.build_file(&path)?
+        }
+        None => analyzer.build()?,
+    };
+    if let Some(path) = args.stdin.then(|| focused_file(args, &workspace.root)).flatten() {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source)?;
+        workspace.replace_source(&path, &source)?;
+    }
+    // The project as of `--since`, analyzed once for every report to compare
+    // against; rules read some files, e.g. Angular templates, so the checkout
+    // stays until the last report
+    let since = match &args.since {
+        Some(git_ref) => {
+            progress!(show_progress, "🕰️  Analyzing {} to compare against...", git_ref);
+            let checkout = changed::Checkout::new(&workspace.root, git_ref)?;
+            let analyzer = analyzer.clone().with_root(checkout.root.clone()).with_cache(false).with_progress(false);
+            let analysis = analyzer.build()?.analyze();
+            Some((checkout, analysis))
+        }
+        None => None,
     };
+    let before = since.as_ref().map(|(checkout, analysis)| (analysis, checkout.root.as_path()));
 
-    println!("🚀 Scanning workspace...");
+    let status = report(&workspace, before, args, &registry, allow_unsafe, show_progress)?;
 
-    // Scan workspace
-    let current_dir = std::env::current_dir()?;
-    let scanner = WorkspaceScanner::new(current_dir);
-    let discovery = scanner.discover(entry_points)?;
+    if show_progress && format == "cli" {
+        println!("⏱️  Completed in {:.2?}", start.elapsed());
+    }
 
-    println!("  📄 Found {} files", discovery.files.len());
-    println!("  🎯 Entry points: {}", discovery.entry_points.len());
-    println!();
+    if args.watch {
+        progress!(show_progress, "👀 Watching for changes (Ctrl+C to stop)...");
+        watch::watch(&mut workspace, || analyzer.build(), |workspace| {
+            let start = Instant::now();
+            progress!(show_progress, "\n🔄 Change detected, re-analyzing...");
+            report(workspace, before, args, &registry, allow_unsafe, show_progress)?;
+            progress!(show_progress, "⏱️  Completed in {:.2?}", start.elapsed());
+            Ok(())
+        })?;
+    }
 
-    println!("🔬 Analyzing code...");
+    Ok(status)
+}
 
-    // Parse all files
-    let files = discovery.files.clone();
-    let parsed_files = parser::AstAnalyzer::parse_files_parallel(files)?;
+/// The `--stdin-path` or `--file` file under `root`, spelled the way
+/// discovery spells paths
+fn focused_file(args: &CheckArgs, root: &Path) -> Option<PathBuf> {
+    let path = args.stdin_path.as_ref().or(args.file.as_ref())?;
+    Some(
+        root.join(path)
+            .components()
+            .filter(|component| component != &std::path::Component::CurDir)
+            .collect(),
+    )
+}
 
-    println!("  ✓ Parsed {} files", parsed_files.len());
+/// Analyze the workspace and write the report, returning the exit status:
+/// `EXIT_PARSE_ERROR` when reported files failed to parse, otherwise failure
+/// when `--fail-on` findings exist, or exceed `--max-issues` when a budget is set
+///
+/// With `before`, the analysis of the project at `--since` and its root, only
+/// findings it doesn't have are reported. Findings the `--baseline` records
+/// never are.
+fn report(
+    workspace: &Workspace,
+    before: Option<(&AnalysisReport, &Path)>,
+    args: &CheckArgs,
+    registry: &ReporterRegistry,
+    allow_unsafe: bool,
+    show_progress: bool,
+) -> Result<ExitCode> {
+    // Run analysis
+    let mut analysis = workspace.analyze();
+    if !args.timing {
+        analysis.timings.clear();
+    }
 
-    // Build graphs
-    let mut file_graph = FileImportGraph::new();
-    let mut symbol_graph = SymbolUsageGraph::new();
-    let mut dependency_graph = DependencyGraph::new();
+    // Analysis covers the whole project so reachability stays correct; the
+    // filter only narrows what is reported
+    if !args.filter.is_empty() {
+        let filter = scanner::build_globset(&args.filter, "filter")?;
+        let root = &workspace.root;
+        analysis.retain_paths(|path| filter.is_match(path.strip_prefix(root).unwrap_or(path)));
+    }
+    if let Some(path) = focused_file(args, &workspace.root) {
+        analysis.retain_paths(|file| file == path);
+    }
+    if let Some(git_ref) = &args.changed {
+        let changed = changed::changed_files(&workspace.root, git_ref)?;
+        analysis.retain_paths(|path| changed.contains(path));
+    }
+    if let Some((before, before_root)) = before {
+        analysis.retain_introduced(&workspace.root, before, before_root);
+    }
+    if let Some(path) = &args.baseline {
+        let baseline = if args.update_baseline {
+            let baseline = Baseline::from_report(&analysis, &workspace.root);
+            baseline.save(path)?;
+            progress!(
+                show_progress,
+                "📌 Recorded {} findings in {}",
+                baseline.findings.len(),
+                path.display()
+            );
+            baseline
+        } else {
+            Baseline::load(path)?
+        };
+        baseline.apply(&mut analysis, &workspace.root);
+    }
+    analysis.sort(args.sort);
+    // Attributed after filtering, so package totals match what is reported
+    if workspace.packages.len() > 1 {
+        analysis.attribute_packages(&workspace.packages);
+    }
 
-    // Add files to graph
-    for file in &discovery.files {
-        file_graph.add_file(file.clone(), discovery.entry_points.contains(file));
+    // Generate report
+    let options = ReportOptions {
+        quiet: args.quiet,
+        color: args.output.is_none()
+            && io::stdout().is_terminal()
+            && std::env::var_os("NO_COLOR").is_none(),
+        show_fixes: args.show_fixes,
+        allow_unsafe,
+        group_by: args.group_by,
+        summary: args.summary,
+    };
+    let context = ReportContext {
+        root: &workspace.root,
+        file_graph: &workspace.file_graph,
+        options: &options,
+    };
+    let reporter = registry
+        .create(args.format(), &context)
+        .expect("format was validated against the registry");
+
+    let (written, duration) =
+        timing::timed(|| write_output(args.output.as_deref(), |out| reporter.report(&analysis, out)));
+    written?;
+    if args.timing {
+        // On stderr, so a machine-readable report on stdout stays intact
+        let mut timings = analysis.timings.clone();
+        timings.push(PhaseTiming::new("report", duration));
+        eprintln!("{}", if args.quiet { "Timing" } else { "⏱️  Timing" });
+        eprint!("{}", timing::table(&timings));
+    }
+    if let Some(path) = &args.output {
+        progress!(show_progress, "📝 Report written to {}", path.display());
     }
 
-    // Process parsed files
-    for parsed_file in &parsed_files {
-        // Add imports to file graph
-        for import in &parsed_file.imports {
-            file_graph.add_import(import.clone());
+    if !analysis.parse_failures.is_empty() {
+        return Ok(ExitCode::from(EXIT_PARSE_ERROR));
+    }
+    let failing = analysis
+        .findings()
+        .iter()
+        .filter(|finding| args.fails_on(finding.rule))
+        .count();
+    match args.max_issues {
+        Some(budget) if failing > budget => {
+            progress!(show_progress, "🚨 {} issues exceed the budget of {}", failing, budget);
+            Ok(ExitCode::FAILURE)
         }
+        Some(_) => Ok(ExitCode::SUCCESS),
+        None if failing > 0 => Ok(ExitCode::FAILURE),
+        None => Ok(ExitCode::SUCCESS),
+    }
+}
 
-        // Add exports to symbol graph
-        for export in &parsed_file.exports {
-            symbol_graph.add_export(parsed_file.path.clone(), export.clone());
+/// Build the graphs and print them without running any rules
+fn run_graph(
+    scan: &ScanArgs,
+    format: GraphFormat,
+    subtree: Option<&Path>,
+    from: Option<&Path>,
+    depth: Option<usize>,
+    output: Option<&Path>,
+) -> Result<ExitCode> {
+    let workspace = build_workspace(scan, false)?;
+    let file_graph = &workspace.file_graph;
+
+    let mut filter = GraphFilter::default();
+    if let Some(dir) = subtree {
+        filter = filter.with_subtree(workspace.root.join(dir));
+    }
+    if let Some(from) = from {
+        let file = resolver::normalize(&workspace.root.join(from));
+        if !file_graph.contains_file(&file) {
+            return Err(PurgeError::FileNotFound(from.display().to_string()));
         }
+        filter = filter.with_reach(file_graph, &[file], depth);
+    } else if depth.is_some() {
+        let entry_points: Vec<PathBuf> = file_graph
+            .files()
+            .filter(|file| file.is_entry_point)
+            .map(|file| file.path.to_path_buf())
+            .collect();
+        filter = filter.with_reach(file_graph, &entry_points, depth);
+    }
+
+    let rendered = match format {
+        GraphFormat::Dot => graph::dot::to_dot(file_graph, &workspace.root, &filter),
+        GraphFormat::Mermaid => graph::mermaid::to_mermaid(file_graph, &workspace.root, &filter),
+        GraphFormat::Json => graph::json::to_json(file_graph, &workspace.root, &filter),
+    };
+
+    write_output(output, |out| out.write_all(rendered.as_bytes()))?;
+    Ok(ExitCode::SUCCESS)
+}
 
-        // Add references to symbol graph
-        for reference in &parsed_file.references {
-            symbol_graph.add_reference(parsed_file.path.clone(), reference.clone());
+/// Print the import chain keeping a file alive, or the imports keeping an export alive
+fn run_why(target: &str, scan: &ScanArgs) -> Result<ExitCode> {
+    let workspace = build_workspace(scan, false)?;
+    match explain(&workspace, target) {
+        Ok(explanation) => {
+            print!("{}", explanation);
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(message) => {
+            println!("{}", message);
+            Ok(ExitCode::FAILURE)
         }
     }
+}
 
-    println!("  ✓ Built analysis graphs");
-
-    // Load package.json dependencies
-    if let Ok(deps) = load_dependencies() {
-        for (name, version) in deps {
-            dependency_graph.add_dependency(name, version);
+/// Explain why a file or exported symbol is kept, or why there is nothing
+/// to explain when the target is neither
+fn explain(workspace: &Workspace, target: &str) -> std::result::Result<String, String> {
+    let root = &workspace.root;
+    let relative = |path: &Path| path.strip_prefix(root).unwrap_or(path).display().to_string();
+    let mut out = String::new();
+
+    let file = root.join(target);
+    if file.is_file() {
+        let file = resolver::normalize(&file);
+        if !workspace.file_graph.contains_file(&file) {
+            return Err(format!("{} is not part of the scanned project", target));
         }
 
-        // Record imports from parsed files
-        for parsed_file in &parsed_files {
-            for import in &parsed_file.imports {
-                let source = import.to.to_string_lossy().to_string();
-                if let Some(package_name) = extract_package_name(&source) {
-                    dependency_graph.record_import(&package_name, parsed_file.path.clone());
+        match workspace.file_graph.import_chain(&file) {
+            Some(chain) if chain.len() == 1 => {
+                let _ = writeln!(out, "{} is an entry point", target);
+            }
+            Some(chain) => {
+                let _ = writeln!(out, "{} is imported through:", target);
+                for (depth, path) in chain.iter().enumerate() {
+                    let _ = writeln!(out, "{}{}", "  ".repeat(depth + 1), relative(path));
                 }
             }
+            None => {
+                let _ = writeln!(out, "{} is not reachable from any entry point", target);
+            }
         }
-
-        println!("  ✓ Loaded {} dependencies", dependency_graph.dependencies.len());
+        return Ok(out);
     }
 
-    println!();
-
-    // Run analysis
-    let analysis = RulesEngine::analyze(&dependency_graph, &file_graph, &symbol_graph);
+    let file_graph = &workspace.file_graph;
+    let name = file_graph.symbol_id(target);
+    let mut exporters: Vec<&Path> = workspace
+        .symbol_graph
+        .exports
+        .iter()
+        .filter(|(_, symbols)| symbols.iter().any(|symbol| Some(symbol.name) == name))
+        .map(|(&file, _)| file_graph.path(file))
+        .collect();
+    exporters.sort();
+
+    if exporters.is_empty() {
+        return Err(format!("No file or exported symbol named {}", target));
+    }
 
-    // Generate report
-    let duration = start.elapsed();
+    for exporter in exporters {
+        let _ = writeln!(out, "{} is exported from {}", target, relative(exporter));
 
-    if json {
-        let reporter = JsonReporter;
-        reporter.report(&analysis)?;
-    } else {
-        let reporter = CliReporter;
-        reporter.report(&analysis)?;
-        println!("⏱️  Completed in {:.2?}", duration);
+        let chains = file_graph.usage_chains(exporter, target);
+        if chains.is_empty() {
+            let _ = writeln!(out, "  not imported anywhere");
+        }
+        for chain in chains {
+            let Some((import, re_exports)) = chain.split_last() else {
+                continue;
+            };
+            let via: Vec<String> = re_exports.iter().map(|edge| relative(file_graph.path(edge.from))).collect();
+            if via.is_empty() {
+                let _ = writeln!(out, "  used by {}", relative(file_graph.path(import.from)));
+            } else {
+                let from = relative(file_graph.path(import.from));
+                let _ = writeln!(out, "  used by {} (via {})", from, via.join(" → "));
+            }
+        }
     }
 
-    Ok(())
+    Ok(out)
 }
 
-fn load_dependencies() -> Result<Vec<(String, String)>> {
-    let current_dir = std::env::current_dir()?;
-    let package_json_path = current_dir.join("package.json");
+/// Run only the dependency rules and print their findings as depcheck does
+fn run_depcheck(scan: &ScanArgs) -> Result<ExitCode> {
+    let mut workspace = build_workspace(scan, false)?;
+    workspace.rules = workspace.rules.dependencies_only();
 
-    if !package_json_path.exists() {
-        return Ok(Vec::new());
-    }
+    let report = depcheck::DepcheckReport::new(&workspace, &workspace.analyze());
+    let json = serde_json::to_string_pretty(&report).map_err(|e| PurgeError::Io(e.into()))?;
+    println!("{}", json);
 
-    let content = std::fs::read_to_string(&package_json_path)
-        .map_err(|e| PurgeError::Io(e))?;
+    Ok(match report.has_issues() {
+        true => ExitCode::FAILURE,
+        false => ExitCode::SUCCESS,
+    })
+}
 
-    let json: serde_json::Value = serde_json::from_str(&content)
-        .map_err(|e| PurgeError::Config(format!("Invalid package.json: {}", e)))?;
+/// Print the files importing each dependency, or only the `packages` named
+///
+/// Fails when a named package isn't declared by any package.json.
+fn run_deps(packages: &[String], json: bool, scan: &ScanArgs) -> Result<ExitCode> {
+    let workspace = build_workspace(scan, false)?;
+    let usages = deps::usage(&workspace, packages);
 
-    let mut dependencies = Vec::new();
+    if json {
+        let json = serde_json::to_string_pretty(&usages).map_err(|e| PurgeError::Io(e.into()))?;
+        println!("{}", json);
+    } else {
+        print!("{}", deps::render(&usages, &workspace.root));
+    }
 
-    if let Some(deps) = json.get("dependencies").and_then(|d| d.as_object()) {
-        for (name, version) in deps {
-            if let Some(version_str) = version.as_str() {
-                dependencies.push((name.clone(), version_str.to_string()));
-            }
-        }
+    let missing: Vec<&String> = packages
+        .iter()
+        .filter(|name| !usages.iter().any(|usage| &usage.name == *name))
+        .collect();
+    for name in &missing {
+        eprintln!("{} is not declared in any package.json", name);
     }
+    Ok(if missing.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
 
-    if let Some(dev_deps) = json.get("devDependencies").and_then(|d| d.as_object()) {
-        for (name, version) in dev_deps {
-            if let Some(version_str) = version.as_str() {
-                dependencies.push((name.clone(), version_str.to_string()));
+/// Clear, summarize, or locate the cache of the project in the current directory
+fn run_cache(action: CacheAction) -> Result<()> {
+    let root = std::env::current_dir()?;
+    match action {
+        CacheAction::Clear => match cache::clear(&root)? {
+            true => println!("🧹 Cleared {}", cache::CACHE_DIR),
+            false => println!("Nothing to clear, {} does not exist", cache::CACHE_DIR),
+        },
+        CacheAction::Stats => {
+            let stats = cache::stats(&root);
+            println!("📦 {}", cache::cache_dir(&root).display());
+            println!("  Parsed files: {}", stats.parsed_files);
+            println!("  Graph snapshot: {}", if stats.has_graphs { "yes" } else { "no" });
+            println!("  Size: {:.1} KiB", stats.bytes as f64 / 1024.0);
+            if stats.parsed_files > 0 && !stats.is_current {
+                println!("  ⚠️  Written by another version of sweepr, rebuilt on the next run");
             }
         }
+        CacheAction::Path => println!("{}", cache::cache_dir(&root).display()),
     }
-
-    Ok(dependencies)
+    Ok(())
 }
 
-fn extract_package_name(import_path: &str) -> Option<String> {
-    // If it's not a relative path, it might be a package
-    if !import_path.starts_with('.') && !import_path.starts_with('/') {
-        // Extract the package name (handle scoped packages like @scope/name)
-        let parts: Vec<&str> = import_path.split('/').collect();
-
-        if import_path.starts_with('@') && parts.len() >= 2 {
-            // Scoped package: @scope/name
-            Some(format!("{}/{}", parts[0], parts[1]))
-        } else if !import_path.starts_with('@') && parts.len() >= 1 {
-            // Regular package
-            Some(parts[0].to_string())
-        } else {
-            None
+/// Write to the `--output` file when given, stdout otherwise
+fn write_output(
+    output: Option<&Path>,
+    write: impl FnOnce(&mut dyn Write) -> io::Result<()>,
+) -> Result<()> {
+    match output {
+        Some(path) => {
+            let mut out = BufWriter::new(File::create(path)?);
+            write(&mut out)?;
+            out.flush()?;
+        }
+        None => {
+            let mut out = io::stdout().lock();
+            write(&mut out)?;
         }
-    } else {
-        None
     }
+
+    Ok(())
+}
+
+/// Scan the current directory, parse every file, and build the analysis graphs
+///
+/// Unless `--no-cache` is given, files unchanged since the last run reuse their cached parse.
+fn build_workspace(scan: &ScanArgs, show_progress: bool) -> Result<Workspace> {
+    analyzer(scan, show_progress)?.build()
 }
 
+/// An analyzer of the current directory, configured by the config file and `scan`
+fn analyzer(scan: &ScanArgs, show_progress: bool) -> Result<Analyzer> {
+    // Load configuration
+    let mut config = Config::find_and_load()?;
+    if let Some(profile) = &scan.profile {
+        config = config.with_profile(profile)?;
+    }
+
+    if let Some(threads) = scan.threads.map(|threads| threads as usize).or(config.threads) {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| PurgeError::Config(format!("Could not start {} threads: {}", threads, e)))?;
+    }
+
+    Ok(Analyzer::new(config)
+        .with_paths(scan.paths.clone())
+        .with_entry(scan.entry.clone())
+        .with_include(scan.include.clone())
+        .with_exclude(scan.exclude.clone())
+        .with_follow_symlinks(scan.follow_symlinks)
+        .with_cache(!scan.no_cache)
+        .with_progress(show_progress))
+}
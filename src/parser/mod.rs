@@ -1,40 +1,160 @@
+use crate::cache::{self, ParseCache};
 use crate::error::{PurgeError, Result};
-use crate::graph::{ImportEdge, Symbol, SymbolReference};
+use crate::frameworks::{angular, astro, markup, svelte, vue};
+use crate::graph::{
+    ClassMember, ImportBinding, LocalBinding, PackageImport, ParsedExport, ParsedImport, ParsedReference, Suppression,
+    Visibility, EXPORT_ASSIGNMENT,
+};
+use crate::resolver;
+use crate::rules::RULE_CODES;
+use crate::source::{self, LineIndex};
 use oxc_ast::ast::*;
+use oxc_ast::AstKind;
 use oxc_allocator::Allocator;
-use oxc_parser::Parser;
-use oxc_span::GetSpan;
+use oxc_parser::{ParseOptions, Parser};
+use oxc_semantic::{NodeId, Semantic, SemanticBuilder, SymbolFlags, SymbolId};
+use oxc_span::{GetSpan, Span};
 use oxc_span::SourceType;
 use rayon::prelude::*;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+thread_local! {
+    /// AST arena of each worker thread, reset between files so its memory is
+    /// reused rather than allocated afresh for every file
+    static ALLOCATOR: RefCell<Allocator> = RefCell::new(Allocator::default());
+}
 
 pub struct AstAnalyzer;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedFile {
     pub path: PathBuf,
-    pub imports: Vec<ImportEdge>,
-    pub exports: Vec<Symbol>,
-    pub references: Vec<SymbolReference>,
+    pub imports: Vec<ParsedImport>,
+    pub package_imports: Vec<PackageImport>,
+    /// Imports by bare specifier, e.g. `@repo/core/utils`, left unresolved
+    /// until linked to a workspace package
+    pub bare_imports: Vec<ParsedImport>,
+    pub exports: Vec<ParsedExport>,
+    pub references: Vec<ParsedReference>,
+    /// Line table to turn the spans above into lines and columns
+    pub lines: LineIndex,
+    /// Element and attribute names used in inline Angular templates
+    pub template_names: Vec<String>,
+    /// Angular template files referenced through `templateUrl`
+    pub template_files: Vec<PathBuf>,
+    /// Modules declared with `declare module 'name'`, e.g. `express` or `*.svg`
+    pub ambient_modules: Vec<String>,
+    /// Declares globals, in a `declare global` block or as a declaration
+    /// file without imports or exports
+    pub declares_globals: bool,
+    /// `#name` and `private` class members their class never reads
+    pub unread_members: Vec<ClassMember>,
+    /// Local variables, functions, and classes nothing reads
+    pub unread_locals: Vec<LocalBinding>,
+    /// Import bindings nothing in the file references
+    pub unused_imports: Vec<ImportBinding>,
+    /// `sweepr-ignore` comments silencing findings in the file
+    pub suppressions: Vec<Suppression>,
+}
+
+/// A file that could not be read or parsed, left out of the graphs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParseFailure {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+impl ParseFailure {
+    pub fn new(path: PathBuf, error: PurgeError) -> Self {
+        let message = match error {
+            PurgeError::ParseError { message, .. } => message,
+            error => error.to_string(),
+        };
+        Self { path, message }
+    }
 }
 
 impl AstAnalyzer {
     /// Parse all files in parallel
-    pub fn parse_files_parallel(files: Vec<PathBuf>) -> Result<Vec<ParsedFile>> {
-        let results: Vec<Result<ParsedFile>> = files
+    ///
+    /// A file that fails to parse doesn't stop the others; it is returned
+    /// among the failures instead.
+    pub fn parse_files_parallel(files: Vec<PathBuf>) -> (Vec<ParsedFile>, Vec<ParseFailure>) {
+        let results: Vec<std::result::Result<ParsedFile, ParseFailure>> = files
             .into_par_iter()
-            .map(|file| Self::parse_file(file))
+            .map(|path| Self::parse_file(path.clone()).map_err(|e| ParseFailure::new(path, e)))
+            .collect();
+
+        let mut parsed_files = Vec::with_capacity(results.len());
+        let mut failures = Vec::new();
+        for result in results {
+            match result {
+                Ok(parsed) => parsed_files.push(parsed),
+                Err(failure) => failures.push(failure),
+            }
+        }
+        (parsed_files, failures)
+    }
+
+    /// Parse all files in parallel, reusing cached results for unchanged files
+    ///
+    /// Returns the parsed files, how many of them came from the cache, and
+    /// the files that failed to parse.
+    pub fn parse_files_cached(
+        files: Vec<PathBuf>,
+        cache: &mut ParseCache,
+    ) -> (Vec<ParsedFile>, usize, Vec<ParseFailure>) {
+        let results: Vec<std::result::Result<(String, ParsedFile, bool), ParseFailure>> = files
+            .into_par_iter()
+            .map(|path| {
+                source::with_source(&path, |source| {
+                    let hash = cache::content_hash(source.as_bytes());
+
+                    match cache.get(&path, &hash) {
+                        Some(parsed) => Ok((hash, parsed.clone(), true)),
+                        None => Ok((hash, Self::parse_file_source(path.clone(), source)?, false)),
+                    }
+                })
+                .map_err(PurgeError::Io)
+                .and_then(|result| result)
+                .map_err(|e| ParseFailure::new(path, e))
+            })
             .collect();
 
-        results.into_iter().collect()
+        let mut parsed_files = Vec::with_capacity(results.len());
+        let mut cached = 0;
+        let mut failures = Vec::new();
+        for result in results {
+            let (hash, parsed, hit) = match result {
+                Ok(result) => result,
+                Err(failure) => {
+                    failures.push(failure);
+                    continue;
+                }
+            };
+            if hit {
+                cached += 1;
+            } else {
+                cache.insert(hash, parsed.clone());
+            }
+            parsed_files.push(parsed);
+        }
+
+        (parsed_files, cached, failures)
     }
 
     /// Parse a single file
     pub fn parse_file(path: PathBuf) -> Result<ParsedFile> {
-        let source = std::fs::read_to_string(&path)
-            .map_err(|e| PurgeError::Io(e))?;
+        source::with_source(&path, |source| Self::parse_file_source(path.clone(), source))
+            .map_err(PurgeError::Io)?
+    }
 
-        let parser_result = Self::parse_source(&source, &path);
+    /// Parse a file from source held in memory, e.g. an unsaved editor buffer
+    pub fn parse_file_source(path: PathBuf, source: &str) -> Result<ParsedFile> {
+        let parser_result = Self::parse_source(source, &path);
 
         match parser_result {
             Ok(parsed) => Ok(parsed),
@@ -45,43 +165,774 @@ impl AstAnalyzer {
         }
     }
 
-    fn parse_source(source: &str, path: &PathBuf) -> std::result::Result<ParsedFile, String> {
+    fn parse_source(source: &str, path: &Path) -> std::result::Result<ParsedFile, String> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("astro") => {
+                let frontmatter: Vec<_> = astro::frontmatter_range(source).into_iter().collect();
+                return Self::parse_component(source, path, &frontmatter);
+            }
+            Some("vue") => return Self::parse_component(source, path, &vue::scripts(source)),
+            Some("svelte") => {
+                let scripts = svelte::scripts(source);
+                let ranges: Vec<_> = scripts.iter().map(|script| script.range).collect();
+                let mut parsed = Self::parse_component(source, path, &ranges)?;
+                // Props aren't exports other files can import
+                let props = |offset| {
+                    scripts
+                        .iter()
+                        .any(|script| !script.is_module && (script.range.0..script.range.1).contains(&offset))
+                };
+                parsed.exports.retain(|export| !props(export.span.0));
+                return Ok(parsed);
+            }
+            _ => {}
+        }
+
         // Parse the source code
-        let source_type = SourceType::from_path(path).unwrap();
-        let allocator = Allocator::default();
-        let parser = Parser::new(&allocator, source, source_type);
+        let source_type = SourceType::from_path(path).map_err(|e| e.to_string())?;
+
+        // Nothing allocated in the arena outlives this parse
+        ALLOCATOR.with(|allocator| {
+            let mut allocator = allocator.borrow_mut();
+            allocator.reset();
+            Self::parse_program(&allocator, source, source_type, path)
+        })
+    }
+
+    /// Parse the scripts of an Astro, Svelte, or Vue component, in the byte ranges
+    /// `scripts`, as TypeScript, and record the components its template
+    /// renders as references
+    fn parse_component(
+        source: &str,
+        path: &Path,
+        scripts: &[(usize, usize)],
+    ) -> std::result::Result<ParsedFile, String> {
+        let script = markup::blank_outside(source, scripts);
+        let mut parsed = ALLOCATOR.with(|allocator| {
+            let mut allocator = allocator.borrow_mut();
+            allocator.reset();
+            Self::parse_program(&allocator, &script, SourceType::ts(), path)
+        })?;
+
+        parsed.lines = LineIndex::new(source);
+        for (name, offset) in markup::component_tags(source, scripts) {
+            parsed.references.push(ParsedReference {
+                span: (offset, offset + name.len()),
+                symbol: name,
+                file: path.to_path_buf(),
+            });
+        }
+        Ok(parsed)
+    }
+
+    fn parse_program(
+        allocator: &Allocator,
+        source: &str,
+        source_type: SourceType,
+        path: &Path,
+    ) -> std::result::Result<ParsedFile, String> {
+        // Astro frontmatter may `return` early, e.g. with `Astro.redirect()`
+        let options = ParseOptions {
+            allow_return_outside_function: path.extension().is_some_and(|ext| ext == "astro"),
+            ..ParseOptions::default()
+        };
+        let parser = Parser::new(allocator, source, source_type).with_options(options);
         let result = parser.parse();
 
-        if !result.errors.is_empty() {
-            return Err(format!("Parse error: {:?}", result.errors[0]));
+        if let Some(error) = result.errors.first() {
+            let offset = error.labels.iter().flatten().next().map_or(0, |label| label.offset());
+            let (line, column) = LineIndex::new(source).line_col(offset);
+            return Err(format!("{} at {}:{}", error.message, line, column));
         }
 
         let program = result.program;
 
         let mut parsed = ParsedFile {
-            path: path.clone(),
+            path: path.to_path_buf(),
             imports: Vec::new(),
+            package_imports: Vec::new(),
+            bare_imports: Vec::new(),
             exports: Vec::new(),
             references: Vec::new(),
+            lines: LineIndex::new(source),
+            template_names: Vec::new(),
+            template_files: Vec::new(),
+            ambient_modules: Vec::new(),
+            declares_globals: false,
+            unread_members: Vec::new(),
+            unread_locals: Vec::new(),
+            unused_imports: Vec::new(),
+            suppressions: Vec::new(),
         };
 
         // Walk the AST
         Self::visit_module(&program, path, &mut parsed);
+        Self::apply_scopes(&program, path, &mut parsed);
+        Self::collect_ambient_declarations(&program, path, &mut parsed);
+        Self::collect_suppressions(&program, &mut parsed);
 
         Ok(parsed)
     }
 
-    fn visit_module(program: &Program, path: &PathBuf, parsed: &mut ParsedFile) {
+    /// Record `sweepr-ignore-next-line` and `sweepr-ignore-file` comments
+    ///
+    /// The rule codes follow the directive, separated by spaces or commas;
+    /// anything after them is an explanation. Without codes, every rule is
+    /// silenced.
+    fn collect_suppressions(program: &Program, parsed: &mut ParsedFile) {
+        for comment in &program.comments {
+            let text = comment
+                .span
+                .source_text(program.source_text)
+                .trim_start_matches(['/', '*']);
+            let mut words = text
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|word| !word.is_empty());
+            let line = match words.next() {
+                Some("sweepr-ignore-next-line") => Some(parsed.lines.line_col(comment.span.end as usize).0 + 1),
+                Some("sweepr-ignore-file") => None,
+                _ => continue,
+            };
+            let codes = words
+                .take_while(|word| RULE_CODES.contains(word))
+                .map(str::to_string)
+                .collect();
+            parsed.suppressions.push(Suppression { line, codes });
+        }
+    }
+
+    /// Record the modules a file declares with `declare module 'name'`, and
+    /// whether it declares globals
+    ///
+    /// A declaration file without imports or exports is a global script,
+    /// unless it only declares modules; one with no statements at all may
+    /// still pull in global types through `/// <reference>` comments.
+    fn collect_ambient_declarations(program: &Program, path: &Path, parsed: &mut ParsedFile) {
+        let mut is_module = false;
+        let mut only_modules = true;
+        for stmt in &program.body {
+            match stmt {
+                Statement::TSModuleDeclaration(decl) => match &decl.id {
+                    TSModuleDeclarationName::StringLiteral(name) => {
+                        parsed.ambient_modules.push(name.value.to_string());
+                    }
+                    TSModuleDeclarationName::Identifier(_) => {
+                        only_modules = false;
+                        if decl.kind == TSModuleDeclarationKind::Global {
+                            parsed.declares_globals = true;
+                        }
+                    }
+                },
+                Statement::TSImportEqualsDeclaration(decl)
+                    if matches!(decl.module_reference, TSModuleReference::ExternalModuleReference(_)) =>
+                {
+                    is_module = true;
+                }
+                _ if stmt.is_module_declaration() => is_module = true,
+                _ => only_modules = false,
+            }
+        }
+
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let is_declaration_file = [".d.ts", ".d.mts", ".d.cts"].iter().any(|ext| name.ends_with(ext));
+        if is_declaration_file && !is_module && (program.body.is_empty() || !only_modules) {
+            parsed.declares_globals = true;
+        }
+    }
+
+    /// Use the file's scopes to mark exports the file itself uses, and to
+    /// narrow imports to what the file does with their bindings
+    fn apply_scopes(program: &Program, path: &Path, parsed: &mut ParsedFile) {
+        // Component templates, blanked out here, may use imports too
+        let is_component = path
+            .extension()
+            .is_some_and(|ext| ext == "astro" || ext == "svelte" || ext == "vue");
+        let (namespaces, defaults, imports) = if is_component {
+            (Vec::new(), Vec::new(), Vec::new())
+        } else {
+            (
+                Self::import_bindings(program, |specifier| {
+                    matches!(specifier, ImportDeclarationSpecifier::ImportNamespaceSpecifier(_))
+                })
+                .into_iter()
+                .map(|(import_decl, specifier)| (&import_decl.source, specifier.local()))
+                .chain(Self::require_bindings(program))
+                .collect::<Vec<_>>(),
+                Self::import_bindings(program, |specifier| match specifier {
+                    ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => true,
+                    ImportDeclarationSpecifier::ImportSpecifier(spec) => spec.imported.name() == "default",
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => false,
+                }),
+                Self::import_bindings(program, |_| true),
+            )
+        };
+        if program.body.is_empty() {
+            return;
+        }
+
+        let semantic = SemanticBuilder::new().build(program).semantic;
+        Self::mark_exports_used_in_module(program, &semantic, parsed);
+        Self::narrow_namespace_imports(&semantic, namespaces, parsed);
+        Self::drop_unreferenced_default_imports(&semantic, defaults, parsed);
+        Self::find_unused_imports(&semantic, &imports, path, parsed);
+        Self::forward_re_exported_imports(&semantic, imports, parsed);
+        // Only a file mentioning them can declare private members
+        let source = program.source_text;
+        if source.contains("private") || source.contains('#') {
+            Self::find_unread_members(&semantic, path, parsed);
+        }
+        let is_declaration_file = path.to_string_lossy().ends_with(".d.ts");
+        if !is_component && !is_declaration_file {
+            Self::find_unread_locals(&semantic, path, parsed);
+        }
+    }
+
+    /// Find import bindings that nothing in the file references
+    ///
+    /// Re-exporting an import or using it only as a type counts as a use.
+    /// `React` in a JSX file is left alone, since the classic JSX transform
+    /// uses it without a reference.
+    fn find_unused_imports(
+        semantic: &Semantic,
+        imports: &[(&ImportDeclaration, &ImportDeclarationSpecifier)],
+        path: &Path,
+        parsed: &mut ParsedFile,
+    ) {
+        let is_jsx = path.extension().is_some_and(|ext| ext == "jsx" || ext == "tsx");
+        for (import_decl, specifier) in imports {
+            let local = specifier.local();
+            if is_jsx && local.name == "React" {
+                continue;
+            }
+            let is_referenced = local
+                .symbol_id
+                .get()
+                .is_none_or(|symbol_id| !semantic.symbols().get_resolved_reference_ids(symbol_id).is_empty());
+            if is_referenced {
+                continue;
+            }
+
+            let is_only = import_decl.specifiers.as_ref().is_some_and(|specifiers| specifiers.len() == 1);
+            let span = specifier.span();
+            parsed.unused_imports.push(ImportBinding {
+                name: local.name.to_string(),
+                source: import_decl.source.value.to_string(),
+                file: path.to_path_buf(),
+                span: (span.start as usize, span.end as usize),
+                statement: is_only.then_some((import_decl.span.start as usize, import_decl.span.end as usize)),
+            });
+        }
+    }
+
+    /// Find variables, functions, and classes declared in the file that
+    /// nothing reads, at the top level or within functions
+    ///
+    /// Exported declarations, parameters, imports, names starting with `_`,
+    /// and destructured names next to a `...rest`, which leave them out of
+    /// it, are never reported. Assigning a variable doesn't read it.
+    fn find_unread_locals(semantic: &Semantic, path: &Path, parsed: &mut ParsedFile) {
+        let (symbols, nodes) = (semantic.symbols(), semantic.nodes());
+
+        for symbol_id in symbols.symbol_ids() {
+            let name = symbols.get_name(symbol_id);
+            if name.starts_with('_') || symbols.get_flags(symbol_id).contains(SymbolFlags::Ambient) {
+                continue;
+            }
+
+            let declaration = symbols.get_declaration(symbol_id);
+            let span = symbols.get_span(symbol_id);
+            let is_local = match nodes.kind(declaration) {
+                AstKind::VariableDeclarator(declarator) => !Self::has_rest_sibling(&declarator.id, span),
+                AstKind::Function(function) => function.is_declaration() && !function.declare,
+                AstKind::Class(class) => class.is_declaration() && !class.declare,
+                _ => false,
+            };
+            let is_exported = nodes.ancestors(declaration).skip(1).take(2).any(|ancestor| {
+                matches!(
+                    nodes.kind(ancestor),
+                    AstKind::ExportNamedDeclaration(_) | AstKind::ExportDefaultDeclaration(_)
+                )
+            });
+            if !is_local || is_exported {
+                continue;
+            }
+
+            let is_read = symbols
+                .get_resolved_references(symbol_id)
+                .any(|reference| reference.is_read() || reference.is_type());
+            if !is_read {
+                parsed.unread_locals.push(LocalBinding {
+                    name: name.to_string(),
+                    file: path.to_path_buf(),
+                    span: (span.start as usize, span.end as usize),
+                });
+            }
+        }
+    }
+
+    /// Whether the binding at `span` is in an object pattern that also has
+    /// a `...rest` element, e.g. `a` in `const { a, ...rest } = value`
+    fn has_rest_sibling(pattern: &BindingPattern, span: Span) -> bool {
+        match &pattern.kind {
+            BindingPatternKind::ObjectPattern(object) => object.properties.iter().any(|property| {
+                property.span.contains_inclusive(span)
+                    && (object.rest.is_some() || Self::has_rest_sibling(&property.value, span))
+            }),
+            BindingPatternKind::ArrayPattern(array) => array
+                .elements
+                .iter()
+                .flatten()
+                .any(|element| Self::has_rest_sibling(element, span)),
+            BindingPatternKind::AssignmentPattern(assignment) => Self::has_rest_sibling(&assignment.left, span),
+            BindingPatternKind::BindingIdentifier(_) => false,
+        }
+    }
+
+    /// Find `#name` and `private` fields and methods that their class never
+    /// reads, from anywhere within its body
+    ///
+    /// Only reading a field uses it; assigning it doesn't, though assigning
+    /// a method, e.g. a setter, does. Decorated members, which frameworks
+    /// may read, and members of `declare` classes are left out.
+    fn find_unread_members(semantic: &Semantic, path: &Path, parsed: &mut ParsedFile) {
+        let nodes = semantic.nodes();
+        // Classes around a node, innermost first
+        let classes = |node_id| {
+            nodes
+                .ancestors(node_id)
+                .filter(move |&ancestor| matches!(nodes.kind(ancestor), AstKind::Class(_)))
+        };
+
+        // (class, name, span, is_method) of each private member
+        let mut members = Vec::new();
+        // (class, name) of every member read, and of every one assigned
+        let mut reads: HashSet<(NodeId, String)> = HashSet::new();
+        let mut writes: HashSet<(NodeId, String)> = HashSet::new();
+
+        for node in nodes.iter() {
+            let (key, accessibility, decorators, is_method) = match node.kind() {
+                AstKind::PropertyDefinition(prop) if !prop.declare => {
+                    (&prop.key, prop.accessibility, &prop.decorators, false)
+                }
+                AstKind::MethodDefinition(method) if method.kind != MethodDefinitionKind::Constructor => {
+                    (&method.key, method.accessibility, &method.decorators, true)
+                }
+                AstKind::MemberExpression(member) => {
+                    let name = match member {
+                        MemberExpression::StaticMemberExpression(expr) => expr.property.name.to_string(),
+                        MemberExpression::PrivateFieldExpression(expr) => format!("#{}", expr.field.name),
+                        MemberExpression::ComputedMemberExpression(expr) => match &expr.expression {
+                            Expression::StringLiteral(literal) => literal.value.to_string(),
+                            _ => continue,
+                        },
+                    };
+                    // `this.x = value` only assigns; `this.x += 1` reads too
+                    let mut ancestors = nodes.ancestors(node.id()).skip(1).map(|id| nodes.kind(id));
+                    let is_write = matches!(ancestors.next(), Some(AstKind::SimpleAssignmentTarget(_)))
+                        && matches!(ancestors.next(), Some(AstKind::AssignmentTarget(_)))
+                        && matches!(
+                            ancestors.next(),
+                            Some(AstKind::AssignmentExpression(assignment))
+                                if assignment.operator == AssignmentOperator::Assign
+                        );
+                    let accessed = if is_write { &mut writes } else { &mut reads };
+                    accessed.extend(classes(node.id()).map(|class| (class, name.clone())));
+                    continue;
+                }
+                AstKind::PrivateInExpression(expr) => {
+                    // `#name in object` reads whether the object has it
+                    let name = format!("#{}", expr.left.name);
+                    reads.extend(classes(node.id()).map(|class| (class, name.clone())));
+                    continue;
+                }
+                AstKind::ObjectPattern(pattern) => {
+                    // `const { name } = this`
+                    for name in pattern.properties.iter().filter_map(|property| property.key.static_name()) {
+                        reads.extend(classes(node.id()).map(|class| (class, name.to_string())));
+                    }
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let name = match key {
+                PropertyKey::PrivateIdentifier(ident) => format!("#{}", ident.name),
+                _ if accessibility == Some(TSAccessibility::Private) => match key.static_name() {
+                    Some(name) => name.to_string(),
+                    None => continue,
+                },
+                _ => continue,
+            };
+            let Some(class) = classes(node.id()).next() else {
+                continue;
+            };
+            let AstKind::Class(class_decl) = nodes.kind(class) else {
+                continue;
+            };
+            if !decorators.is_empty() || class_decl.declare {
+                continue;
+            }
+            let span = key.span();
+            members.push((class, class_decl.id.as_ref().map(|id| id.name.to_string()), name, span, is_method));
+        }
+
+        let mut seen = HashSet::new();
+        for (class, class_name, name, span, is_method) in members {
+            let key = (class, name);
+            let is_used = reads.contains(&key) || (is_method && writes.contains(&key));
+            // Overloads declare a method more than once
+            if is_used || !seen.insert(key.clone()) {
+                continue;
+            }
+            parsed.unread_members.push(ClassMember {
+                name: key.1,
+                class: class_name,
+                file: path.to_path_buf(),
+                span: (span.start as usize, span.end as usize),
+            });
+        }
+    }
+
+    /// Mark exports that their own file references apart from exporting
+    /// them, and record the declaration each one names
+    fn mark_exports_used_in_module(program: &Program, semantic: &Semantic, parsed: &mut ParsedFile) {
+        // Local names of exports that are renamed or default
+        let mut locals: HashMap<String, String> = HashMap::new();
+        for stmt in &program.body {
+            match stmt {
+                Statement::ExportNamedDeclaration(export_decl) if export_decl.source.is_none() => {
+                    for specifier in &export_decl.specifiers {
+                        locals.insert(specifier.exported.name().to_string(), specifier.local.name().to_string());
+                    }
+                }
+                Statement::ExportDefaultDeclaration(export_decl) => {
+                    let local = match &export_decl.declaration {
+                        ExportDefaultDeclarationKind::FunctionDeclaration(func) => func.id.as_ref().map(|id| &id.name),
+                        ExportDefaultDeclarationKind::ClassDeclaration(class) => class.id.as_ref().map(|id| &id.name),
+                        ExportDefaultDeclarationKind::Identifier(ident) => Some(&ident.name),
+                        _ => None,
+                    };
+                    if let Some(local) = local {
+                        locals.insert("default".to_string(), local.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let (scopes, symbols, nodes) = (semantic.scopes(), semantic.symbols(), semantic.nodes());
+        for export in &mut parsed.exports {
+            let local = locals.get(&export.name).unwrap_or(&export.name);
+            let Some(symbol_id) = scopes.get_binding(scopes.root_scope_id(), local) else {
+                continue;
+            };
+            export.used_in_module = symbols.get_resolved_reference_ids(symbol_id).iter().any(|&reference_id| {
+                let node_id = symbols.get_reference(reference_id).node_id();
+                !matches!(
+                    nodes.parent_kind(node_id),
+                    Some(AstKind::ExportSpecifier(_) | AstKind::ExportDefaultDeclaration(_))
+                )
+            });
+            export.declaration = Self::declaration_extent(semantic, symbols.get_declaration(symbol_id))
+                .map(|span| (span.start as usize, span.end as usize));
+        }
+    }
+
+    /// The statement declaring a top-level binding, with `export` if it has
+    /// one, or just the declarator when a statement declares several
+    /// variables; imported bindings declare nothing
+    fn declaration_extent(semantic: &Semantic, declaration: NodeId) -> Option<Span> {
+        let nodes = semantic.nodes();
+        match nodes.kind(declaration) {
+            AstKind::VariableDeclarator(declarator) => {
+                if let Some(AstKind::VariableDeclaration(decl)) = nodes.parent_kind(declaration) {
+                    if decl.declarations.len() > 1 {
+                        return Some(declarator.span);
+                    }
+                }
+            }
+            AstKind::Function(_)
+            | AstKind::Class(_)
+            | AstKind::TSInterfaceDeclaration(_)
+            | AstKind::TSTypeAliasDeclaration(_)
+            | AstKind::TSEnumDeclaration(_)
+            | AstKind::TSModuleDeclaration(_) => {}
+            _ => return None,
+        }
+        nodes
+            .ancestors(declaration)
+            .find(|&ancestor| matches!(nodes.parent_kind(ancestor), Some(AstKind::Program(_))))
+            .map(|statement| nodes.kind(statement).span())
+    }
+
+    /// Import declarations of the file, each with a specifier `select` accepts
+    fn import_bindings<'a>(
+        program: &'a Program,
+        select: impl Fn(&ImportDeclarationSpecifier) -> bool,
+    ) -> Vec<(&'a ImportDeclaration<'a>, &'a ImportDeclarationSpecifier<'a>)> {
+        program
+            .body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::ImportDeclaration(import_decl) => Some(&**import_decl),
+                _ => None,
+            })
+            .flat_map(|import_decl| {
+                import_decl
+                    .specifiers
+                    .iter()
+                    .flatten()
+                    .filter(|specifier| select(specifier))
+                    .map(move |specifier| (import_decl, specifier))
+            })
+            .collect()
+    }
+
+    /// Sources and bindings of `import x = require('./x')` declarations
+    fn require_bindings<'a>(program: &'a Program) -> Vec<(&'a StringLiteral<'a>, &'a BindingIdentifier<'a>)> {
+        program
+            .body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::TSImportEqualsDeclaration(decl) => Some(&**decl),
+                _ => None,
+            })
+            .filter_map(|decl| match &decl.module_reference {
+                TSModuleReference::ExternalModuleReference(reference) => Some((&reference.expression, &decl.id)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The import edges an import of `source` was recorded as
+    fn edges_of<'p>(
+        source: &StringLiteral,
+        parsed: &'p mut ParsedFile,
+    ) -> impl Iterator<Item = &'p mut ParsedImport> {
+        let span = (source.span.start as usize, source.span.end as usize);
+        parsed
+            .imports
+            .iter_mut()
+            .chain(parsed.bare_imports.iter_mut())
+            .filter(move |edge| edge.span == span)
+    }
+
+    /// Stop a default import using the default export when the importing
+    /// file never references the binding, whatever name it gave it
+    fn drop_unreferenced_default_imports(
+        semantic: &Semantic,
+        defaults: Vec<(&ImportDeclaration, &ImportDeclarationSpecifier)>,
+        parsed: &mut ParsedFile,
+    ) {
+        for (import_decl, specifier) in defaults {
+            let is_referenced = specifier
+                .local()
+                .symbol_id
+                .get()
+                .is_none_or(|symbol_id| !semantic.symbols().get_resolved_reference_ids(symbol_id).is_empty());
+            if !is_referenced {
+                for edge in Self::edges_of(&import_decl.source, parsed) {
+                    edge.imported_symbols.retain(|symbol| symbol != "default");
+                }
+            }
+        }
+    }
+
+    /// Turn imports the file only re-exports, e.g. `import { Button } from
+    /// './Button'` with `export { Button }`, into forwarding edges like
+    /// `export { Button } from './Button'`
+    ///
+    /// The original export is then used only when something uses the
+    /// re-export, rather than by the barrel importing it.
+    fn forward_re_exported_imports(
+        semantic: &Semantic,
+        imports: Vec<(&ImportDeclaration, &ImportDeclarationSpecifier)>,
+        parsed: &mut ParsedFile,
+    ) {
+        let (symbols, nodes) = (semantic.symbols(), semantic.nodes());
+        // (exported, imported) pairs, by the span of their import's source
+        let mut forwards: Vec<((usize, usize), String, String)> = Vec::new();
+
+        for (import_decl, specifier) in imports {
+            let imported = match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(spec) => spec.imported.name().to_string(),
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => "default".to_string(),
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => "*".to_string(),
+            };
+            let Some(symbol_id) = specifier.local().symbol_id.get() else {
+                continue;
+            };
+
+            let mut exported = Vec::new();
+            for &reference_id in symbols.get_resolved_reference_ids(symbol_id) {
+                let node_id = symbols.get_reference(reference_id).node_id();
+                match nodes.parent_kind(node_id) {
+                    Some(AstKind::ExportSpecifier(spec)) => exported.push(spec.exported.name().to_string()),
+                    Some(AstKind::ExportDefaultDeclaration(_)) => exported.push("default".to_string()),
+                    _ => {
+                        exported.clear();
+                        break;
+                    }
+                }
+            }
+            let span = (import_decl.source.span.start as usize, import_decl.source.span.end as usize);
+            forwards.extend(exported.into_iter().map(|name| (span, name, imported.clone())));
+        }
+
+        let mut spans: Vec<(usize, usize)> = forwards.iter().map(|(span, _, _)| *span).collect();
+        spans.dedup();
+        for span in spans {
+            let pairs: Vec<(String, String)> = forwards
+                .iter()
+                .filter(|(forward_span, _, _)| *forward_span == span)
+                .map(|(_, exported, imported)| (exported.clone(), imported.clone()))
+                .collect();
+            let mut names: Vec<String> = pairs.iter().map(|(_, imported)| imported.clone()).collect();
+            names.dedup();
+
+            // Unresolved relative imports forward nothing, so aren't split
+            for (edges, split_unresolved) in [(&mut parsed.imports, false), (&mut parsed.bare_imports, true)] {
+                let mut split = Vec::new();
+                for edge in edges.iter_mut().filter(|edge| edge.span == span) {
+                    edge.imported_symbols.retain(|symbol| !names.contains(symbol));
+                    let forwarding = ParsedImport {
+                        imported_symbols: names.clone(),
+                        forwarded: pairs.clone(),
+                        ..edge.clone()
+                    };
+                    if edge.imported_symbols.is_empty() {
+                        *edge = forwarding;
+                    } else if edge.is_resolved || split_unresolved {
+                        split.push(forwarding);
+                    }
+                }
+                edges.extend(split);
+            }
+        }
+    }
+
+    /// Narrow namespace imports such as `import * as utils from './utils'`
+    /// to the members the file accesses, e.g. `formatDate` for
+    /// `utils.formatDate()`
+    ///
+    /// `import utils = require('./utils')` binds a namespace the same way. A
+    /// namespace used any other way, e.g. passed to a function or indexed
+    /// with a computed key, keeps using every export.
+    fn narrow_namespace_imports(
+        semantic: &Semantic,
+        namespaces: Vec<(&StringLiteral, &BindingIdentifier)>,
+        parsed: &mut ParsedFile,
+    ) {
+        for (source, local) in namespaces {
+            let Some(members) = local
+                .symbol_id
+                .get()
+                .and_then(|symbol_id| Self::namespace_members(semantic, symbol_id))
+            else {
+                continue;
+            };
+
+            for edge in Self::edges_of(source, parsed) {
+                edge.imported_symbols.retain(|symbol| symbol != "*");
+                edge.imported_symbols.extend(members.iter().cloned());
+            }
+        }
+    }
+
+    /// Names accessed on a namespace binding, as `ns.name`, `ns.Type`, or
+    /// `<ns.Component />`; `None` when the binding is used any other way
+    fn namespace_members(semantic: &Semantic, symbol_id: SymbolId) -> Option<Vec<String>> {
+        let nodes = semantic.nodes();
+        let mut members = Vec::new();
+
+        for &reference_id in semantic.symbols().get_resolved_reference_ids(symbol_id) {
+            let node_id = semantic.symbols().get_reference(reference_id).node_id();
+            let span = nodes.kind(node_id).span();
+            let parent = nodes.parent_node(node_id)?;
+            // Type names and JSX member objects wrap the identifier once more
+            let grandparent = || nodes.parent_kind(parent.id());
+            let member = match parent.kind() {
+                AstKind::MemberExpression(member) if member.object().span() == span => {
+                    member.static_property_name()?.to_string()
+                }
+                AstKind::TSTypeName(_) => match grandparent()? {
+                    AstKind::TSQualifiedName(name) if name.left.span() == span => name.right.name.to_string(),
+                    _ => return None,
+                },
+                AstKind::JSXMemberExpressionObject(_) => match grandparent()? {
+                    AstKind::JSXMemberExpression(member) => member.property.name.to_string(),
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            if !members.contains(&member) {
+                members.push(member);
+            }
+        }
+
+        Some(members)
+    }
+
+    fn visit_module(program: &Program, path: &Path, parsed: &mut ParsedFile) {
         // Program body is directly accessible
         Self::visit_module_body(&program.body, path, parsed);
+        Self::apply_jsdoc_visibility(program, parsed);
     }
 
-    fn visit_module_body(body: &[Statement], path: &PathBuf, parsed: &mut ParsedFile) {
+    /// Tag the exports of statements documented with `@public` or `@internal`
+    ///
+    /// `@internal` wins when a comment has both.
+    fn apply_jsdoc_visibility(program: &Program, parsed: &mut ParsedFile) {
+        for stmt in &program.body {
+            let span = match stmt {
+                Statement::ExportNamedDeclaration(export_decl) => export_decl.span,
+                Statement::ExportDefaultDeclaration(export_decl) => export_decl.span,
+                _ => continue,
+            };
+
+            // The JSDoc closest to the statement documents it
+            let Some(comment) = program
+                .comments
+                .iter()
+                .rev()
+                .find(|comment| comment.attached_to == span.start && comment.is_jsdoc(program.source_text))
+            else {
+                continue;
+            };
+            let tags: Vec<&str> = comment
+                .span
+                .source_text(program.source_text)
+                .split_whitespace()
+                .filter(|word| word.starts_with('@'))
+                .collect();
+            let visibility = if tags.contains(&"@internal") {
+                Visibility::Internal
+            } else if tags.contains(&"@public") {
+                Visibility::Public
+            } else {
+                continue;
+            };
+
+            let (start, end) = (span.start as usize, span.end as usize);
+            for export in parsed
+                .exports
+                .iter_mut()
+                .filter(|export| export.span.0 >= start && export.span.1 <= end)
+            {
+                export.visibility = Some(visibility);
+            }
+        }
+    }
+
+    fn visit_module_body(body: &[Statement], path: &Path, parsed: &mut ParsedFile) {
         for stmt in body {
             match stmt {
                 Statement::ImportDeclaration(import_decl) => {
                     Self::handle_import_declaration(import_decl, path, parsed);
                 }
+                Statement::TSImportEqualsDeclaration(import_decl) => {
+                    Self::handle_import_equals_declaration(import_decl, false, path, parsed);
+                }
                 Statement::ExportNamedDeclaration(export_decl) => {
                     Self::handle_export_named_declaration(export_decl, path, parsed);
                 }
@@ -89,8 +940,20 @@ impl AstAnalyzer {
                     Self::handle_export_default_declaration(export_decl, path, parsed);
                 }
                 Statement::ExportAllDeclaration(export_decl) => {
-                    // Barrel export - skip for now
-                    let _ = export_decl;
+                    Self::handle_export_all_declaration(export_decl, path, parsed);
+                }
+                Statement::TSExportAssignment(assignment) => {
+                    // `export = value` makes the value the whole module
+                    parsed.exports.push(ParsedExport {
+                        name: EXPORT_ASSIGNMENT.to_string(),
+                        file: path.to_path_buf(),
+                        span: (assignment.span.start as usize, assignment.span.end as usize),
+                        is_type: false,
+                        visibility: None,
+                        selector: None,
+                        used_in_module: false,
+                        declaration: None,
+                    });
                 }
                 Statement::ExpressionStatement(expr_stmt) => {
                     Self::extract_references(&expr_stmt.expression, path, parsed);
@@ -128,38 +991,22 @@ impl AstAnalyzer {
                     }
                     Self::visit_statement(&for_stmt.body, path, parsed);
                 }
-                Statement::FunctionDeclaration(func_decl) => {
-                    // Function declarations are hoisted
-                    if let Some(ident) = &func_decl.id {
-                        parsed.exports.push(Symbol {
-                            name: ident.name.to_string(),
-                            file: path.clone(),
-                            span: (ident.span.start as usize, ident.span.end as usize),
-                        });
-                    }
+                Statement::VariableDeclaration(var_decl) => {
+                    Self::handle_variable_declaration(var_decl, path, parsed, false);
                 }
                 Statement::ClassDeclaration(class_decl) => {
-                    if let Some(ident) = &class_decl.id {
-                        parsed.exports.push(Symbol {
-                            name: ident.name.to_string(),
-                            file: path.clone(),
-                            span: (ident.span.start as usize, ident.span.end as usize),
-                        });
-                    }
-                }
-                Statement::VariableDeclaration(var_decl) => {
-                    Self::handle_variable_declaration(var_decl, path, parsed, true);
+                    Self::handle_class(class_decl, path, parsed);
                 }
                 _ => {}
             }
         }
     }
 
-    fn visit_block(block: &BlockStatement, path: &PathBuf, parsed: &mut ParsedFile) {
+    fn visit_block(block: &BlockStatement, path: &Path, parsed: &mut ParsedFile) {
         Self::visit_module_body(&block.body, path, parsed);
     }
 
-    fn visit_statement(stmt: &Statement, path: &PathBuf, parsed: &mut ParsedFile) {
+    fn visit_statement(stmt: &Statement, path: &Path, parsed: &mut ParsedFile) {
         match stmt {
             Statement::BlockStatement(block) => Self::visit_block(block, path, parsed),
             Statement::IfStatement(if_stmt) => {
@@ -173,21 +1020,16 @@ impl AstAnalyzer {
         }
     }
 
-    fn visit_for_init(init: &VariableDeclaration<'_>, path: &PathBuf, parsed: &mut ParsedFile) {
+    fn visit_for_init(init: &VariableDeclaration<'_>, path: &Path, parsed: &mut ParsedFile) {
         // For now, just handle variable declarations in for loops
         Self::handle_variable_declaration(init, path, parsed, false);
     }
 
     fn handle_import_declaration(
         import_decl: &ImportDeclaration,
-        path: &PathBuf,
+        path: &Path,
         parsed: &mut ParsedFile,
     ) {
-        let source = import_decl.source.value.as_str();
-
-        // Check if it's a package import (starts with non-dot/slash)
-        let is_package_import = !source.starts_with('.') && !source.starts_with('/');
-
         let mut imported_symbols = Vec::new();
 
         // Iterate over specifiers - convert to slice first
@@ -208,97 +1050,273 @@ impl AstAnalyzer {
             }
         }
 
-        // Don't track package imports in the file graph for now
-        if !is_package_import {
-            parsed.imports.push(ImportEdge {
-                from: path.clone(),
-                to: path.parent().unwrap().join(source).to_path_buf(),
-                imported_symbols,
-                is_type_only: import_decl.import_kind.is_type(),
-            });
+        Self::push_import(
+            &import_decl.source,
+            imported_symbols,
+            Vec::new(),
+            import_decl.import_kind.is_type(),
+            path,
+            parsed,
+        );
+    }
+
+    /// `import utils = require('./utils')` binds the whole module, like a
+    /// namespace import, and `export import` forwards it like `export * as`;
+    /// `import Alias = Namespace.Member` imports nothing
+    fn handle_import_equals_declaration(
+        import_decl: &TSImportEqualsDeclaration,
+        is_export: bool,
+        path: &Path,
+        parsed: &mut ParsedFile,
+    ) {
+        if let TSModuleReference::ExternalModuleReference(reference) = &import_decl.module_reference {
+            let forwarded = if is_export {
+                vec![(import_decl.id.name.to_string(), "*".to_string())]
+            } else {
+                Vec::new()
+            };
+            Self::push_import(
+                &reference.expression,
+                vec!["*".to_string()],
+                forwarded,
+                import_decl.import_kind.is_type(),
+                path,
+                parsed,
+            );
+        }
+    }
+
+    /// Record an import of `source`, resolved to the file it refers to, or
+    /// the package it names
+    fn push_import(
+        source: &StringLiteral,
+        imported_symbols: Vec<String>,
+        forwarded: Vec<(String, String)>,
+        is_type_only: bool,
+        path: &Path,
+        parsed: &mut ParsedFile,
+    ) {
+        let span = (source.span.start as usize, source.span.end as usize);
+        let source = source.value.as_str();
+
+        if !resolver::is_relative(source) {
+            if let Some(name) = resolver::package_name(source) {
+                parsed.package_imports.push(PackageImport {
+                    name: name.to_string(),
+                    file: path.to_path_buf(),
+                    span,
+                    is_type_only,
+                });
+                parsed.bare_imports.push(ParsedImport {
+                    from: path.to_path_buf(),
+                    to: PathBuf::from(source),
+                    specifier: source.to_string(),
+                    span,
+                    is_resolved: false,
+                    imported_symbols,
+                    is_type_only,
+                    forwarded,
+                });
+            }
+            return;
         }
+
+        let resolved = resolver::resolve_relative(path, source);
+        parsed.imports.push(ParsedImport {
+            from: path.to_path_buf(),
+            is_resolved: resolved.is_some(),
+            to: resolved.unwrap_or_else(|| resolver::unresolved_target(path, source)),
+            specifier: source.to_string(),
+            span,
+            imported_symbols,
+            is_type_only,
+            forwarded,
+        });
     }
 
     fn handle_export_named_declaration(
         export_decl: &ExportNamedDeclaration,
-        path: &PathBuf,
+        path: &Path,
         parsed: &mut ParsedFile,
     ) {
         if let Some(declaration) = &export_decl.declaration {
             match declaration {
                 Declaration::FunctionDeclaration(func_decl) => {
                     if let Some(ident) = &func_decl.id {
-                        parsed.exports.push(Symbol {
+                        parsed.exports.push(ParsedExport {
                             name: ident.name.to_string(),
-                            file: path.clone(),
+                            file: path.to_path_buf(),
                             span: (ident.span.start as usize, ident.span.end as usize),
+                            is_type: false,
+                            visibility: None,
+                            selector: None,
+                            used_in_module: false,
+                            declaration: None,
                         });
                     }
                 }
                 Declaration::ClassDeclaration(class_decl) => {
+                    let selector = Self::handle_class(class_decl, path, parsed);
                     if let Some(ident) = &class_decl.id {
-                        parsed.exports.push(Symbol {
+                        parsed.exports.push(ParsedExport {
                             name: ident.name.to_string(),
-                            file: path.clone(),
+                            file: path.to_path_buf(),
                             span: (ident.span.start as usize, ident.span.end as usize),
+                            is_type: false,
+                            visibility: None,
+                            selector,
+                            used_in_module: false,
+                            declaration: None,
                         });
                     }
                 }
                 Declaration::VariableDeclaration(var_decl) => {
                     Self::handle_variable_declaration(var_decl, path, parsed, true);
                 }
+                Declaration::TSInterfaceDeclaration(interface_decl) => {
+                    let ident = &interface_decl.id;
+                    parsed.exports.push(ParsedExport {
+                        name: ident.name.to_string(),
+                        file: path.to_path_buf(),
+                        span: (ident.span.start as usize, ident.span.end as usize),
+                        is_type: true,
+                        visibility: None,
+                        selector: None,
+                        used_in_module: false,
+                        declaration: None,
+                    });
+                }
+                Declaration::TSTypeAliasDeclaration(alias_decl) => {
+                    let ident = &alias_decl.id;
+                    parsed.exports.push(ParsedExport {
+                        name: ident.name.to_string(),
+                        file: path.to_path_buf(),
+                        span: (ident.span.start as usize, ident.span.end as usize),
+                        is_type: true,
+                        visibility: None,
+                        selector: None,
+                        used_in_module: false,
+                        declaration: None,
+                    });
+                }
+                Declaration::TSImportEqualsDeclaration(import_decl) => {
+                    Self::handle_import_equals_declaration(import_decl, true, path, parsed);
+                    let ident = &import_decl.id;
+                    parsed.exports.push(ParsedExport {
+                        name: ident.name.to_string(),
+                        file: path.to_path_buf(),
+                        span: (ident.span.start as usize, ident.span.end as usize),
+                        is_type: import_decl.import_kind.is_type(),
+                        visibility: None,
+                        selector: None,
+                        used_in_module: false,
+                        declaration: None,
+                    });
+                }
                 _ => {}
             }
         }
 
-        // Handle explicit export specifiers (e.g., export { foo, bar })
+        // Handle explicit export specifiers (e.g., export { foo, type Bar })
         for specifier in &export_decl.specifiers {
-            parsed.exports.push(Symbol {
+            parsed.exports.push(ParsedExport {
                 name: specifier.exported.name().to_string(),
-                file: path.clone(),
+                file: path.to_path_buf(),
                 span: (specifier.span.start as usize, specifier.span.end as usize),
+                is_type: export_decl.export_kind.is_type() || specifier.export_kind.is_type(),
+                visibility: None,
+                selector: None,
+                used_in_module: false,
+                declaration: None,
             });
         }
+
+        // `export { foo } from './foo'` forwards names out of another file
+        if let Some(source) = &export_decl.source {
+            let forwarded: Vec<(String, String)> = export_decl
+                .specifiers
+                .iter()
+                .map(|specifier| {
+                    (
+                        specifier.exported.name().to_string(),
+                        specifier.local.name().to_string(),
+                    )
+                })
+                .collect();
+            Self::push_import(
+                source,
+                forwarded.iter().map(|(_, imported)| imported.clone()).collect(),
+                forwarded,
+                export_decl.export_kind.is_type(),
+                path,
+                parsed,
+            );
+        }
+    }
+
+    /// A barrel `export *` forwards, and so uses, everything it can reach;
+    /// `export * as ns` exports a single name standing for all of it
+    fn handle_export_all_declaration(
+        export_decl: &ExportAllDeclaration,
+        path: &Path,
+        parsed: &mut ParsedFile,
+    ) {
+        let exported = match &export_decl.exported {
+            Some(name) => {
+                parsed.exports.push(ParsedExport {
+                    name: name.name().to_string(),
+                    file: path.to_path_buf(),
+                    span: (name.span().start as usize, name.span().end as usize),
+                    is_type: export_decl.export_kind.is_type(),
+                    visibility: None,
+                    selector: None,
+                    used_in_module: false,
+                    declaration: None,
+                });
+                name.name().to_string()
+            }
+            None => "*".to_string(),
+        };
+
+        Self::push_import(
+            &export_decl.source,
+            vec!["*".to_string()],
+            vec![(exported, "*".to_string())],
+            export_decl.export_kind.is_type(),
+            path,
+            parsed,
+        );
     }
 
     fn handle_export_default_declaration(
         export_decl: &ExportDefaultDeclaration,
-        path: &PathBuf,
+        path: &Path,
         parsed: &mut ParsedFile,
     ) {
-        match &export_decl.declaration {
-            ExportDefaultDeclarationKind::FunctionDeclaration(func_decl) => {
-                if let Some(ident) = &func_decl.id {
-                    parsed.exports.push(Symbol {
-                        name: ident.name.to_string(),
-                        file: path.clone(),
-                        span: (ident.span.start as usize, ident.span.end as usize),
-                    });
-                }
-            }
+        let selector = match &export_decl.declaration {
             ExportDefaultDeclarationKind::ClassDeclaration(class_decl) => {
-                if let Some(ident) = &class_decl.id {
-                    parsed.exports.push(Symbol {
-                        name: ident.name.to_string(),
-                        file: path.clone(),
-                        span: (ident.span.start as usize, ident.span.end as usize),
-                    });
-                }
+                Self::handle_class(class_decl, path, parsed)
             }
-            _ => {}
-        }
+            _ => None,
+        };
 
-        // Default export is always named "default"
-        parsed.exports.push(Symbol {
+        // Default export is always named "default"; the name of a default
+        // function or class is only bound within the file
+        parsed.exports.push(ParsedExport {
             name: "default".to_string(),
-            file: path.clone(),
+            file: path.to_path_buf(),
             span: (export_decl.span.start as usize, export_decl.span.end as usize),
+            is_type: false,
+            visibility: None,
+            selector,
+            used_in_module: false,
+            declaration: None,
         });
     }
 
     fn handle_variable_declaration(
         var_decl: &VariableDeclaration,
-        path: &PathBuf,
+        path: &Path,
         parsed: &mut ParsedFile,
         is_exported: bool,
     ) {
@@ -309,10 +1327,15 @@ impl AstAnalyzer {
             };
 
             if is_exported {
-                parsed.exports.push(Symbol {
+                parsed.exports.push(ParsedExport {
                     name: ident.name.to_string(),
-                    file: path.clone(),
+                    file: path.to_path_buf(),
                     span: (ident.span.start as usize, ident.span.end as usize),
+                    is_type: false,
+                    visibility: None,
+                    selector: None,
+                    used_in_module: false,
+                    declaration: None,
                 });
             } else {
                 // It's a declaration, not a reference
@@ -325,12 +1348,12 @@ impl AstAnalyzer {
         }
     }
 
-    fn extract_references(expr: &Expression, path: &PathBuf, parsed: &mut ParsedFile) {
+    fn extract_references(expr: &Expression, path: &Path, parsed: &mut ParsedFile) {
         match expr {
             Expression::Identifier(ident) => {
-                parsed.references.push(SymbolReference {
+                parsed.references.push(ParsedReference {
                     symbol: ident.name.to_string(),
-                    file: path.clone(),
+                    file: path.to_path_buf(),
                     span: (ident.span.start as usize, ident.span.end as usize),
                 });
             }
@@ -345,9 +1368,9 @@ impl AstAnalyzer {
                     Self::extract_references(member_expr.object(), path, parsed);
                     // Extract the property name if it's a static property
                     if let Some(prop_name) = member_expr.static_property_name() {
-                        parsed.references.push(SymbolReference {
+                        parsed.references.push(ParsedReference {
                             symbol: prop_name.to_string(),
-                            file: path.clone(),
+                            file: path.to_path_buf(),
                             span: (member_expr.span().start as usize, member_expr.span().end as usize),
                         });
                     }
@@ -392,9 +1415,94 @@ impl AstAnalyzer {
         }
     }
 
+    /// Record the references a class makes outside its method bodies, as
+    /// dependency injection reads them: its decorators, then the types and
+    /// decorators of its constructor parameters, e.g. the `UsersService` of a
+    /// Nest `constructor(private users: UsersService)`
+    fn handle_class(class: &Class, path: &Path, parsed: &mut ParsedFile) -> Option<String> {
+        let selector = Self::handle_decorators(&class.decorators, path, parsed);
+
+        let constructor = class.body.body.iter().find_map(|element| match element {
+            ClassElement::MethodDefinition(method) if method.kind == MethodDefinitionKind::Constructor => {
+                Some(method)
+            }
+            _ => None,
+        });
+        for param in constructor.iter().flat_map(|method| &method.value.params.items) {
+            for decorator in &param.decorators {
+                Self::extract_references(&decorator.expression, path, parsed);
+            }
+            let annotation = param.pattern.type_annotation.as_ref();
+            if let Some(TSType::TSTypeReference(reference)) = annotation.map(|a| &a.type_annotation) {
+                let mut name = &reference.type_name;
+                // `Config.Service` refers to the `Config` binding
+                while let TSTypeName::QualifiedName(qualified) = name {
+                    name = &qualified.left;
+                }
+                if let TSTypeName::IdentifierReference(ident) = name {
+                    parsed.references.push(ParsedReference {
+                        symbol: ident.name.to_string(),
+                        file: path.to_path_buf(),
+                        span: (ident.span.start as usize, ident.span.end as usize),
+                    });
+                }
+            }
+        }
+
+        selector
+    }
+
+    /// Record the references in a class's decorators, e.g. the components
+    /// and providers listed in an Angular `@NgModule`, returning the selector
+    /// of an Angular `@Component` or `@Directive`
+    ///
+    /// The element and attribute names its template uses are recorded too,
+    /// from an inline `template` or the file its `templateUrl` names.
+    fn handle_decorators(decorators: &[Decorator], path: &Path, parsed: &mut ParsedFile) -> Option<String> {
+        let mut selector = None;
+        for decorator in decorators {
+            Self::extract_references(&decorator.expression, path, parsed);
+
+            let Expression::CallExpression(call) = &decorator.expression else {
+                continue;
+            };
+            let is_component = matches!(
+                &call.callee,
+                Expression::Identifier(ident) if matches!(ident.name.as_str(), "Component" | "Directive")
+            );
+            let metadata = call.arguments.first().and_then(|arg| arg.as_expression());
+            let (true, Some(Expression::ObjectExpression(metadata))) = (is_component, metadata) else {
+                continue;
+            };
+
+            for property in &metadata.properties {
+                let ObjectPropertyKind::ObjectProperty(property) = property else {
+                    continue;
+                };
+                let value = match &property.value {
+                    Expression::StringLiteral(literal) => literal.value.to_string(),
+                    Expression::TemplateLiteral(literal) => {
+                        literal.quasis.iter().map(|quasi| quasi.value.raw.as_str()).collect()
+                    }
+                    _ => continue,
+                };
+                match property.key.static_name().as_deref() {
+                    Some("selector") => selector = Some(value),
+                    Some("template") => parsed.template_names.extend(angular::template_names(&value)),
+                    Some("templateUrl") => {
+                        let dir = path.parent().unwrap_or(Path::new(""));
+                        parsed.template_files.push(resolver::normalize(&dir.join(value)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        selector
+    }
+
     fn extract_references_from_argument(
         arg: &Argument,
-        path: &PathBuf,
+        path: &Path,
         parsed: &mut ParsedFile,
     ) {
         match arg {
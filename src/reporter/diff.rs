@@ -0,0 +1,59 @@
+use crate::fixer::Fix;
+use crate::source::LineIndex;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// Render a planned fix as `-`/`+` lines covering the lines it touches
+pub fn render_fix(fix: &Fix, color: bool) -> String {
+    let paint = |line: String, code: &str| {
+        if color {
+            format!("{}{}{}", code, line, RESET)
+        } else {
+            line
+        }
+    };
+
+    match fix {
+        Fix::DeleteFile { path } => {
+            format!("{}\n", paint(format!("    - delete {}", path.display()), RED))
+        }
+        Fix::Edit {
+            path,
+            source,
+            edits,
+        } => {
+            let (Some(first), Some(last)) = (edits.first(), edits.last()) else {
+                return String::new();
+            };
+
+            // Expand the edited range to whole lines
+            let start = source[..first.span.0].rfind('\n').map_or(0, |i| i + 1);
+            let end = source[last.span.1..]
+                .find('\n')
+                .map_or(source.len(), |i| last.span.1 + i);
+
+            let mut replaced = String::new();
+            let mut cursor = start;
+            for edit in edits {
+                replaced.push_str(&source[cursor..edit.span.0]);
+                replaced.push_str(&edit.replacement);
+                cursor = edit.span.1;
+            }
+            replaced.push_str(&source[cursor.min(end)..end]);
+
+            let (line, _) = LineIndex::new(source).line_col(start);
+            let mut diff = format!("    @@ {}:{} @@\n", path.display(), line);
+            for line in source[start..end].lines() {
+                diff.push_str(&paint(format!("    - {}", line), RED));
+                diff.push('\n');
+            }
+            for line in replaced.lines() {
+                diff.push_str(&paint(format!("    + {}", line), GREEN));
+                diff.push('\n');
+            }
+            diff
+        }
+    }
+}
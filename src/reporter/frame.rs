@@ -0,0 +1,50 @@
+use crate::source::LineIndex;
+
+/// Lines of context shown above and below the highlighted line
+const CONTEXT_LINES: usize = 2;
+
+/// Render a code frame around a byte span, underlining the span
+///
+/// ```text
+///    3 | import { helper } from './helper';
+///    4 |
+///  > 5 | export function unused() {}
+///      |                 ^^^^^^
+/// ```
+pub fn code_frame(source: &str, index: &LineIndex, span: (usize, usize)) -> String {
+    let (line, column) = index.line_col(span.0);
+    let (end_line, end_column) = index.line_col(span.1);
+
+    // The empty line after a trailing newline, or blank ones before it, aren't context
+    let last_line = index.line_col(source.trim_end().len()).0.max(line);
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (line + CONTEXT_LINES).min(last_line);
+    let gutter = last.to_string().len();
+
+    let mut frame = String::new();
+    for current in first..=last {
+        let text = index.line_text(source, current).unwrap_or_default();
+        let marker = if current == line { '>' } else { ' ' };
+        frame.push_str(&format!(" {} {:>gutter$} | {}\n", marker, current, text));
+
+        if current == line {
+            let text_width = text.chars().count() + 1;
+            let underline_end = if end_line == line { end_column } else { text_width };
+            let width = underline_end.saturating_sub(column).max(1);
+            // Keep tabs so the underline lines up with the source text
+            let padding: String = text
+                .chars()
+                .take(column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            frame.push_str(&format!(
+                "   {:>gutter$} | {}{}\n",
+                "",
+                padding,
+                "^".repeat(width)
+            ));
+        }
+    }
+
+    frame
+}
@@ -0,0 +1,522 @@
+use super::Reporter;
+use crate::graph::FileImportGraph;
+use crate::rules::AnalysisReport;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Most files drawn in the import graph; laying it out in the browser takes
+/// time quadratic in the number of files, so larger graphs are left out
+const MAX_GRAPH_NODES: usize = 1500;
+
+/// Renders a self-contained HTML page with filterable findings grouped by
+/// directory and an interactive view of the import graph
+pub struct HtmlReporter {
+    root: PathBuf,
+    graph: GraphData,
+}
+
+#[derive(Debug, Serialize)]
+struct GraphData {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+#[derive(Debug, Serialize)]
+struct GraphNode {
+    id: String,
+    entry: bool,
+    unused: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct GraphEdge {
+    from: String,
+    to: String,
+    symbols: Vec<String>,
+    type_only: bool,
+}
+
+struct Row {
+    category: &'static str,
+    name: String,
+    location: String,
+}
+
+impl HtmlReporter {
+    pub fn new(root: &Path, file_graph: &FileImportGraph) -> Self {
+        let reachable = file_graph.reachable_files();
+        let relative = |path: &Path| relative_path(root, path);
+
+        let mut nodes: BTreeMap<String, GraphNode> = file_graph
+            .files()
+            .map(|file| {
+                let id = relative(&file.path);
+                let node = GraphNode {
+                    id: id.clone(),
+                    entry: file.is_entry_point,
+                    unused: !file.is_entry_point && !reachable.contains(&file.id),
+                };
+                (id, node)
+            })
+            .collect();
+
+        let edges = file_graph
+            .imports()
+            .map(|edge| {
+                let to = relative(file_graph.path(edge.to));
+                // Imports that don't match a discovered file still get a node
+                nodes.entry(to.clone()).or_insert_with(|| GraphNode {
+                    id: to.clone(),
+                    entry: false,
+                    unused: false,
+                });
+                GraphEdge {
+                    from: relative(file_graph.path(edge.from)),
+                    to,
+                    symbols: edge
+                        .imported_symbols
+                        .iter()
+                        .map(|&name| file_graph.name(name).to_string())
+                        .collect(),
+                    type_only: edge.is_type_only,
+                }
+            })
+            .collect();
+
+        Self {
+            root: root.to_path_buf(),
+            graph: GraphData {
+                nodes: nodes.into_values().collect(),
+                edges,
+            },
+        }
+    }
+
+    /// Flatten the report into table rows grouped by directory
+    fn rows_by_directory(&self, report: &AnalysisReport) -> BTreeMap<String, Vec<Row>> {
+        let mut groups: BTreeMap<String, Vec<Row>> = BTreeMap::new();
+
+        for dep in &report.unused_dependencies {
+            let manifest = relative_path(&self.root, &dep.manifest);
+            groups.entry(manifest.clone()).or_default().push(Row {
+                category: "dependency",
+                name: format!("{}@{}", dep.name, dep.version),
+                location: manifest,
+            });
+        }
+
+        for dep in &report.unused_peer_dependencies {
+            let manifest = relative_path(&self.root, &dep.manifest);
+            groups.entry(manifest.clone()).or_default().push(Row {
+                category: "peer",
+                name: format!("{}@{}", dep.name, dep.version),
+                location: manifest,
+            });
+        }
+
+        for dep in &report.orphaned_types {
+            let manifest = relative_path(&self.root, &dep.manifest);
+            groups.entry(manifest.clone()).or_default().push(Row {
+                category: "orphaned",
+                name: format!("{}@{}", dep.name, dep.version),
+                location: manifest,
+            });
+        }
+
+        for dep in &report.type_only_dependencies {
+            let manifest = relative_path(&self.root, &dep.manifest);
+            groups.entry(manifest.clone()).or_default().push(Row {
+                category: "type-only",
+                name: format!("{}@{}", dep.name, dep.version),
+                location: manifest,
+            });
+        }
+
+        for export in &report.unused_exports {
+            groups.entry(self.directory_of(&export.file)).or_default().push(Row {
+                category: "export",
+                name: export.name.clone(),
+                location: format!("{}:{}", relative_path(&self.root, &export.file), export.line),
+            });
+        }
+
+        for export in &report.module_only_exports {
+            groups.entry(self.directory_of(&export.file)).or_default().push(Row {
+                category: "module-only",
+                name: export.name.clone(),
+                location: format!("{}:{}", relative_path(&self.root, &export.file), export.line),
+            });
+        }
+
+        for member in &report.unused_private_members {
+            groups.entry(self.directory_of(&member.file)).or_default().push(Row {
+                category: "private-member",
+                name: super::member_label(member),
+                location: format!("{}:{}", relative_path(&self.root, &member.file), member.line),
+            });
+        }
+
+        for local in &report.unused_locals {
+            groups.entry(self.directory_of(&local.file)).or_default().push(Row {
+                category: "local",
+                name: local.name.clone(),
+                location: format!("{}:{}", relative_path(&self.root, &local.file), local.line),
+            });
+        }
+
+        for import in &report.unused_imports {
+            groups.entry(self.directory_of(&import.file)).or_default().push(Row {
+                category: "import",
+                name: format!("{} from '{}'", import.name, import.source),
+                location: format!("{}:{}", relative_path(&self.root, &import.file), import.line),
+            });
+        }
+
+        for export in &report.unused_types {
+            groups.entry(self.directory_of(&export.file)).or_default().push(Row {
+                category: "type",
+                name: export.name.clone(),
+                location: format!("{}:{}", relative_path(&self.root, &export.file), export.line),
+            });
+        }
+
+        for file in &report.removable_files {
+            let name = relative_path(&self.root, &file.path);
+            groups.entry(self.directory_of(&file.path)).or_default().push(Row {
+                category: "removable",
+                location: format!("{} (imported by {} files, none of its exports used)", name, file.importers.len()),
+                name,
+            });
+        }
+
+        for file in &report.unused_files {
+            let name = relative_path(&self.root, &file.path);
+            let mut location = match report.dead_chains.iter().find(|chain| chain.files.contains(&file.path)) {
+                Some(chain) => format!(
+                    "{} (one of {} unused files importing each other, from {})",
+                    name,
+                    chain.files.len(),
+                    relative_path(&self.root, &chain.roots[0])
+                ),
+                None => name.clone(),
+            };
+            if file.has_side_effects {
+                location.push_str(" (may have side effects)");
+            }
+            groups.entry(self.directory_of(&file.path)).or_default().push(Row {
+                category: "file",
+                name,
+                location,
+            });
+        }
+
+        for dep in &report.unlisted_dependencies {
+            groups.entry(self.directory_of(&dep.file)).or_default().push(Row {
+                category: "unlisted",
+                name: dep.name.clone(),
+                location: relative_path(&self.root, &dep.file),
+            });
+        }
+
+        for import in &report.unresolved_imports {
+            groups.entry(self.directory_of(&import.file)).or_default().push(Row {
+                category: "unresolved",
+                name: import.specifier.clone(),
+                location: relative_path(&self.root, &import.file),
+            });
+        }
+
+        for export in &report.test_only_exports {
+            groups.entry(self.directory_of(&export.file)).or_default().push(Row {
+                category: "test-only",
+                name: export.name.clone(),
+                location: format!(
+                    "{} (used by {} test files)",
+                    relative_path(&self.root, &export.file),
+                    export.tests.len()
+                ),
+            });
+        }
+
+        for export in &report.story_only_exports {
+            groups.entry(self.directory_of(&export.file)).or_default().push(Row {
+                category: "story-only",
+                name: export.name.clone(),
+                location: format!(
+                    "{} (used by {} stories)",
+                    relative_path(&self.root, &export.file),
+                    export.stories.len()
+                ),
+            });
+        }
+
+        for duplicate in &report.duplicate_exports {
+            for location in &duplicate.locations {
+                groups.entry(self.directory_of(&location.file)).or_default().push(Row {
+                    category: "duplicate",
+                    name: duplicate.name.clone(),
+                    location: format!(
+                        "{} ({} usages, exported from {} files)",
+                        relative_path(&self.root, &location.file),
+                        location.usages,
+                        duplicate.locations.len()
+                    ),
+                });
+            }
+        }
+
+        groups
+    }
+
+    fn directory_of(&self, path: &Path) -> String {
+        let dir = path
+            .parent()
+            .map(|parent| relative_path(&self.root, parent))
+            .unwrap_or_default();
+
+        if dir.is_empty() {
+            ".".to_string()
+        } else {
+            dir
+        }
+    }
+}
+
+impl Reporter for HtmlReporter {
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()> {
+        let total = report.total();
+
+        writeln!(out, "<!DOCTYPE html>")?;
+        writeln!(out, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">")?;
+        writeln!(out, "<title>Sweepr Analysis Report</title>")?;
+        writeln!(out, "<style>{}</style>\n</head>\n<body>", STYLE)?;
+        writeln!(out, "<h1>Sweepr Analysis Report</h1>")?;
+        writeln!(
+            out,
+            "<p class=\"summary\">{} issues found: {} unused dependencies, {} unused exports, {} unused types, {} unused files, {} duplicate exports, {} unlisted dependencies, {} unresolved imports, {} test-only exports, {} orphaned type packages, {} unused peer dependencies, {} story-only exports, {} type-only dependencies, {} module-only exports, {} unused private members, {} unused locals, {} unused imports, {} removable files</p>",
+            total,
+            report.unused_dependencies.len(),
+            report.unused_exports.len(),
+            report.unused_types.len(),
+            report.unused_files.len(),
+            report.duplicate_exports.len(),
+            report.unlisted_dependencies.len(),
+            report.unresolved_imports.len(),
+            report.test_only_exports.len(),
+            report.orphaned_types.len(),
+            report.unused_peer_dependencies.len(),
+            report.story_only_exports.len(),
+            report.type_only_dependencies.len(),
+            report.module_only_exports.len(),
+            report.unused_private_members.len(),
+            report.unused_locals.len(),
+            report.unused_imports.len(),
+            report.removable_files.len()
+        )?;
+        let savings = report.savings();
+        if savings.lines > 0 {
+            writeln!(
+                out,
+                "<p class=\"summary\">Removing them deletes about {} lines ({} bytes)</p>",
+                savings.lines, savings.bytes
+            )?;
+        }
+
+        writeln!(out, "<h2>Findings</h2>")?;
+        writeln!(out, "<div class=\"filters\">")?;
+        writeln!(out, "<input id=\"filter\" type=\"search\" placeholder=\"Filter by name or path\">")?;
+        let categories = [
+            "dependency", "peer", "orphaned", "export", "type", "file", "duplicate", "unlisted",
+            "unresolved", "test-only", "story-only", "type-only", "module-only",
+            "private-member", "local", "import", "removable",
+        ];
+        for category in categories {
+            writeln!(
+                out,
+                "<label><input type=\"checkbox\" class=\"category\" value=\"{0}\" checked> {0}</label>",
+                category
+            )?;
+        }
+        writeln!(out, "</div>")?;
+
+        for (directory, rows) in self.rows_by_directory(report) {
+            writeln!(out, "<details class=\"group\" open>")?;
+            writeln!(
+                out,
+                "<summary>{} <span class=\"count\">({})</span></summary>",
+                escape_html(&directory),
+                rows.len()
+            )?;
+            writeln!(out, "<table>\n<tr><th>Kind</th><th>Name</th><th>Location</th></tr>")?;
+            for row in rows {
+                writeln!(
+                    out,
+                    "<tr data-category=\"{0}\"><td>{0}</td><td>{1}</td><td>{2}</td></tr>",
+                    row.category,
+                    escape_html(&row.name),
+                    escape_html(&row.location)
+                )?;
+            }
+            writeln!(out, "</table>\n</details>")?;
+        }
+
+        if total == 0 {
+            writeln!(out, "<p>No unused code found! Your project is clean.</p>")?;
+        }
+
+        writeln!(out, "<h2>Import Graph</h2>")?;
+        if self.graph.nodes.len() > MAX_GRAPH_NODES {
+            writeln!(
+                out,
+                "<p>The graph of {} files is too large to draw here; run <code>sweepr graph</code> to export it.</p>",
+                self.graph.nodes.len()
+            )?;
+        } else {
+            writeln!(
+                out,
+                "<p class=\"legend\"><span class=\"entry\">&#9679; entry point</span> <span class=\"unused\">&#9679; unused file</span> <span>&#9679; reachable file</span> &mdash; dashed edges are type-only imports</p>"
+            )?;
+            writeln!(out, "<svg id=\"graph\" width=\"100%\" height=\"600\"></svg>")?;
+
+            // `</` must not appear inside the script element
+            let graph_json = serde_json::to_string(&self.graph)?.replace("</", "<\\/");
+            writeln!(out, "<script>const GRAPH = {};</script>", graph_json)?;
+        }
+        writeln!(out, "<script>{}</script>", SCRIPT)?;
+        writeln!(out, "</body>\n</html>")?;
+
+        Ok(())
+    }
+}
+
+fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Helvetica, Arial, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { margin-bottom: 0.25rem; }
+.summary { color: #59636e; }
+.filters { display: flex; gap: 1rem; align-items: center; margin-bottom: 1rem; }
+.filters input[type=search] { padding: 0.4rem; width: 20rem; }
+details.group { margin-bottom: 0.75rem; }
+summary { cursor: pointer; font-weight: 600; font-family: monospace; }
+.count { color: #59636e; font-weight: normal; }
+table { border-collapse: collapse; margin: 0.5rem 0 0 1rem; }
+th, td { text-align: left; padding: 0.25rem 0.75rem; border-bottom: 1px solid #d1d9e0; }
+td:nth-child(2), td:nth-child(3) { font-family: monospace; }
+.legend span { margin-right: 1rem; }
+.legend .entry { color: #1a7f37; }
+.legend .unused { color: #cf222e; }
+#graph { border: 1px solid #d1d9e0; border-radius: 6px; }
+#graph text { font: 10px monospace; fill: #1f2328; }
+"#;
+
+const SCRIPT: &str = r#"
+(function () {
+  const filter = document.getElementById('filter');
+  const boxes = Array.from(document.querySelectorAll('input.category'));
+  function applyFilter() {
+    const needle = filter.value.toLowerCase();
+    const enabled = new Set(boxes.filter(b => b.checked).map(b => b.value));
+    document.querySelectorAll('details.group').forEach(group => {
+      let visible = 0;
+      group.querySelectorAll('tr[data-category]').forEach(row => {
+        const show = enabled.has(row.dataset.category) &&
+          row.textContent.toLowerCase().includes(needle);
+        row.style.display = show ? '' : 'none';
+        if (show) visible++;
+      });
+      group.style.display = visible ? '' : 'none';
+    });
+  }
+  filter.addEventListener('input', applyFilter);
+  boxes.forEach(b => b.addEventListener('change', applyFilter));
+
+  const svg = document.getElementById('graph');
+  if (!svg) return;
+  const ns = 'http://www.w3.org/2000/svg';
+  const width = svg.clientWidth || 960, height = 600;
+  const index = new Map(GRAPH.nodes.map((n, i) => [n.id, i]));
+  const nodes = GRAPH.nodes.map((n, i) => Object.assign({}, n, {
+    x: width / 2 + Math.cos(i) * width / 3 * Math.random(),
+    y: height / 2 + Math.sin(i) * height / 3 * Math.random(),
+    vx: 0, vy: 0
+  }));
+  const edges = GRAPH.edges
+    .filter(e => index.has(e.from) && index.has(e.to))
+    .map(e => Object.assign({}, e, { source: nodes[index.get(e.from)], target: nodes[index.get(e.to)] }));
+
+  // Simple force-directed layout: repulsion between nodes, springs along edges.
+  // Each step compares every pair of nodes, so large graphs take fewer steps.
+  const steps = Math.min(300, Math.max(20, Math.floor(5e7 / (nodes.length * nodes.length || 1))));
+  for (let step = 0; step < steps; step++) {
+    for (let i = 0; i < nodes.length; i++) {
+      for (let j = i + 1; j < nodes.length; j++) {
+        const a = nodes[i], b = nodes[j];
+        let dx = a.x - b.x, dy = a.y - b.y;
+        const dist2 = Math.max(dx * dx + dy * dy, 1);
+        const force = 800 / dist2;
+        dx *= force; dy *= force;
+        a.vx += dx; a.vy += dy; b.vx -= dx; b.vy -= dy;
+      }
+    }
+    edges.forEach(e => {
+      const dx = e.target.x - e.source.x, dy = e.target.y - e.source.y;
+      e.source.vx += dx * 0.01; e.source.vy += dy * 0.01;
+      e.target.vx -= dx * 0.01; e.target.vy -= dy * 0.01;
+    });
+    nodes.forEach(n => {
+      n.vx += (width / 2 - n.x) * 0.002; n.vy += (height / 2 - n.y) * 0.002;
+      n.x = Math.min(width - 10, Math.max(10, n.x + n.vx * 0.5));
+      n.y = Math.min(height - 10, Math.max(10, n.y + n.vy * 0.5));
+      n.vx *= 0.6; n.vy *= 0.6;
+    });
+  }
+
+  edges.forEach(e => {
+    const line = document.createElementNS(ns, 'line');
+    line.setAttribute('x1', e.source.x); line.setAttribute('y1', e.source.y);
+    line.setAttribute('x2', e.target.x); line.setAttribute('y2', e.target.y);
+    line.setAttribute('stroke', '#8c959f');
+    if (e.type_only) line.setAttribute('stroke-dasharray', '4 3');
+    const title = document.createElementNS(ns, 'title');
+    title.textContent = e.from + ' -> ' + e.to + (e.symbols.length ? ' {' + e.symbols.join(', ') + '}' : '');
+    line.appendChild(title);
+    svg.appendChild(line);
+  });
+  nodes.forEach(n => {
+    const circle = document.createElementNS(ns, 'circle');
+    circle.setAttribute('cx', n.x); circle.setAttribute('cy', n.y); circle.setAttribute('r', 5);
+    circle.setAttribute('fill', n.entry ? '#1a7f37' : n.unused ? '#cf222e' : '#0969da');
+    const title = document.createElementNS(ns, 'title');
+    title.textContent = n.id;
+    circle.appendChild(title);
+    svg.appendChild(circle);
+    const label = document.createElementNS(ns, 'text');
+    label.setAttribute('x', n.x + 7); label.setAttribute('y', n.y + 3);
+    label.textContent = n.id.split('/').pop();
+    svg.appendChild(label);
+  });
+})();
+"#;
@@ -1,64 +1,428 @@
-use crate::rules::AnalysisReport;
+mod diff;
+mod frame;
+mod html;
+mod registry;
+
+pub use html::HtmlReporter;
+pub use registry::{ReportContext, ReportOptions, ReporterFactory, ReporterRegistry};
+
+use crate::fixer::{Fix, Fixer};
+use crate::rules::{rule_code, AnalysisReport, Finding, UnusedExport, UnusedPrivateMember, RULE_CODES, RULE_NAMES};
+use crate::source::LineIndex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// How `CliReporter` groups findings
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Directory containing the finding
+    Dir,
+    /// Nearest package.json above the finding
+    Package,
+    /// Rule that produced the finding
+    Rule,
+}
 
 pub trait Reporter {
-    fn report(&self, report: &AnalysisReport) -> io::Result<()>;
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()>;
 }
 
-pub struct CliReporter;
+pub struct CliReporter {
+    /// Findings only: no title, emoji, or summary
+    pub quiet: bool,
+    /// Show the edit the fixer would apply below each finding
+    pub fixer: Option<Fixer>,
+    /// Use ANSI colors for proposed fixes
+    pub color: bool,
+    /// Replace the per-category sections with groups and subtotals
+    pub group_by: Option<GroupBy>,
+    /// Only print counts per rule and the total
+    pub summary: bool,
+}
 
 impl Reporter for CliReporter {
-    fn report(&self, report: &AnalysisReport) -> io::Result<()> {
-        let stdout = io::stdout();
-        let mut handle = stdout.lock();
+    fn report(&self, report: &AnalysisReport, handle: &mut dyn Write) -> io::Result<()> {
+        if self.summary {
+            return self.report_summary(report, handle);
+        }
+        if let Some(group_by) = self.group_by {
+            return self.report_grouped(report, handle, group_by);
+        }
+        // Fixes need the full findings, which only the per-category sections show
+        if !report.packages.is_empty() && self.fixer.is_none() {
+            return self.report_packages(report, handle);
+        }
+        if self.quiet {
+            return self.report_quiet(report, handle);
+        }
 
         writeln!(handle, "\n🔍 Sweepr Analysis Report\n")?;
 
         // Unused dependencies
         if !report.unused_dependencies.is_empty() {
-            writeln!(handle, "❌ Unused Dependencies ({})", report.unused_dependencies.len())?;
+            writeln!(
+                handle,
+                "❌ Unused Dependencies ({}) [{}]",
+                report.unused_dependencies.len(),
+                rule_code("unused-deps")
+            )?;
             writeln!(handle, "────────────────────────────────")?;
             for dep in &report.unused_dependencies {
                 writeln!(handle, "  • {}@{}", dep.name, dep.version)?;
+                if let Some(fixer) = &self.fixer {
+                    self.write_fix(handle, fixer.fix_unused_dependency(dep))?;
+                }
             }
             writeln!(handle)?;
         }
 
         // Unused exports
         if !report.unused_exports.is_empty() {
-            writeln!(handle, "📦 Unused Exports ({})", report.unused_exports.len())?;
+            writeln!(
+                handle,
+                "📦 Unused Exports ({}) [{}]",
+                report.unused_exports.len(),
+                rule_code("unused-exports")
+            )?;
             writeln!(handle, "────────────────────────────────")?;
-            for export in &report.unused_exports {
+            self.write_exports(handle, &report.unused_exports)?;
+            writeln!(handle)?;
+        }
+
+        // Unused types
+        if !report.unused_types.is_empty() {
+            writeln!(
+                handle,
+                "🏷️  Unused Types ({}) [{}]",
+                report.unused_types.len(),
+                rule_code("unused-types")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            self.write_exports(handle, &report.unused_types)?;
+            writeln!(handle)?;
+        }
+
+        // Exports their own file uses, where only the keyword is unused
+        if !report.module_only_exports.is_empty() {
+            writeln!(
+                handle,
+                "🏠 Module-only Exports ({}) [{}]",
+                report.module_only_exports.len(),
+                rule_code("module-only-exports")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            self.write_exports(handle, &report.module_only_exports)?;
+            writeln!(handle, "  Only used in their own file; remove the `export` keyword")?;
+            writeln!(handle)?;
+        }
+
+        // Class members their class never reads, which nothing else can
+        if !report.unused_private_members.is_empty() {
+            writeln!(
+                handle,
+                "🔒 Unused Private Members ({}) [{}]",
+                report.unused_private_members.len(),
+                rule_code("unused-private-members")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            let mut sources = HashMap::new();
+            for member in &report.unused_private_members {
                 writeln!(
                     handle,
                     "  • {} in {}:{}",
-                    export.name,
-                    export.file.display(),
-                    export.line
+                    member_label(member),
+                    member.file.display(),
+                    member.line
                 )?;
+                write_frame(handle, &mut sources, &member.file, member.span)?;
+            }
+            writeln!(handle)?;
+        }
+
+        // Declarations nothing reads
+        if !report.unused_locals.is_empty() {
+            writeln!(
+                handle,
+                "🧹 Unused Locals ({}) [{}]",
+                report.unused_locals.len(),
+                rule_code("unused-locals")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            let mut sources = HashMap::new();
+            for local in &report.unused_locals {
+                writeln!(handle, "  • {} in {}:{}", local.name, local.file.display(), local.line)?;
+                write_frame(handle, &mut sources, &local.file, local.span)?;
+            }
+            writeln!(handle)?;
+        }
+
+        // Import bindings nothing references
+        if !report.unused_imports.is_empty() {
+            writeln!(
+                handle,
+                "📥 Unused Imports ({}) [{}]",
+                report.unused_imports.len(),
+                rule_code("unused-imports")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            let mut sources = HashMap::new();
+            for import in &report.unused_imports {
+                writeln!(
+                    handle,
+                    "  • {} from '{}' in {}:{}",
+                    import.name,
+                    import.source,
+                    import.file.display(),
+                    import.line
+                )?;
+                write_frame(handle, &mut sources, &import.file, import.span)?;
+                if let Some(fixer) = &self.fixer {
+                    self.write_fix(handle, fixer.fix_unused_import(import))?;
+                    writeln!(handle)?;
+                }
             }
             writeln!(handle)?;
         }
 
         // Unused files
         if !report.unused_files.is_empty() {
-            writeln!(handle, "📄 Unused Files ({})", report.unused_files.len())?;
+            writeln!(
+                handle,
+                "📄 Unused Files ({}) [{}]",
+                report.unused_files.len(),
+                rule_code("unused-files")
+            )?;
             writeln!(handle, "────────────────────────────────")?;
             for file in &report.unused_files {
-                writeln!(handle, "  • {}", file.path.display())?;
+                if file.has_side_effects {
+                    writeln!(handle, "  • {} (may have side effects)", file.path.display())?;
+                } else {
+                    writeln!(handle, "  • {}", file.path.display())?;
+                }
+                if let Some(fixer) = &self.fixer {
+                    match fixer.fix_unused_file(file) {
+                        Some(fix) => self.write_fix(handle, Some(fix))?,
+                        None if file.has_side_effects => writeln!(
+                            handle,
+                            "    (package.json declares side effects for this file; deleting it requires `sweepr fix --unsafe`)"
+                        )?,
+                        None => writeln!(handle, "    (deleting files requires `sweepr fix --unsafe`)")?,
+                    }
+                }
+            }
+            for chain in &report.dead_chains {
+                let roots: Vec<String> = chain.roots.iter().map(|root| root.display().to_string()).collect();
+                writeln!(
+                    handle,
+                    "  ⛓️  {} files only import each other, starting from {}",
+                    chain.files.len(),
+                    roots.join(", ")
+                )?;
+                for file in &chain.files {
+                    writeln!(handle, "      {}", file.display())?;
+                }
             }
             writeln!(handle)?;
         }
 
-        if report.unused_dependencies.is_empty()
-            && report.unused_exports.is_empty()
-            && report.unused_files.is_empty()
-        {
+        // Files still imported, though nothing they export is used
+        if !report.removable_files.is_empty() {
+            writeln!(
+                handle,
+                "✂️  Removable Files ({}) [{}]",
+                report.removable_files.len(),
+                rule_code("removable-files")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for file in &report.removable_files {
+                writeln!(handle, "  • {} ({})", file.path.display(), export_count(file.exports))?;
+                for importer in &file.importers {
+                    writeln!(handle, "      imported by {}", importer.display())?;
+                }
+            }
+            writeln!(handle, "  None of their exports are used; delete them along with the imports of them")?;
+            writeln!(handle)?;
+        }
+
+        // Names exported from several files
+        if !report.duplicate_exports.is_empty() {
+            writeln!(
+                handle,
+                "👯 Duplicate Exports ({}) [{}]",
+                report.duplicate_exports.len(),
+                rule_code("duplicate-exports")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for duplicate in &report.duplicate_exports {
+                writeln!(
+                    handle,
+                    "  • {} is exported from {} files",
+                    duplicate.name,
+                    duplicate.locations.len()
+                )?;
+                for location in &duplicate.locations {
+                    writeln!(
+                        handle,
+                        "      {} ({})",
+                        location.file.display(),
+                        usages(location.usages)
+                    )?;
+                }
+            }
+            writeln!(handle)?;
+        }
+
+        // Packages imported without being declared
+        if !report.unlisted_dependencies.is_empty() {
+            writeln!(
+                handle,
+                "❓ Unlisted Dependencies ({}) [{}]",
+                report.unlisted_dependencies.len(),
+                rule_code("unlisted-deps")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for dep in &report.unlisted_dependencies {
+                writeln!(handle, "  • {} imported in {}", dep.name, dep.file.display())?;
+            }
+            writeln!(handle)?;
+        }
+
+        // Imports pointing at files that don't exist
+        if !report.unresolved_imports.is_empty() {
+            writeln!(
+                handle,
+                "💔 Unresolved Imports ({}) [{}]",
+                report.unresolved_imports.len(),
+                rule_code("unresolved-imports")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for import in &report.unresolved_imports {
+                writeln!(handle, "  • '{}' in {}", import.specifier, import.file.display())?;
+            }
+            writeln!(handle)?;
+        }
+
+        // Exports kept alive only by tests
+        if !report.test_only_exports.is_empty() {
+            writeln!(
+                handle,
+                "🧪 Test-only Exports ({}) [{}]",
+                report.test_only_exports.len(),
+                rule_code("test-only-exports")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for export in &report.test_only_exports {
+                writeln!(handle, "  • {} in {}", export.name, export.file.display())?;
+                for test in &export.tests {
+                    writeln!(handle, "      used by {}", test.display())?;
+                }
+            }
+            writeln!(handle)?;
+        }
+
+        // Exports kept alive only by Storybook stories
+        if !report.story_only_exports.is_empty() {
+            writeln!(
+                handle,
+                "📚 Story-only Exports ({}) [{}]",
+                report.story_only_exports.len(),
+                rule_code("story-only-exports")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for export in &report.story_only_exports {
+                writeln!(handle, "  • {} in {}", export.name, export.file.display())?;
+                for story in &export.stories {
+                    writeln!(handle, "      used by {}", story.display())?;
+                }
+            }
+            writeln!(handle)?;
+        }
+
+        // Peers the package asks hosts for without using them
+        if !report.unused_peer_dependencies.is_empty() {
+            writeln!(
+                handle,
+                "🤝 Unused Peer Dependencies ({}) [{}]",
+                report.unused_peer_dependencies.len(),
+                rule_code("unused-peers")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for dep in &report.unused_peer_dependencies {
+                writeln!(handle, "  • {}@{} in {}", dep.name, dep.version, dep.manifest.display())?;
+            }
+            writeln!(handle, "  Removing a peer changes what consumers must install; publish it as a breaking change")?;
+            writeln!(handle)?;
+        }
+
+        // Type packages left behind by removed dependencies
+        if !report.orphaned_types.is_empty() {
+            writeln!(
+                handle,
+                "👻 Orphaned Type Packages ({}) [{}]",
+                report.orphaned_types.len(),
+                rule_code("orphaned-types")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for dep in &report.orphaned_types {
+                writeln!(handle, "  • {}@{} in {}", dep.name, dep.version, dep.manifest.display())?;
+                if let Some(fixer) = &self.fixer {
+                    self.write_fix(handle, fixer.fix_unused_dependency(dep))?;
+                }
+            }
+            writeln!(handle)?;
+        }
+
+        // Runtime dependencies that only ever provide types
+        if !report.type_only_dependencies.is_empty() {
+            writeln!(
+                handle,
+                "🏷️  Type-only Dependencies ({}) [{}]",
+                report.type_only_dependencies.len(),
+                rule_code("type-only-deps")
+            )?;
+            writeln!(handle, "────────────────────────────────")?;
+            for dep in &report.type_only_dependencies {
+                writeln!(handle, "  • {}@{} in {}", dep.name, dep.version, dep.manifest.display())?;
+            }
+            writeln!(handle, "  Only imported with `import type`; consider moving them to devDependencies")?;
+            writeln!(handle)?;
+        }
+
+        // Files the scanner left out
+        if !report.skipped_files.is_empty() {
+            writeln!(handle, "⏭️  Skipped Files ({})", report.skipped_files.len())?;
+            writeln!(handle, "────────────────────────────────")?;
+            for skipped in &report.skipped_files {
+                writeln!(handle, "  • {} ({})", skipped.path.display(), skipped.reason)?;
+            }
+            writeln!(handle)?;
+        }
+
+        // Files that failed to parse, analyzed as if they didn't exist
+        if !report.parse_failures.is_empty() {
+            writeln!(handle, "💥 Parse Failures ({})", report.parse_failures.len())?;
+            writeln!(handle, "────────────────────────────────")?;
+            for failure in &report.parse_failures {
+                writeln!(handle, "  • {}: {}", failure.path.display(), failure.message)?;
+            }
+            writeln!(handle)?;
+        }
+
+        let total = report.total();
+        let savings = report.savings();
+        if total == 0 {
             writeln!(handle, "✅ No unused code found! Your project is clean.\n")?;
+        } else if savings.lines > 0 {
+            writeln!(
+                handle,
+                "📊 Summary: {} issues found (~{} LOC, {} removable)\n",
+                total,
+                thousands(savings.lines),
+                byte_size(savings.bytes)
+            )?;
         } else {
-            let total = report.unused_dependencies.len()
-                + report.unused_exports.len()
-                + report.unused_files.len();
             writeln!(handle, "📊 Summary: {} issues found\n", total)?;
         }
 
@@ -66,12 +430,772 @@ impl Reporter for CliReporter {
     }
 }
 
-pub struct JsonReporter;
+impl CliReporter {
+    fn report_summary(&self, report: &AnalysisReport, handle: &mut dyn Write) -> io::Result<()> {
+        let total = report.total();
+
+        if !self.quiet {
+            writeln!(handle, "\n📊 Summary")?;
+        }
+        for (rule, count) in report.counts() {
+            writeln!(handle, "  {} {:<22} {}", rule_code(rule), rule, count)?;
+        }
+        writeln!(handle, "  {:<29} {}", "total", total)?;
+        if !report.packages.is_empty() {
+            writeln!(handle, "{}", if self.quiet { "packages" } else { "\n📦 Packages" })?;
+            for package in &report.packages {
+                writeln!(handle, "  {:<18} {}", package.name, package.total)?;
+            }
+        }
+        if !self.quiet {
+            writeln!(handle)?;
+        }
+
+        Ok(())
+    }
+
+    fn report_grouped(
+        &self,
+        report: &AnalysisReport,
+        handle: &mut dyn Write,
+        group_by: GroupBy,
+    ) -> io::Result<()> {
+        let mut packages: HashMap<PathBuf, String> = HashMap::new();
+        let mut groups: BTreeMap<String, Vec<Finding>> = BTreeMap::new();
+
+        for finding in report.findings() {
+            let key = match group_by {
+                GroupBy::Rule => format!("{} {}", finding.code, finding.rule),
+                GroupBy::Dir => match finding.path.parent() {
+                    Some(parent) if !parent.as_os_str().is_empty() => parent.display().to_string(),
+                    _ => ".".to_string(),
+                },
+                GroupBy::Package => package_of(&finding.path, &mut packages),
+            };
+            groups.entry(key).or_default().push(finding);
+        }
+
+        if !self.quiet {
+            writeln!(handle, "\n🔍 Sweepr Analysis Report\n")?;
+        }
+
+        for (key, findings) in &groups {
+            if self.quiet {
+                writeln!(handle, "{} ({})", key, findings.len())?;
+            } else {
+                writeln!(handle, "📁 {} ({})", key, findings.len())?;
+                writeln!(handle, "────────────────────────────────")?;
+            }
+
+            for finding in findings {
+                self.write_finding(handle, finding)?;
+            }
+
+            if !self.quiet {
+                writeln!(handle)?;
+            }
+        }
+
+        if !self.quiet {
+            let total: usize = groups.values().map(Vec::len).sum();
+            if total == 0 {
+                writeln!(handle, "✅ No unused code found! Your project is clean.\n")?;
+            } else {
+                writeln!(handle, "📊 Summary: {} issues found in {} groups\n", total, groups.len())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// One section per workspace package with its totals by rule, then the
+    /// total of every package
+    fn report_packages(&self, report: &AnalysisReport, handle: &mut dyn Write) -> io::Result<()> {
+        if !self.quiet {
+            writeln!(handle, "\n🔍 Sweepr Analysis Report\n")?;
+        }
+
+        for package in report.packages.iter().filter(|package| package.total > 0) {
+            if self.quiet {
+                writeln!(handle, "{} ({})", package.name, package.total)?;
+            } else {
+                let counts: Vec<String> = package
+                    .counts
+                    .iter()
+                    .map(|(rule, count)| format!("{} {}", rule, count))
+                    .collect();
+                writeln!(handle, "📦 {} ({}: {})", package.name, package.total, counts.join(", "))?;
+                writeln!(handle, "────────────────────────────────")?;
+            }
+
+            for finding in &package.findings {
+                self.write_finding(handle, finding)?;
+            }
+
+            if !self.quiet {
+                writeln!(handle)?;
+            }
+        }
+
+        if !self.quiet {
+            let total: usize = report.packages.iter().map(|package| package.total).sum();
+            if total == 0 {
+                writeln!(handle, "✅ No unused code found! Your project is clean.\n")?;
+            } else {
+                writeln!(
+                    handle,
+                    "📊 Summary: {} issues found in {} packages",
+                    total,
+                    report.packages.len()
+                )?;
+                for package in &report.packages {
+                    writeln!(handle, "  {:<24} {}", package.name, package.total)?;
+                }
+                writeln!(handle)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// A finding on one line, with its rule and location
+    fn write_finding(&self, handle: &mut dyn Write, finding: &Finding) -> io::Result<()> {
+        let location = match finding.line {
+            Some(line) => format!("{}:{}", finding.path.display(), line),
+            None => finding.path.display().to_string(),
+        };
+        let bullet = if self.quiet { "" } else { "• " };
+        writeln!(
+            handle,
+            "  {}{} ({} {}) {}",
+            bullet,
+            finding.message(),
+            finding.code,
+            finding.rule,
+            location
+        )
+    }
+
+    /// One entry per export, with a code frame and, when enabled, its fix
+    fn write_exports(&self, handle: &mut dyn Write, exports: &[UnusedExport]) -> io::Result<()> {
+        let mut sources: HashMap<&PathBuf, Option<(String, LineIndex)>> = HashMap::new();
+        for export in exports {
+            writeln!(
+                handle,
+                "  • {} in {}:{}",
+                export.name,
+                export.file.display(),
+                export.line
+            )?;
+
+            write_frame(handle, &mut sources, &export.file, export.span)?;
+            if let Some(fixer) = &self.fixer {
+                self.write_fix(handle, fixer.fix_unused_export(export))?;
+                writeln!(handle)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_fix(&self, handle: &mut dyn Write, fix: Option<Fix>) -> io::Result<()> {
+        match fix {
+            Some(fix) => write!(handle, "{}", diff::render_fix(&fix, self.color)),
+            None => writeln!(handle, "    (no automatic fix available)"),
+        }
+    }
+
+    fn report_quiet(&self, report: &AnalysisReport, handle: &mut dyn Write) -> io::Result<()> {
+        if !report.unused_dependencies.is_empty() {
+            writeln!(
+                handle,
+                "Unused dependencies ({}) [{}]",
+                report.unused_dependencies.len(),
+                rule_code("unused-deps")
+            )?;
+            for dep in &report.unused_dependencies {
+                writeln!(handle, "  {}@{}", dep.name, dep.version)?;
+            }
+        }
+
+        if !report.unused_exports.is_empty() {
+            writeln!(
+                handle,
+                "Unused exports ({}) [{}]",
+                report.unused_exports.len(),
+                rule_code("unused-exports")
+            )?;
+            for export in &report.unused_exports {
+                writeln!(
+                    handle,
+                    "  {} in {}:{}",
+                    export.name,
+                    export.file.display(),
+                    export.line
+                )?;
+            }
+        }
+
+        if !report.unused_types.is_empty() {
+            writeln!(
+                handle,
+                "Unused types ({}) [{}]",
+                report.unused_types.len(),
+                rule_code("unused-types")
+            )?;
+            for export in &report.unused_types {
+                writeln!(
+                    handle,
+                    "  {} in {}:{}",
+                    export.name,
+                    export.file.display(),
+                    export.line
+                )?;
+            }
+        }
+
+        if !report.module_only_exports.is_empty() {
+            writeln!(
+                handle,
+                "Module-only exports ({}) [{}]",
+                report.module_only_exports.len(),
+                rule_code("module-only-exports")
+            )?;
+            for export in &report.module_only_exports {
+                writeln!(
+                    handle,
+                    "  {} in {}:{}",
+                    export.name,
+                    export.file.display(),
+                    export.line
+                )?;
+            }
+        }
+
+        if !report.unused_private_members.is_empty() {
+            writeln!(
+                handle,
+                "Unused private members ({}) [{}]",
+                report.unused_private_members.len(),
+                rule_code("unused-private-members")
+            )?;
+            for member in &report.unused_private_members {
+                writeln!(
+                    handle,
+                    "  {} in {}:{}",
+                    member_label(member),
+                    member.file.display(),
+                    member.line
+                )?;
+            }
+        }
+
+        if !report.unused_locals.is_empty() {
+            writeln!(
+                handle,
+                "Unused locals ({}) [{}]",
+                report.unused_locals.len(),
+                rule_code("unused-locals")
+            )?;
+            for local in &report.unused_locals {
+                writeln!(handle, "  {} in {}:{}", local.name, local.file.display(), local.line)?;
+            }
+        }
+
+        if !report.unused_imports.is_empty() {
+            writeln!(
+                handle,
+                "Unused imports ({}) [{}]",
+                report.unused_imports.len(),
+                rule_code("unused-imports")
+            )?;
+            for import in &report.unused_imports {
+                writeln!(handle, "  {} in {}:{}", import.name, import.file.display(), import.line)?;
+            }
+        }
+
+        if !report.unused_files.is_empty() {
+            writeln!(
+                handle,
+                "Unused files ({}) [{}]",
+                report.unused_files.len(),
+                rule_code("unused-files")
+            )?;
+            for file in &report.unused_files {
+                match file.has_side_effects {
+                    true => writeln!(handle, "  {} (may have side effects)", file.path.display())?,
+                    false => writeln!(handle, "  {}", file.path.display())?,
+                }
+            }
+        }
+
+        if !report.removable_files.is_empty() {
+            writeln!(
+                handle,
+                "Removable files ({}) [{}]",
+                report.removable_files.len(),
+                rule_code("removable-files")
+            )?;
+            for file in &report.removable_files {
+                writeln!(handle, "  {}", file.path.display())?;
+            }
+        }
+
+        if !report.duplicate_exports.is_empty() {
+            writeln!(
+                handle,
+                "Duplicate exports ({}) [{}]",
+                report.duplicate_exports.len(),
+                rule_code("duplicate-exports")
+            )?;
+            for duplicate in &report.duplicate_exports {
+                let locations: Vec<String> = duplicate
+                    .locations
+                    .iter()
+                    .map(|location| format!("{} ({})", location.file.display(), usages(location.usages)))
+                    .collect();
+                writeln!(handle, "  {} in {}", duplicate.name, locations.join(", "))?;
+            }
+        }
+
+        if !report.unlisted_dependencies.is_empty() {
+            writeln!(
+                handle,
+                "Unlisted dependencies ({}) [{}]",
+                report.unlisted_dependencies.len(),
+                rule_code("unlisted-deps")
+            )?;
+            for dep in &report.unlisted_dependencies {
+                writeln!(handle, "  {} in {}", dep.name, dep.file.display())?;
+            }
+        }
+
+        if !report.unresolved_imports.is_empty() {
+            writeln!(
+                handle,
+                "Unresolved imports ({}) [{}]",
+                report.unresolved_imports.len(),
+                rule_code("unresolved-imports")
+            )?;
+            for import in &report.unresolved_imports {
+                writeln!(handle, "  '{}' in {}", import.specifier, import.file.display())?;
+            }
+        }
+
+        if !report.test_only_exports.is_empty() {
+            writeln!(
+                handle,
+                "Test-only exports ({}) [{}]",
+                report.test_only_exports.len(),
+                rule_code("test-only-exports")
+            )?;
+            for export in &report.test_only_exports {
+                writeln!(handle, "  {} in {}", export.name, export.file.display())?;
+            }
+        }
+
+        if !report.story_only_exports.is_empty() {
+            writeln!(
+                handle,
+                "Story-only exports ({}) [{}]",
+                report.story_only_exports.len(),
+                rule_code("story-only-exports")
+            )?;
+            for export in &report.story_only_exports {
+                writeln!(handle, "  {} in {}", export.name, export.file.display())?;
+            }
+        }
+
+        if !report.unused_peer_dependencies.is_empty() {
+            writeln!(
+                handle,
+                "Unused peer dependencies ({}) [{}]",
+                report.unused_peer_dependencies.len(),
+                rule_code("unused-peers")
+            )?;
+            for dep in &report.unused_peer_dependencies {
+                writeln!(handle, "  {}@{}", dep.name, dep.version)?;
+            }
+        }
+
+        if !report.orphaned_types.is_empty() {
+            writeln!(
+                handle,
+                "Orphaned type packages ({}) [{}]",
+                report.orphaned_types.len(),
+                rule_code("orphaned-types")
+            )?;
+            for dep in &report.orphaned_types {
+                writeln!(handle, "  {}@{}", dep.name, dep.version)?;
+            }
+        }
+
+        if !report.type_only_dependencies.is_empty() {
+            writeln!(
+                handle,
+                "Type-only dependencies ({}) [{}]",
+                report.type_only_dependencies.len(),
+                rule_code("type-only-deps")
+            )?;
+            for dep in &report.type_only_dependencies {
+                writeln!(handle, "  {}@{}", dep.name, dep.version)?;
+            }
+        }
+
+        if !report.parse_failures.is_empty() {
+            writeln!(handle, "Parse failures ({})", report.parse_failures.len())?;
+            for failure in &report.parse_failures {
+                writeln!(handle, "  {}: {}", failure.path.display(), failure.message)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write the code frame of a span, reading each file once into `sources`
+///
+/// A missing file just skips its frames.
+fn write_frame<'a>(
+    handle: &mut dyn Write,
+    sources: &mut HashMap<&'a PathBuf, Option<(String, LineIndex)>>,
+    file: &'a PathBuf,
+    span: (usize, usize),
+) -> io::Result<()> {
+    let source = sources.entry(file).or_insert_with(|| {
+        let text = std::fs::read_to_string(file).ok()?;
+        let index = LineIndex::new(&text);
+        Some((text, index))
+    });
+    if let Some((text, index)) = source {
+        writeln!(handle, "{}", frame::code_frame(text, index, span))?;
+    }
+    Ok(())
+}
+
+/// `Class.member`, or just the member of an anonymous class
+fn member_label(member: &UnusedPrivateMember) -> String {
+    match &member.class {
+        Some(class) => format!("{}.{}", class, member.name),
+        None => member.name.clone(),
+    }
+}
+
+fn usages(count: usize) -> String {
+    match count {
+        1 => "1 usage".to_string(),
+        _ => format!("{} usages", count),
+    }
+}
+
+/// A count with thousands separators, e.g. `4,200`
+fn thousands(count: usize) -> String {
+    let digits = count.to_string();
+    let mut out = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+/// A byte count in the largest unit that keeps it above one, e.g. `12.4 KB`
+fn byte_size(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+fn export_count(count: usize) -> String {
+    match count {
+        1 => "1 unused export".to_string(),
+        _ => format!("{} unused exports", count),
+    }
+}
+
+/// Name of the package owning a path: the nearest package.json above it
+fn package_of(path: &Path, cache: &mut HashMap<PathBuf, String>) -> String {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut dir = path.parent();
+
+    while let Some(current) = dir {
+        if let Some(name) = cache.get(current) {
+            return name.clone();
+        }
+
+        if let Ok(content) = std::fs::read_to_string(current.join("package.json")) {
+            let name = serde_json::from_str::<serde_json::Value>(&content)
+                .ok()
+                .and_then(|json| json.get("name")?.as_str().map(str::to_string))
+                .unwrap_or_else(|| current.display().to_string());
+            cache.insert(current.to_path_buf(), name.clone());
+            return name;
+        }
+
+        dir = current.parent();
+    }
+
+    "(no package)".to_string()
+}
+
+pub struct JsonReporter {
+    /// Serialize counts per rule instead of the full report
+    pub summary: bool,
+}
+
+/// The report, followed by every finding with its rule's code
+#[derive(Serialize)]
+struct FullReport<'a> {
+    #[serde(flatten)]
+    report: &'a AnalysisReport,
+    findings: Vec<Finding>,
+}
 
 impl Reporter for JsonReporter {
-    fn report(&self, report: &AnalysisReport) -> io::Result<()> {
-        let json = serde_json::to_string_pretty(report)?;
-        println!("{}", json);
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()> {
+        let json = if self.summary {
+            let mut summary: serde_json::Map<String, serde_json::Value> = report
+                .counts()
+                .into_iter()
+                .map(|(rule, count)| (rule.to_string(), count.into()))
+                .collect();
+            summary.insert("total".to_string(), report.total().into());
+            summary.insert("savings".to_string(), serde_json::to_value(report.savings())?);
+            if !report.packages.is_empty() {
+                let packages: serde_json::Map<String, serde_json::Value> = report
+                    .packages
+                    .iter()
+                    .map(|package| (package.name.clone(), package.total.into()))
+                    .collect();
+                summary.insert("packages".to_string(), packages.into());
+            }
+            serde_json::to_string_pretty(&summary)?
+        } else {
+            serde_json::to_string_pretty(&FullReport {
+                report,
+                findings: report.findings(),
+            })?
+        };
+        writeln!(out, "{}", json)
+    }
+}
+
+/// One row per finding, for spreadsheets and ad-hoc triage
+pub struct CsvReporter;
+
+impl Reporter for CsvReporter {
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "rule,path,symbol,line,column,code")?;
+
+        for finding in report.findings() {
+            let optional = |value: Option<usize>| value.map(|v| v.to_string()).unwrap_or_default();
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                finding.rule,
+                csv_field(&finding.path.to_string_lossy()),
+                csv_field(finding.symbol.as_deref().unwrap_or("")),
+                optional(finding.line),
+                optional(finding.column),
+                finding.code
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Quote a CSV field when it contains a delimiter, quote, or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Grep-friendly `path:line:col code rule message` lines, one per finding
+pub struct CompactReporter;
+
+impl Reporter for CompactReporter {
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()> {
+        for finding in report.findings() {
+            writeln!(
+                out,
+                "{}:{}:{} {} {} {}",
+                finding.path.display(),
+                finding.line.unwrap_or(1),
+                finding.column.unwrap_or(1),
+                finding.code,
+                finding.rule,
+                finding.message()
+            )?;
+        }
+
         Ok(())
     }
 }
+
+/// ts-prune's `path:line - name` lines for unused exports and types, with
+/// ` (used in module)` when the file itself uses the export
+pub struct TsPruneReporter {
+    /// Paths are printed relative to this directory
+    pub root: PathBuf,
+}
+
+impl Reporter for TsPruneReporter {
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()> {
+        let mut exports: Vec<&UnusedExport> = report
+            .unused_exports
+            .iter()
+            .chain(&report.unused_types)
+            .chain(&report.module_only_exports)
+            .collect();
+        exports.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+        for export in exports {
+            let path = export.file.strip_prefix(&self.root).unwrap_or(&export.file);
+            write!(out, "{}:{} - {}", path.display(), export.line, export.name)?;
+            if export.used_in_module {
+                write!(out, " (used in module)")?;
+            }
+            writeln!(out)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// ESLint's "stylish" output: findings grouped under their file as
+/// `line:col  warning  message  rule` rows, then a problem count
+pub struct EslintReporter {
+    /// Underline file names and color severities as ESLint does in a terminal
+    pub color: bool,
+}
+
+impl EslintReporter {
+    fn paint(&self, code: &str, text: &str) -> String {
+        match self.color {
+            true => format!("\x1b[{}m{}\x1b[0m", code, text),
+            false => text.to_string(),
+        }
+    }
+}
+
+impl Reporter for EslintReporter {
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()> {
+        let findings = report.findings();
+        if findings.is_empty() {
+            return Ok(());
+        }
+
+        let mut by_file: BTreeMap<&Path, Vec<&Finding>> = BTreeMap::new();
+        for finding in &findings {
+            by_file.entry(&finding.path).or_default().push(finding);
+        }
+
+        for (path, mut findings) in by_file {
+            findings.sort_by_key(|finding| (finding.line, finding.column));
+            // ESLint places messages without a location at 0:0
+            let rows: Vec<(String, String)> = findings
+                .iter()
+                .map(|finding| {
+                    let location = format!("{}:{}", finding.line.unwrap_or(0), finding.column.unwrap_or(0));
+                    (location, finding.message())
+                })
+                .collect();
+            let location_width = rows.iter().map(|(location, _)| location.len()).max().unwrap_or(0);
+            let message_width = rows.iter().map(|(_, message)| message.chars().count()).max().unwrap_or(0);
+
+            writeln!(out)?;
+            writeln!(out, "{}", self.paint("4", &path.display().to_string()))?;
+            for (finding, (location, message)) in findings.iter().zip(&rows) {
+                writeln!(
+                    out,
+                    "  {}  {}  {}{}  {}",
+                    self.paint("2", &format!("{:<location_width$}", location)),
+                    self.paint("33", "warning"),
+                    message,
+                    " ".repeat(message_width - message.chars().count()),
+                    self.paint("2", finding.rule)
+                )?;
+            }
+        }
+
+        let problems = findings.len();
+        let summary = format!(
+            "✖ {} problem{} (0 errors, {} warning{})",
+            problems,
+            if problems == 1 { "" } else { "s" },
+            problems,
+            if problems == 1 { "" } else { "s" }
+        );
+        writeln!(out)?;
+        writeln!(out, "{}", self.paint("1;33", &summary))?;
+        writeln!(out)
+    }
+}
+
+/// SARIF 2.1.0, which code scanning services such as GitHub's read: every
+/// rule under its code, and one warning result per finding
+pub struct SarifReporter {
+    /// Paths are given relative to this directory, the `%SRCROOT%` base
+    pub root: PathBuf,
+}
+
+impl Reporter for SarifReporter {
+    fn report(&self, report: &AnalysisReport, out: &mut dyn Write) -> io::Result<()> {
+        let rules: Vec<serde_json::Value> = RULE_CODES
+            .iter()
+            .zip(RULE_NAMES)
+            .map(|(code, name)| serde_json::json!({ "id": code, "name": name }))
+            .collect();
+
+        let results: Vec<serde_json::Value> = report
+            .findings()
+            .iter()
+            .map(|finding| {
+                let path = finding.path.strip_prefix(&self.root).unwrap_or(&finding.path);
+                let mut location = serde_json::json!({
+                    "artifactLocation": {
+                        "uri": path.to_string_lossy().replace('\\', "/"),
+                        "uriBaseId": "%SRCROOT%",
+                    }
+                });
+                if let Some(line) = finding.line {
+                    location["region"] = serde_json::json!({
+                        "startLine": line,
+                        "startColumn": finding.column.unwrap_or(1),
+                    });
+                }
+                serde_json::json!({
+                    "ruleId": finding.code,
+                    "ruleIndex": RULE_CODES.iter().position(|code| *code == finding.code),
+                    "level": "warning",
+                    "message": { "text": finding.message() },
+                    "locations": [{ "physicalLocation": location }],
+                })
+            })
+            .collect();
+
+        let sarif = serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "sweepr",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    }
+                },
+                "results": results,
+            }],
+        });
+        writeln!(out, "{}", serde_json::to_string_pretty(&sarif)?)
+    }
+}
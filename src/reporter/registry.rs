@@ -0,0 +1,111 @@
+use super::{
+    CliReporter, CompactReporter, CsvReporter, EslintReporter, GroupBy, HtmlReporter, JsonReporter,
+    Reporter, SarifReporter, TsPruneReporter,
+};
+use crate::fixer::Fixer;
+use crate::graph::FileImportGraph;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Everything a reporter may need besides the analysis report itself
+pub struct ReportContext<'a> {
+    pub root: &'a Path,
+    pub file_graph: &'a FileImportGraph,
+    pub options: &'a ReportOptions,
+}
+
+/// Presentation options chosen on the command line
+#[derive(Debug, Clone, Default)]
+pub struct ReportOptions {
+    pub quiet: bool,
+    pub color: bool,
+    pub show_fixes: bool,
+    pub allow_unsafe: bool,
+    pub group_by: Option<GroupBy>,
+    pub summary: bool,
+}
+
+pub type ReporterFactory = Box<dyn Fn(&ReportContext) -> Box<dyn Reporter> + Send + Sync>;
+
+/// Report formats keyed by the name used with `--format`
+pub struct ReporterRegistry {
+    factories: BTreeMap<String, ReporterFactory>,
+}
+
+impl ReporterRegistry {
+    /// A registry without any formats
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Register a format, replacing any existing format with the same name
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&ReportContext) -> Box<dyn Reporter> + Send + Sync + 'static,
+    {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Build the reporter registered under `name`
+    pub fn create(&self, name: &str, context: &ReportContext) -> Option<Box<dyn Reporter>> {
+        self.factories.get(name).map(|factory| factory(context))
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Registered format names in sorted order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+}
+
+impl Default for ReporterRegistry {
+    /// A registry with every built-in format
+    fn default() -> Self {
+        let mut registry = Self::empty();
+
+        registry.register("cli", |context| {
+            let options = context.options;
+            Box::new(CliReporter {
+                quiet: options.quiet,
+                fixer: options
+                    .show_fixes
+                    .then(|| Fixer::new(options.allow_unsafe)),
+                color: options.color,
+                group_by: options.group_by,
+                summary: options.summary,
+            })
+        });
+        registry.register("json", |context| {
+            Box::new(JsonReporter {
+                summary: context.options.summary,
+            })
+        });
+        registry.register("html", |context| {
+            Box::new(HtmlReporter::new(context.root, context.file_graph))
+        });
+        registry.register("csv", |_| Box::new(CsvReporter));
+        registry.register("compact", |_| Box::new(CompactReporter));
+        registry.register("ts-prune", |context| {
+            Box::new(TsPruneReporter {
+                root: context.root.to_path_buf(),
+            })
+        });
+        registry.register("eslint", |context| {
+            Box::new(EslintReporter {
+                color: context.options.color,
+            })
+        });
+        registry.register("sarif", |context| {
+            Box::new(SarifReporter {
+                root: context.root.to_path_buf(),
+            })
+        });
+
+        registry
+    }
+}
@@ -0,0 +1,120 @@
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf};
+
+/// Extensions tried, in order, for extensionless specifiers and index files
+const EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "js", "jsx", "mjs", "cjs", "mts", "cts"];
+
+/// Node.js built-in modules, importable with or without the `node:` prefix
+const NODE_BUILTINS: &[&str] = &[
+    "assert", "async_hooks", "buffer", "child_process", "cluster", "console", "constants",
+    "crypto", "dgram", "diagnostics_channel", "dns", "domain", "events", "fs", "http", "http2",
+    "https", "inspector", "module", "net", "os", "path", "perf_hooks", "process", "punycode",
+    "querystring", "readline", "repl", "stream", "string_decoder", "sys", "timers", "tls",
+    "trace_events", "tty", "url", "util", "v8", "vm", "wasi", "worker_threads", "zlib",
+];
+
+/// Whether a specifier points into the project rather than at a package
+pub fn is_relative(specifier: &str) -> bool {
+    specifier.starts_with('.') || specifier.starts_with('/')
+}
+
+/// The npm package a bare specifier imports, e.g. `@scope/pkg` for `@scope/pkg/sub`
+///
+/// `None` for relative paths, Node built-ins, URLs and other `scheme:`
+/// specifiers, and aliases that can't name a package such as `#internal`,
+/// `~/lib`, or `@/components`.
+pub fn package_name(specifier: &str) -> Option<&str> {
+    if is_relative(specifier) || specifier.contains(':') {
+        return None;
+    }
+
+    let mut segments = specifier.split('/');
+    let first = segments.next()?;
+    let name_len = match first.strip_prefix('@') {
+        Some(scope) => {
+            let name = segments.next()?;
+            if scope.is_empty() || name.is_empty() {
+                return None;
+            }
+            first.len() + 1 + name.len()
+        }
+        None => first.len(),
+    };
+
+    let valid_start = first
+        .chars()
+        .next()
+        .is_some_and(|c| c == '@' || c.is_ascii_alphanumeric());
+    if !valid_start || NODE_BUILTINS.contains(&first) {
+        return None;
+    }
+
+    Some(&specifier[..name_len])
+}
+
+/// Resolve a relative specifier imported by `from` to an existing file
+///
+/// Follows Node and TypeScript conventions: the exact path, a `.js`-style
+/// extension standing in for its TypeScript source, appended extensions, and
+/// `index` files of directories.
+pub fn resolve_relative(from: &Path, specifier: &str) -> Option<PathBuf> {
+    let base = normalize(&from.parent()?.join(specifier));
+
+    if base.is_file() {
+        return Some(base);
+    }
+
+    let ts_sources: &[&str] = match base.extension().and_then(|ext| ext.to_str()) {
+        Some("js") => &["ts", "tsx"],
+        Some("jsx") => &["tsx"],
+        Some("mjs") => &["mts"],
+        Some("cjs") => &["cts"],
+        _ => &[],
+    };
+    if let Some(found) = ts_sources
+        .iter()
+        .map(|ext| base.with_extension(ext))
+        .find(|candidate| candidate.is_file())
+    {
+        return Some(found);
+    }
+
+    EXTENSIONS
+        .iter()
+        .map(|ext| with_appended_extension(&base, ext))
+        .chain(EXTENSIONS.iter().map(|ext| base.join(format!("index.{}", ext))))
+        .find(|candidate| candidate.is_file())
+}
+
+/// The path an unresolvable specifier would point at, for reporting
+pub fn unresolved_target(from: &Path, specifier: &str) -> PathBuf {
+    match from.parent() {
+        Some(dir) => normalize(&dir.join(specifier)),
+        None => PathBuf::from(specifier),
+    }
+}
+
+/// Lexically remove `.` and `..` components
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// `file.service` + `ts` is `file.service.ts`, where `with_extension` would drop `.service`
+fn with_appended_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".");
+    name.push(ext);
+    PathBuf::from(name)
+}
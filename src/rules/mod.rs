@@ -1,97 +1,1629 @@
-use crate::graph::{DependencyGraph, FileImportGraph, SymbolUsageGraph};
+use crate::config::RulesConfig;
+use crate::frameworks::angular;
+use crate::graph::{
+    DependencyGraph, DependencyKind, FileId, FileImportGraph, FileNode, Symbol, SymbolId, SymbolUsageGraph,
+    UsedExports, Visibility, EXPORT_ASSIGNMENT,
+};
+use crate::parser::ParseFailure;
+use crate::scanner::SkippedFile;
+use crate::source::LineIndex;
+use crate::timing::{self, PhaseTiming};
+use crate::workspaces::WorkspacePackage;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnusedDependency {
     pub name: String,
     pub version: String,
+    /// package.json declaring the dependency
+    pub manifest: PathBuf,
+    /// Field of the manifest declaring it
+    pub kind: DependencyKind,
+}
+
+/// Import of a package that no package.json above the importing file declares
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlistedDependency {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the import specifier in the source file
+    pub span: (usize, usize),
+}
+
+/// Relative import whose specifier matches no file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnresolvedImport {
+    pub specifier: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the specifier in the importing file
+    pub span: (usize, usize),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnusedExport {
     pub name: String,
     pub file: PathBuf,
+    /// 1-based line and column, in characters, of the exported name
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the exported name in the source file
+    pub span: (usize, usize),
+    /// Referenced within its own file, so only the `export` keyword is unused
+    #[serde(default)]
+    pub used_in_module: bool,
+    /// Lines and bytes of the declaration, removed with the export
+    #[serde(default)]
+    pub lines: usize,
+    #[serde(default)]
+    pub bytes: usize,
+}
+
+/// A `#name` or `private` class member that its class never reads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedPrivateMember {
+    /// The member's name, with the `#` of a private name
+    pub name: String,
+    /// Name of the declaring class, unless it is anonymous
+    pub class: Option<String>,
+    pub file: PathBuf,
     pub line: usize,
     pub column: usize,
+    /// Byte offsets of the member's name in the source file
+    pub span: (usize, usize),
+}
+
+/// A local variable, function, or class that nothing reads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedLocal {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the declared name in the source file
+    pub span: (usize, usize),
+}
+
+/// An import binding nothing in its file references
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedImport {
+    pub name: String,
+    /// The module specifier imported from
+    pub source: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the import specifier in the source file
+    pub span: (usize, usize),
+    /// Byte offsets of the whole declaration, when this is its only binding
+    pub statement: Option<(usize, usize)>,
+}
+
+/// Export imported only by test files, directly or through re-exports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestOnlyExport {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the exported name in the source file
+    pub span: (usize, usize),
+    /// Test files importing the export
+    pub tests: Vec<PathBuf>,
+}
+
+/// Export imported only by Storybook stories, directly or through re-exports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoryOnlyExport {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the exported name in the source file
+    pub span: (usize, usize),
+    /// Stories importing the export
+    pub stories: Vec<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnusedFile {
     pub path: PathBuf,
+    /// Declared with side effects by its package.json, so it may be imported
+    /// for those alone, e.g. by a bundler
+    #[serde(default)]
+    pub has_side_effects: bool,
+    /// Lines and bytes of the file
+    #[serde(default)]
+    pub lines: usize,
+    #[serde(default)]
+    pub bytes: usize,
+}
+
+/// Code removing findings would delete, as an estimate to prioritize by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Savings {
+    pub lines: usize,
+    pub bytes: usize,
+}
+
+/// A reachable file none of whose exports are used, and which nothing
+/// imports for its side effects alone, so it can be deleted along with the
+/// imports of it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovableFile {
+    pub path: PathBuf,
+    /// Number of exports, all of them unused
+    pub exports: usize,
+    /// Files whose imports of it go when it does
+    pub importers: Vec<PathBuf>,
+}
+
+/// Unused files importing each other, which can only be removed together
+///
+/// Every file of the chain is also reported in `unused_files`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadChain {
+    /// Files of the chain that no other file imports
+    pub roots: Vec<PathBuf>,
+    /// Every file of the chain, roots included
+    pub files: Vec<PathBuf>,
 }
 
+/// A name exported from more than one file
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateExport {
+    pub name: String,
+    pub locations: Vec<ExportLocation>,
+}
+
+/// One file declaring a duplicated export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportLocation {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+    /// Byte offsets of the exported name in the source file
+    pub span: (usize, usize),
+    /// Number of imports using this file's export, directly or through re-exports
+    pub usages: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnalysisReport {
     pub unused_dependencies: Vec<UnusedDependency>,
     pub unused_exports: Vec<UnusedExport>,
+    /// Unused interfaces, type aliases, and type-only exports
+    #[serde(default)]
+    pub unused_types: Vec<UnusedExport>,
+    /// Exports only their own file uses, which just need the `export` dropped
+    #[serde(default)]
+    pub module_only_exports: Vec<UnusedExport>,
     pub unused_files: Vec<UnusedFile>,
+    /// Groups of unused files connected by imports
+    #[serde(default)]
+    pub dead_chains: Vec<DeadChain>,
+    #[serde(default)]
+    pub duplicate_exports: Vec<DuplicateExport>,
+    #[serde(default)]
+    pub unlisted_dependencies: Vec<UnlistedDependency>,
+    #[serde(default)]
+    pub unresolved_imports: Vec<UnresolvedImport>,
+    #[serde(default)]
+    pub test_only_exports: Vec<TestOnlyExport>,
+    /// Exports only stories import, reported when `story_only_exports` is on
+    #[serde(default)]
+    pub story_only_exports: Vec<StoryOnlyExport>,
+    /// Unused `peerDependencies`
+    #[serde(default)]
+    pub unused_peer_dependencies: Vec<UnusedDependency>,
+    /// Unused `@types` packages without their runtime package
+    #[serde(default)]
+    pub orphaned_types: Vec<UnusedDependency>,
+    /// `dependencies` only ever imported with `import type`
+    #[serde(default)]
+    pub type_only_dependencies: Vec<UnusedDependency>,
+    /// Private class members their class never reads
+    #[serde(default)]
+    pub unused_private_members: Vec<UnusedPrivateMember>,
+    /// Local bindings nothing reads, reported when `unused_locals` is on
+    #[serde(default)]
+    pub unused_locals: Vec<UnusedLocal>,
+    /// Import bindings nothing in their file references
+    #[serde(default)]
+    pub unused_imports: Vec<UnusedImport>,
+    /// Reachable files with only unused exports, apart from `unused_files`
+    #[serde(default)]
+    pub removable_files: Vec<RemovableFile>,
+    /// Files left out of the analysis, reported so their absence is visible
+    #[serde(default)]
+    pub skipped_files: Vec<SkippedFile>,
+    /// Files that failed to parse, so the rest were analyzed without them
+    #[serde(default)]
+    pub parse_failures: Vec<ParseFailure>,
+    /// How long each phase took; `analyze` records the rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub timings: Vec<PhaseTiming>,
+    /// Findings per workspace package, set by `attribute_packages`
+    #[serde(skip_deserializing, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<PackageReport>,
+    /// Order findings are in, set by `sort`
+    #[serde(skip)]
+    pub sort_by: SortBy,
+}
+
+/// Order of the findings in a report
+#[derive(clap::ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortBy {
+    /// File path, then position in the file, across rules
+    Path,
+    /// Name of the export, dependency, or other symbol, across rules
+    Name,
+    /// Lines and bytes removable, largest first, within each rule
+    Size,
+    /// Rule, then file path and position
+    #[default]
+    Rule,
+}
+
+/// Findings of one workspace package and their totals
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageReport {
+    pub name: String,
+    pub dir: PathBuf,
+    pub total: usize,
+    /// Number of findings per rule, leaving out rules without any
+    pub counts: BTreeMap<&'static str, usize>,
+    pub findings: Vec<Finding>,
+}
+
+/// Identifiers of every rule, as used in findings and on the command line
+pub const RULE_NAMES: [&str; 17] = [
+    "unused-deps",
+    "unused-exports",
+    "unused-types",
+    "unused-files",
+    "duplicate-exports",
+    "unlisted-deps",
+    "unresolved-imports",
+    "test-only-exports",
+    "orphaned-types",
+    "unused-peers",
+    "story-only-exports",
+    "type-only-deps",
+    "module-only-exports",
+    "unused-private-members",
+    "unused-locals",
+    "unused-imports",
+    "removable-files",
+];
+
+/// Stable code of every rule, in `RULE_NAMES` order, e.g. `SWP001` for
+/// `unused-deps`; a new rule takes the next number and codes are never reused
+pub const RULE_CODES: [&str; 17] = [
+    "SWP001",
+    "SWP002",
+    "SWP003",
+    "SWP004",
+    "SWP005",
+    "SWP006",
+    "SWP007",
+    "SWP008",
+    "SWP009",
+    "SWP010",
+    "SWP011",
+    "SWP012",
+    "SWP013",
+    "SWP014",
+    "SWP015",
+    "SWP016",
+    "SWP017",
+];
+
+/// The code of a rule, e.g. `SWP002` for `unused-exports`
+pub fn rule_code(rule: &str) -> &'static str {
+    RULE_NAMES
+        .iter()
+        .position(|name| *name == rule)
+        .map_or("", |index| RULE_CODES[index])
+}
+
+/// The rule a code or name, e.g. `SWP002` or `unused-exports`, stands for
+pub fn find_rule(code_or_name: &str) -> Option<&'static str> {
+    RULE_NAMES
+        .iter()
+        .zip(RULE_CODES)
+        .find(|(name, code)| **name == code_or_name || *code == code_or_name)
+        .map(|(name, _)| *name)
+}
+
+/// Package name given to findings outside every workspace package
+const NO_PACKAGE: &str = "(no package)";
+
+/// A single finding flattened out of the report, independent of its category
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub rule: &'static str,
+    /// The rule's stable code, e.g. `SWP002`
+    pub code: &'static str,
+    pub path: PathBuf,
+    pub symbol: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+}
+
+impl AnalysisReport {
+    /// Keep only findings located in files, or package.json manifests, that
+    /// `keep` accepts
+    ///
+    /// A duplicated name stays when any of its files is kept, and a dead
+    /// chain when any of its files is.
+    pub fn retain_paths(&mut self, keep: impl Fn(&Path) -> bool) {
+        self.unused_dependencies.retain(|dep| keep(&dep.manifest));
+        self.unused_peer_dependencies.retain(|dep| keep(&dep.manifest));
+        self.orphaned_types.retain(|dep| keep(&dep.manifest));
+        self.type_only_dependencies.retain(|dep| keep(&dep.manifest));
+        self.unused_exports.retain(|export| keep(&export.file));
+        self.unused_types.retain(|export| keep(&export.file));
+        self.module_only_exports.retain(|export| keep(&export.file));
+        self.unused_private_members.retain(|member| keep(&member.file));
+        self.unused_locals.retain(|local| keep(&local.file));
+        self.unused_imports.retain(|import| keep(&import.file));
+        self.removable_files.retain(|file| keep(&file.path));
+        self.unused_files.retain(|file| keep(&file.path));
+        self.dead_chains.retain(|chain| chain.files.iter().any(|file| keep(file)));
+        self.duplicate_exports
+            .retain(|duplicate| duplicate.locations.iter().any(|location| keep(&location.file)));
+        self.unlisted_dependencies.retain(|dep| keep(&dep.file));
+        self.unresolved_imports.retain(|import| keep(&import.file));
+        self.test_only_exports.retain(|export| keep(&export.file));
+        self.story_only_exports.retain(|export| keep(&export.file));
+        self.skipped_files.retain(|skipped| keep(&skipped.path));
+        self.parse_failures.retain(|failure| keep(&failure.path));
+    }
+
+    /// Keep only findings `keep` accepts, given each one's rule code, path,
+    /// name, and line, as in `findings`
+    ///
+    /// A duplicated name keeps the locations accepted, and goes when none
+    /// is; a dead chain stays when any of its files is still reported.
+    pub fn retain_findings(&mut self, mut keep: impl FnMut(&str, &Path, Option<&str>, Option<usize>) -> bool) {
+        self.unused_dependencies
+            .retain(|dep| keep(RULE_CODES[0], &dep.manifest, Some(&dep.name), None));
+        self.unused_exports
+            .retain(|export| keep(RULE_CODES[1], &export.file, Some(&export.name), Some(export.line)));
+        self.unused_types
+            .retain(|export| keep(RULE_CODES[2], &export.file, Some(&export.name), Some(export.line)));
+        self.unused_files.retain(|file| keep(RULE_CODES[3], &file.path, None, None));
+        for duplicate in &mut self.duplicate_exports {
+            let name = &duplicate.name;
+            duplicate
+                .locations
+                .retain(|location| keep(RULE_CODES[4], &location.file, Some(name), Some(location.line)));
+        }
+        self.duplicate_exports.retain(|duplicate| !duplicate.locations.is_empty());
+        self.unlisted_dependencies
+            .retain(|dep| keep(RULE_CODES[5], &dep.file, Some(&dep.name), Some(dep.line)));
+        self.unresolved_imports
+            .retain(|import| keep(RULE_CODES[6], &import.file, Some(&import.specifier), Some(import.line)));
+        self.test_only_exports
+            .retain(|export| keep(RULE_CODES[7], &export.file, Some(&export.name), Some(export.line)));
+        self.orphaned_types
+            .retain(|dep| keep(RULE_CODES[8], &dep.manifest, Some(&dep.name), None));
+        self.unused_peer_dependencies
+            .retain(|dep| keep(RULE_CODES[9], &dep.manifest, Some(&dep.name), None));
+        self.story_only_exports
+            .retain(|export| keep(RULE_CODES[10], &export.file, Some(&export.name), Some(export.line)));
+        self.type_only_dependencies
+            .retain(|dep| keep(RULE_CODES[11], &dep.manifest, Some(&dep.name), None));
+        self.module_only_exports
+            .retain(|export| keep(RULE_CODES[12], &export.file, Some(&export.name), Some(export.line)));
+        self.unused_private_members
+            .retain(|member| keep(RULE_CODES[13], &member.file, Some(&member.name), Some(member.line)));
+        self.unused_locals
+            .retain(|local| keep(RULE_CODES[14], &local.file, Some(&local.name), Some(local.line)));
+        self.unused_imports
+            .retain(|import| keep(RULE_CODES[15], &import.file, Some(&import.name), Some(import.line)));
+        self.removable_files.retain(|file| keep(RULE_CODES[16], &file.path, None, None));
+
+        let unused_files = &self.unused_files;
+        self.dead_chains
+            .retain(|chain| chain.files.iter().any(|file| unused_files.iter().any(|unused| &unused.path == file)));
+    }
+
+    /// Attribute every finding to the package whose directory contains it,
+    /// the innermost one for nested packages
+    ///
+    /// Every package gets an entry, in the order given, even without
+    /// findings; ones outside all packages go to a trailing `(no package)`.
+    pub fn attribute_packages(&mut self, packages: &[WorkspacePackage]) {
+        let mut reports: Vec<PackageReport> = packages
+            .iter()
+            .map(|package| PackageReport {
+                name: package
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| package.dir.display().to_string()),
+                dir: package.dir.clone(),
+                total: 0,
+                counts: BTreeMap::new(),
+                findings: Vec::new(),
+            })
+            .collect();
+
+        for finding in self.findings() {
+            let owner = packages
+                .iter()
+                .enumerate()
+                .filter(|(_, package)| finding.path.starts_with(&package.dir))
+                .max_by_key(|(_, package)| package.dir.components().count())
+                .map(|(index, _)| index);
+            let index = owner.unwrap_or_else(|| {
+                if reports.last().is_none_or(|last| last.name != NO_PACKAGE) {
+                    reports.push(PackageReport {
+                        name: NO_PACKAGE.to_string(),
+                        dir: PathBuf::new(),
+                        total: 0,
+                        counts: BTreeMap::new(),
+                        findings: Vec::new(),
+                    });
+                }
+                reports.len() - 1
+            });
+
+            let report = &mut reports[index];
+            report.total += 1;
+            *report.counts.entry(finding.rule).or_default() += 1;
+            report.findings.push(finding);
+        }
+
+        self.packages = reports;
+    }
+
+    /// Keep only dependency findings for packages that `keep` accepts
+    pub fn retain_dependencies(&mut self, keep: impl Fn(&str) -> bool) {
+        self.unused_dependencies.retain(|dep| keep(&dep.name));
+        self.unused_peer_dependencies.retain(|dep| keep(&dep.name));
+        self.orphaned_types.retain(|dep| keep(&dep.name));
+        self.type_only_dependencies.retain(|dep| keep(&dep.name));
+        self.unlisted_dependencies.retain(|dep| keep(&dep.name));
+    }
+
+    /// Keep only findings `before`, the report of an earlier version of the
+    /// project under `before_root`, doesn't have
+    ///
+    /// Findings match by rule, path relative to their root, and name, so
+    /// code moving within a file doesn't make its findings new. A dead chain
+    /// stays when any of its files is still reported.
+    pub fn retain_introduced(&mut self, root: &Path, before: &AnalysisReport, before_root: &Path) {
+        fn dependency(dep: &UnusedDependency) -> (&Path, &str) {
+            (&dep.manifest, &dep.name)
+        }
+        fn export(export: &UnusedExport) -> (&Path, &str) {
+            (&export.file, &export.name)
+        }
+        let roots = (root, before_root);
+
+        retain_introduced(&mut self.unused_dependencies, &before.unused_dependencies, roots, dependency);
+        retain_introduced(&mut self.unused_peer_dependencies, &before.unused_peer_dependencies, roots, dependency);
+        retain_introduced(&mut self.orphaned_types, &before.orphaned_types, roots, dependency);
+        retain_introduced(&mut self.type_only_dependencies, &before.type_only_dependencies, roots, dependency);
+        retain_introduced(&mut self.unused_exports, &before.unused_exports, roots, export);
+        retain_introduced(&mut self.unused_types, &before.unused_types, roots, export);
+        retain_introduced(&mut self.module_only_exports, &before.module_only_exports, roots, export);
+        retain_introduced(&mut self.test_only_exports, &before.test_only_exports, roots, |export| {
+            (&export.file, &export.name)
+        });
+        retain_introduced(&mut self.story_only_exports, &before.story_only_exports, roots, |export| {
+            (&export.file, &export.name)
+        });
+        retain_introduced(&mut self.unused_private_members, &before.unused_private_members, roots, |member| {
+            (&member.file, &member.name)
+        });
+        retain_introduced(&mut self.unused_locals, &before.unused_locals, roots, |local| {
+            (&local.file, &local.name)
+        });
+        retain_introduced(&mut self.unused_imports, &before.unused_imports, roots, |import| {
+            (&import.file, &import.name)
+        });
+        retain_introduced(&mut self.unused_files, &before.unused_files, roots, |file| (&file.path, ""));
+        retain_introduced(&mut self.removable_files, &before.removable_files, roots, |file| (&file.path, ""));
+        retain_introduced(&mut self.duplicate_exports, &before.duplicate_exports, roots, |duplicate| {
+            (Path::new(""), &duplicate.name)
+        });
+        retain_introduced(&mut self.unlisted_dependencies, &before.unlisted_dependencies, roots, |dep| {
+            (&dep.file, &dep.name)
+        });
+        retain_introduced(&mut self.unresolved_imports, &before.unresolved_imports, roots, |import| {
+            (&import.file, &import.specifier)
+        });
+
+        let unused_files = &self.unused_files;
+        self.dead_chains
+            .retain(|chain| chain.files.iter().any(|file| unused_files.iter().any(|unused| &unused.path == file)));
+    }
+
+    /// Number of findings per rule, in `RULE_NAMES` order
+    pub fn counts(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            (RULE_NAMES[0], self.unused_dependencies.len()),
+            (RULE_NAMES[1], self.unused_exports.len()),
+            (RULE_NAMES[2], self.unused_types.len()),
+            (RULE_NAMES[3], self.unused_files.len()),
+            (RULE_NAMES[4], self.duplicate_exports.len()),
+            (RULE_NAMES[5], self.unlisted_dependencies.len()),
+            (RULE_NAMES[6], self.unresolved_imports.len()),
+            (RULE_NAMES[7], self.test_only_exports.len()),
+            (RULE_NAMES[8], self.orphaned_types.len()),
+            (RULE_NAMES[9], self.unused_peer_dependencies.len()),
+            (RULE_NAMES[10], self.story_only_exports.len()),
+            (RULE_NAMES[11], self.type_only_dependencies.len()),
+            (RULE_NAMES[12], self.module_only_exports.len()),
+            (RULE_NAMES[13], self.unused_private_members.len()),
+            (RULE_NAMES[14], self.unused_locals.len()),
+            (RULE_NAMES[15], self.unused_imports.len()),
+            (RULE_NAMES[16], self.removable_files.len()),
+        ]
+    }
+
+    /// Number of findings across all rules, counting each duplicated name once
+    pub fn total(&self) -> usize {
+        self.counts().iter().map(|(_, count)| count).sum()
+    }
+
+    /// Lines and bytes of unused files and of the declarations of unused
+    /// exports and types; module-only exports keep theirs
+    pub fn savings(&self) -> Savings {
+        let exports = self.unused_exports.iter().chain(&self.unused_types);
+        let sizes = exports
+            .map(|export| (export.lines, export.bytes))
+            .chain(self.unused_files.iter().map(|file| (file.lines, file.bytes)));
+        sizes.fold(Savings::default(), |total, (lines, bytes)| Savings {
+            lines: total.lines + lines,
+            bytes: total.bytes + bytes,
+        })
+    }
+
+    /// Order every category of findings by `by`, which `findings` follows
+    /// across categories too when sorting by path or name
+    pub fn sort(&mut self, by: SortBy) {
+        self.sort_by = by;
+        sort_list(&mut self.unused_dependencies, by, |dep| (&dep.manifest, &dep.name, 0, 0));
+        sort_list(&mut self.unused_exports, by, |export| (&export.file, &export.name, export.span.0, export.bytes));
+        sort_list(&mut self.unused_types, by, |export| (&export.file, &export.name, export.span.0, export.bytes));
+        sort_list(&mut self.module_only_exports, by, |export| (&export.file, &export.name, export.span.0, 0));
+        sort_list(&mut self.unused_files, by, |file| (&file.path, file_name(&file.path), 0, file.bytes));
+        sort_list(&mut self.dead_chains, by, |chain| (&chain.files[0], file_name(&chain.files[0]), 0, 0));
+        sort_list(&mut self.duplicate_exports, by, |duplicate| {
+            (&duplicate.locations[0].file, &duplicate.name, duplicate.locations[0].span.0, 0)
+        });
+        sort_list(&mut self.unlisted_dependencies, by, |dep| (&dep.file, &dep.name, dep.span.0, 0));
+        sort_list(&mut self.unresolved_imports, by, |import| (&import.file, &import.specifier, import.span.0, 0));
+        sort_list(&mut self.test_only_exports, by, |export| (&export.file, &export.name, export.span.0, 0));
+        sort_list(&mut self.story_only_exports, by, |export| (&export.file, &export.name, export.span.0, 0));
+        sort_list(&mut self.unused_peer_dependencies, by, |dep| (&dep.manifest, &dep.name, 0, 0));
+        sort_list(&mut self.orphaned_types, by, |dep| (&dep.manifest, &dep.name, 0, 0));
+        sort_list(&mut self.type_only_dependencies, by, |dep| (&dep.manifest, &dep.name, 0, 0));
+        sort_list(&mut self.unused_private_members, by, |member| (&member.file, &member.name, member.span.0, 0));
+        sort_list(&mut self.unused_locals, by, |local| (&local.file, &local.name, local.span.0, 0));
+        sort_list(&mut self.unused_imports, by, |import| (&import.file, &import.name, import.span.0, 0));
+        sort_list(&mut self.removable_files, by, |file| (&file.path, file_name(&file.path), 0, 0));
+        sort_list(&mut self.skipped_files, by, |file| (&file.path, file_name(&file.path), 0, 0));
+        sort_list(&mut self.parse_failures, by, |file| (&file.path, file_name(&file.path), 0, 0));
+    }
+
+    /// All findings in report order: dependencies, exports, types, files,
+    /// duplicates, with one finding per file declaring a duplicated name,
+    /// unlisted dependencies, unresolved imports, test-only exports, orphaned
+    /// type packages, unused peer dependencies, story-only exports, type-only
+    /// dependencies, module-only exports, unused private members, unused
+    /// locals, unused imports, then removable files
+    ///
+    /// Sorting the report by path or name orders them across categories.
+    pub fn findings(&self) -> Vec<Finding> {
+        let dependencies = self.unused_dependencies.iter().map(|dep| Finding {
+            rule: RULE_NAMES[0],
+            code: RULE_CODES[0],
+            path: dep.manifest.clone(),
+            symbol: Some(dep.name.clone()),
+            line: None,
+            column: None,
+        });
+
+        let exports = self.unused_exports.iter().map(|export| Finding {
+            rule: RULE_NAMES[1],
+            code: RULE_CODES[1],
+            path: export.file.clone(),
+            symbol: Some(export.name.clone()),
+            line: Some(export.line),
+            column: Some(export.column),
+        });
+
+        let types = self.unused_types.iter().map(|export| Finding {
+            rule: RULE_NAMES[2],
+            code: RULE_CODES[2],
+            path: export.file.clone(),
+            symbol: Some(export.name.clone()),
+            line: Some(export.line),
+            column: Some(export.column),
+        });
+
+        let files = self.unused_files.iter().map(|file| Finding {
+            rule: RULE_NAMES[3],
+            code: RULE_CODES[3],
+            path: file.path.clone(),
+            symbol: None,
+            line: None,
+            column: None,
+        });
+
+        let duplicates = self.duplicate_exports.iter().flat_map(|duplicate| {
+            duplicate.locations.iter().map(|location| Finding {
+                rule: RULE_NAMES[4],
+                code: RULE_CODES[4],
+                path: location.file.clone(),
+                symbol: Some(duplicate.name.clone()),
+                line: Some(location.line),
+                column: Some(location.column),
+            })
+        });
+
+        let unlisted = self.unlisted_dependencies.iter().map(|dep| Finding {
+            rule: RULE_NAMES[5],
+            code: RULE_CODES[5],
+            path: dep.file.clone(),
+            symbol: Some(dep.name.clone()),
+            line: Some(dep.line),
+            column: Some(dep.column),
+        });
+
+        let unresolved = self.unresolved_imports.iter().map(|import| Finding {
+            rule: RULE_NAMES[6],
+            code: RULE_CODES[6],
+            path: import.file.clone(),
+            symbol: Some(import.specifier.clone()),
+            line: Some(import.line),
+            column: Some(import.column),
+        });
+
+        let test_only = self.test_only_exports.iter().map(|export| Finding {
+            rule: RULE_NAMES[7],
+            code: RULE_CODES[7],
+            path: export.file.clone(),
+            symbol: Some(export.name.clone()),
+            line: Some(export.line),
+            column: Some(export.column),
+        });
+
+        let orphaned = self.orphaned_types.iter().map(|dep| Finding {
+            rule: RULE_NAMES[8],
+            code: RULE_CODES[8],
+            path: dep.manifest.clone(),
+            symbol: Some(dep.name.clone()),
+            line: None,
+            column: None,
+        });
+
+        let peers = self.unused_peer_dependencies.iter().map(|dep| Finding {
+            rule: RULE_NAMES[9],
+            code: RULE_CODES[9],
+            path: dep.manifest.clone(),
+            symbol: Some(dep.name.clone()),
+            line: None,
+            column: None,
+        });
+
+        let story_only = self.story_only_exports.iter().map(|export| Finding {
+            rule: RULE_NAMES[10],
+            code: RULE_CODES[10],
+            path: export.file.clone(),
+            symbol: Some(export.name.clone()),
+            line: Some(export.line),
+            column: Some(export.column),
+        });
+
+        let type_only = self.type_only_dependencies.iter().map(|dep| Finding {
+            rule: RULE_NAMES[11],
+            code: RULE_CODES[11],
+            path: dep.manifest.clone(),
+            symbol: Some(dep.name.clone()),
+            line: None,
+            column: None,
+        });
+
+        let module_only = self.module_only_exports.iter().map(|export| Finding {
+            rule: RULE_NAMES[12],
+            code: RULE_CODES[12],
+            path: export.file.clone(),
+            symbol: Some(export.name.clone()),
+            line: Some(export.line),
+            column: Some(export.column),
+        });
+
+        let private_members = self.unused_private_members.iter().map(|member| Finding {
+            rule: RULE_NAMES[13],
+            code: RULE_CODES[13],
+            path: member.file.clone(),
+            symbol: Some(member.name.clone()),
+            line: Some(member.line),
+            column: Some(member.column),
+        });
+
+        let locals = self.unused_locals.iter().map(|local| Finding {
+            rule: RULE_NAMES[14],
+            code: RULE_CODES[14],
+            path: local.file.clone(),
+            symbol: Some(local.name.clone()),
+            line: Some(local.line),
+            column: Some(local.column),
+        });
+
+        let imports = self.unused_imports.iter().map(|import| Finding {
+            rule: RULE_NAMES[15],
+            code: RULE_CODES[15],
+            path: import.file.clone(),
+            symbol: Some(import.name.clone()),
+            line: Some(import.line),
+            column: Some(import.column),
+        });
+
+        let removable = self.removable_files.iter().map(|file| Finding {
+            rule: RULE_NAMES[16],
+            code: RULE_CODES[16],
+            path: file.path.clone(),
+            symbol: None,
+            line: None,
+            column: None,
+        });
+
+        let mut findings = dependencies
+            .chain(exports)
+            .chain(types)
+            .chain(files)
+            .chain(duplicates)
+            .chain(unlisted)
+            .chain(unresolved)
+            .chain(test_only)
+            .chain(orphaned)
+            .chain(peers)
+            .chain(story_only)
+            .chain(type_only)
+            .chain(module_only)
+            .chain(private_members)
+            .chain(locals)
+            .chain(imports)
+            .chain(removable)
+            .collect::<Vec<_>>();
+        match self.sort_by {
+            SortBy::Path => findings.sort_by(|a, b| (&a.path, a.line, a.column).cmp(&(&b.path, b.line, b.column))),
+            SortBy::Name => findings.sort_by(|a, b| (&a.symbol, &a.path, a.line).cmp(&(&b.symbol, &b.path, b.line))),
+            SortBy::Size | SortBy::Rule => {}
+        }
+        findings
+    }
+}
+
+/// Sort findings by `by`, given each one's path, name, offset in the file,
+/// and size in bytes
+fn sort_list<T>(list: &mut [T], by: SortBy, key: impl Fn(&T) -> (&Path, &str, usize, usize)) {
+    list.sort_by(|a, b| {
+        let (a, b) = (key(a), key(b));
+        let by_path = (a.0, a.2, a.1).cmp(&(b.0, b.2, b.1));
+        match by {
+            SortBy::Path | SortBy::Rule => by_path,
+            SortBy::Name => a.1.cmp(b.1).then(by_path),
+            SortBy::Size => b.3.cmp(&a.3).then(by_path),
+        }
+    });
+}
+
+/// Keep the items of `list` without a counterpart among `before`, matching
+/// each item at most once by its path, relative to its root in `roots`
+/// (this report's, then the earlier one's), and name
+fn retain_introduced<T>(
+    list: &mut Vec<T>,
+    before: &[T],
+    roots: (&Path, &Path),
+    key: impl Fn(&T) -> (&Path, &str),
+) {
+    let relative = |path: &Path, root: &Path| path.strip_prefix(root).unwrap_or(path).to_path_buf();
+    let mut remaining: HashMap<(PathBuf, String), usize> = HashMap::new();
+    for item in before {
+        let (path, name) = key(item);
+        *remaining.entry((relative(path, roots.1), name.to_string())).or_default() += 1;
+    }
+
+    list.retain(|item| {
+        let (path, name) = key(item);
+        match remaining.get_mut(&(relative(path, roots.0), name.to_string())) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        }
+    });
+}
+
+/// Last component of a path, which files are named by
+fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|name| name.to_str()).unwrap_or_default()
+}
+
+impl Finding {
+    /// Human-readable description of the finding
+    pub fn message(&self) -> String {
+        let symbol = self.symbol.as_deref().unwrap_or_default();
+        match self.rule {
+            "unused-deps" => format!("Unused dependency '{}'", symbol),
+            "unused-exports" => format!("Unused export '{}'", symbol),
+            "unused-types" => format!("Unused type '{}'", symbol),
+            "duplicate-exports" => format!("Duplicate export '{}'", symbol),
+            "unlisted-deps" => format!("Unlisted dependency '{}'", symbol),
+            "unresolved-imports" => format!("Unresolved import '{}'", symbol),
+            "test-only-exports" => format!("Export '{}' is only used by tests", symbol),
+            "orphaned-types" => format!("Orphaned type package '{}'", symbol),
+            "unused-peers" => format!("Unused peer dependency '{}'", symbol),
+            "story-only-exports" => format!("Export '{}' is only used by stories", symbol),
+            "module-only-exports" => format!("Export '{}' is only used in its own module", symbol),
+            "unused-private-members" => format!("Private member '{}' is never read", symbol),
+            "unused-locals" => format!("'{}' is declared but never read", symbol),
+            "unused-imports" => format!("'{}' is imported but never used", symbol),
+            "removable-files" => "File is imported but none of its exports are used".to_string(),
+            "type-only-deps" => format!(
+                "Dependency '{}' is only imported as types; consider moving it to devDependencies",
+                symbol
+            ),
+            _ => "Unused file".to_string(),
+        }
+    }
 }
 
 pub struct RulesEngine;
 
 impl RulesEngine {
+    /// Run the rules enabled in `rules`; disabled ones report nothing
+    ///
+    /// The rules only read the graphs, so each runs as its own task on the
+    /// rayon pool and fills in its own fields of the report. How long each
+    /// took is recorded in the report's `timings`.
     pub fn analyze(
         dependency_graph: &DependencyGraph,
         file_graph: &FileImportGraph,
         symbol_graph: &SymbolUsageGraph,
+        rules: &RulesConfig,
     ) -> AnalysisReport {
-        AnalysisReport {
-            unused_dependencies: Self::find_unused_dependencies(dependency_graph),
-            unused_exports: Self::find_unused_exports(symbol_graph, file_graph),
-            unused_files: Self::find_unused_files(file_graph),
-        }
+        let mut report = AnalysisReport::default();
+        let (rule_timings, duration) = timing::timed(|| {
+            Self::run_rules(&mut report, dependency_graph, file_graph, symbol_graph, rules)
+        });
+        // Findings silenced by a `sweepr-ignore` comment in their file
+        report.retain_findings(|code, path, _, line| {
+            !file_graph
+                .file_id(path)
+                .is_some_and(|file| symbol_graph.is_suppressed(file, code, line))
+        });
+
+        report.timings.push(PhaseTiming::new("rules", duration));
+        report.timings.extend(rule_timings);
+        report.sort(SortBy::default());
+        report
+    }
+
+    /// Fill in `report` from every enabled rule, returning how long each took
+    fn run_rules(
+        report: &mut AnalysisReport,
+        dependency_graph: &DependencyGraph,
+        file_graph: &FileImportGraph,
+        symbol_graph: &SymbolUsageGraph,
+        rules: &RulesConfig,
+    ) -> Vec<PhaseTiming> {
+        // One traversal of the import graph, and of the imports, serves every rule
+        let ((reachable, used), duration) = timing::timed(|| {
+            rayon::join(|| file_graph.reachable_files(), || file_graph.used_exports())
+        });
+        let (reachable, used) = (&reachable, &used);
+        let reachability = PhaseTiming::new("rules/reachability", duration);
+        let timings = Mutex::new(Vec::new());
+        let timed = |rule: &str, run: &mut dyn FnMut()| {
+            let ((), duration) = timing::timed(run);
+            timings.lock().unwrap().push(PhaseTiming::new(format!("rules/{}", rule), duration));
+        };
+
+        let AnalysisReport {
+            unused_dependencies,
+            unused_exports,
+            unused_types,
+            module_only_exports,
+            unused_files,
+            dead_chains,
+            duplicate_exports,
+            unlisted_dependencies,
+            unresolved_imports,
+            test_only_exports,
+            story_only_exports,
+            unused_peer_dependencies,
+            orphaned_types,
+            type_only_dependencies,
+            unused_private_members,
+            unused_locals,
+            unused_imports,
+            removable_files,
+            skipped_files: _,
+            parse_failures: _,
+            timings: _,
+            packages: _,
+            sort_by: _,
+        } = report;
+
+        rayon::scope(|scope| {
+            if rules.unused_deps {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[0], &mut || {
+                        *unused_dependencies = Self::find_unused_dependencies(dependency_graph, file_graph)
+                    })
+                });
+            }
+            if rules.unused_exports || rules.unused_types || rules.module_only_exports || rules.removable_files {
+                scope.spawn(|_| {
+                    timed("unused-exports, unused-types, module-only-exports, removable-files", &mut || {
+                        let unused =
+                            Self::find_unused_exports(symbol_graph, file_graph, reachable, used, rules.jsdoc_tags);
+                        if rules.removable_files {
+                            *removable_files = Self::find_removable_files(symbol_graph, file_graph, &unused);
+                        }
+                        for (export, is_type) in unused {
+                            match (is_type, export.used_in_module) {
+                                (_, true) if rules.module_only_exports => module_only_exports.push(export),
+                                (true, _) if rules.unused_types => unused_types.push(export),
+                                (false, _) if rules.unused_exports => unused_exports.push(export),
+                                _ => {}
+                            }
+                        }
+                    })
+                });
+            }
+            if rules.unused_files {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[3], &mut || {
+                        *unused_files = Self::find_unused_files(file_graph, symbol_graph, reachable);
+                        *dead_chains = Self::find_dead_chains(file_graph, unused_files);
+                    })
+                });
+            }
+            if rules.duplicate_exports {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[4], &mut || {
+                        *duplicate_exports = Self::find_duplicate_exports(symbol_graph, file_graph)
+                    })
+                });
+            }
+            if rules.unlisted_deps {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[5], &mut || {
+                        *unlisted_dependencies = Self::find_unlisted_dependencies(dependency_graph, file_graph, symbol_graph)
+                    })
+                });
+            }
+            if rules.unresolved_imports {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[6], &mut || {
+                        *unresolved_imports = Self::find_unresolved_imports(file_graph, symbol_graph)
+                    })
+                });
+            }
+            if rules.test_only_exports {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[7], &mut || {
+                        *test_only_exports =
+                            Self::find_test_only_exports(symbol_graph, file_graph, reachable, used, rules.jsdoc_tags)
+                    })
+                });
+            }
+            if rules.story_only_exports {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[10], &mut || {
+                        *story_only_exports =
+                            Self::find_story_only_exports(symbol_graph, file_graph, reachable, used, rules.jsdoc_tags)
+                    })
+                });
+            }
+            if rules.orphaned_types {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[8], &mut || {
+                        *orphaned_types = Self::find_orphaned_types(dependency_graph, file_graph)
+                    })
+                });
+            }
+            if rules.unused_peers {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[9], &mut || {
+                        *unused_peer_dependencies = Self::find_unused_peer_dependencies(dependency_graph, file_graph)
+                    })
+                });
+            }
+            if rules.type_only_deps {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[11], &mut || {
+                        *type_only_dependencies = Self::find_type_only_dependencies(dependency_graph, file_graph)
+                    })
+                });
+            }
+            if rules.unused_private_members {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[13], &mut || {
+                        *unused_private_members = Self::find_unused_private_members(symbol_graph, reachable)
+                    })
+                });
+            }
+            if rules.unused_locals {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[14], &mut || {
+                        *unused_locals = Self::find_unused_locals(symbol_graph, reachable)
+                    })
+                });
+            }
+            if rules.unused_imports {
+                scope.spawn(|_| {
+                    timed(RULE_NAMES[15], &mut || {
+                        *unused_imports = Self::find_unused_imports(symbol_graph, reachable)
+                    })
+                });
+            }
+        });
+
+        // Tasks finish in any order; list them the same way every run
+        let mut timings = timings.into_inner().unwrap();
+        timings.sort_by(|a, b| a.phase.cmp(&b.phase));
+        timings.insert(0, reachability);
+        timings
     }
 
     /// Find dependencies that are never imported
-    fn find_unused_dependencies(dependency_graph: &DependencyGraph) -> Vec<UnusedDependency> {
+    fn find_unused_dependencies(
+        dependency_graph: &DependencyGraph,
+        file_graph: &FileImportGraph,
+    ) -> Vec<UnusedDependency> {
+        dependency_graph
+            .unused_dependencies(file_graph.interners())
+            .into_iter()
+            .map(|dep| UnusedDependency {
+                name: file_graph.name(dep.name).to_string(),
+                version: dep.version.clone(),
+                manifest: file_graph.path(dep.manifest).to_path_buf(),
+                kind: dep.kind,
+            })
+            .collect()
+    }
+
+    /// Find peer dependencies that are never imported
+    fn find_unused_peer_dependencies(
+        dependency_graph: &DependencyGraph,
+        file_graph: &FileImportGraph,
+    ) -> Vec<UnusedDependency> {
         dependency_graph
-            .unused_dependencies()
+            .unused_peer_dependencies()
             .into_iter()
             .map(|dep| UnusedDependency {
-                name: dep.name.clone(),
+                name: file_graph.name(dep.name).to_string(),
                 version: dep.version.clone(),
+                manifest: file_graph.path(dep.manifest).to_path_buf(),
+                kind: dep.kind,
             })
             .collect()
     }
 
-    /// Find exports that are never referenced
+    /// Find unused `@types` packages whose runtime package was removed
+    fn find_orphaned_types(
+        dependency_graph: &DependencyGraph,
+        file_graph: &FileImportGraph,
+    ) -> Vec<UnusedDependency> {
+        dependency_graph
+            .orphaned_types(file_graph.interners())
+            .into_iter()
+            .map(|dep| UnusedDependency {
+                name: file_graph.name(dep.name).to_string(),
+                version: dep.version.clone(),
+                manifest: file_graph.path(dep.manifest).to_path_buf(),
+                kind: dep.kind,
+            })
+            .collect()
+    }
+
+    /// Find `dependencies` used only for their types
+    fn find_type_only_dependencies(
+        dependency_graph: &DependencyGraph,
+        file_graph: &FileImportGraph,
+    ) -> Vec<UnusedDependency> {
+        dependency_graph
+            .type_only_dependencies(file_graph.interners())
+            .into_iter()
+            .map(|dep| UnusedDependency {
+                name: file_graph.name(dep.name).to_string(),
+                version: dep.version.clone(),
+                manifest: file_graph.path(dep.manifest).to_path_buf(),
+                kind: dep.kind,
+            })
+            .collect()
+    }
+
+    /// Find private class members never read, in reachable files
+    fn find_unused_private_members(
+        symbol_graph: &SymbolUsageGraph,
+        reachable: &HashSet<FileId>,
+    ) -> Vec<UnusedPrivateMember> {
+        let mut unused: Vec<UnusedPrivateMember> = symbol_graph
+            .unread_members
+            .iter()
+            .filter(|(file, _)| reachable.contains(file))
+            .flat_map(|(&file, members)| members.iter().map(move |item| (file, item)))
+            .map(|(file, member)| {
+                let (line, column) = symbol_graph.line_col(file, member.span.0);
+                UnusedPrivateMember {
+                    name: member.name.clone(),
+                    class: member.class.clone(),
+                    file: member.file.clone(),
+                    line,
+                    column,
+                    span: member.span,
+                }
+            })
+            .collect();
+        unused.sort_by(|a, b| (&a.file, a.span).cmp(&(&b.file, b.span)));
+        unused
+    }
+
+    /// Find local bindings nothing reads, in reachable files
+    fn find_unused_locals(
+        symbol_graph: &SymbolUsageGraph,
+        reachable: &HashSet<FileId>,
+    ) -> Vec<UnusedLocal> {
+        let mut unused: Vec<UnusedLocal> = symbol_graph
+            .unread_locals
+            .iter()
+            .filter(|(file, _)| reachable.contains(file))
+            .flat_map(|(&file, locals)| locals.iter().map(move |item| (file, item)))
+            .map(|(file, local)| {
+                let (line, column) = symbol_graph.line_col(file, local.span.0);
+                UnusedLocal {
+                    name: local.name.clone(),
+                    file: local.file.clone(),
+                    line,
+                    column,
+                    span: local.span,
+                }
+            })
+            .collect();
+        unused.sort_by(|a, b| (&a.file, a.span).cmp(&(&b.file, b.span)));
+        unused
+    }
+
+    /// Find import bindings nothing references, in reachable files
+    fn find_unused_imports(
+        symbol_graph: &SymbolUsageGraph,
+        reachable: &HashSet<FileId>,
+    ) -> Vec<UnusedImport> {
+        let mut unused: Vec<UnusedImport> = symbol_graph
+            .unused_imports
+            .iter()
+            .filter(|(file, _)| reachable.contains(file))
+            .flat_map(|(&file, imports)| imports.iter().map(move |item| (file, item)))
+            .map(|(file, import)| {
+                let (line, column) = symbol_graph.line_col(file, import.span.0);
+                UnusedImport {
+                    name: import.name.clone(),
+                    source: import.source.clone(),
+                    file: import.file.clone(),
+                    line,
+                    column,
+                    span: import.span,
+                    statement: import.statement,
+                }
+            })
+            .collect();
+        unused.sort_by(|a, b| (&a.file, a.span).cmp(&(&b.file, b.span)));
+        unused
+    }
+
+    /// Find package imports missing from every package.json above the importing file
+    fn find_unlisted_dependencies(
+        dependency_graph: &DependencyGraph,
+        file_graph: &FileImportGraph,
+        symbol_graph: &SymbolUsageGraph,
+    ) -> Vec<UnlistedDependency> {
+        let mut unlisted: Vec<UnlistedDependency> = dependency_graph
+            .unlisted
+            .iter()
+            .map(|import| {
+                let (line, column) = file_graph
+                    .file_id(&import.file)
+                    .map_or((1, 1), |file| symbol_graph.line_col(file, import.span.0));
+                UnlistedDependency {
+                    name: import.name.clone(),
+                    file: import.file.clone(),
+                    line,
+                    column,
+                    span: import.span,
+                }
+            })
+            .collect();
+        unlisted.sort_by(|a, b| (&a.name, &a.file, a.span).cmp(&(&b.name, &b.file, b.span)));
+        unlisted
+    }
+
+    /// Find relative imports that match no file
+    fn find_unresolved_imports(
+        file_graph: &FileImportGraph,
+        symbol_graph: &SymbolUsageGraph,
+    ) -> Vec<UnresolvedImport> {
+        let mut unresolved: Vec<UnresolvedImport> = file_graph
+            .imports()
+            .filter(|import| !import.is_resolved)
+            .map(|import| {
+                let (line, column) = symbol_graph.line_col(import.from, import.span.0);
+                UnresolvedImport {
+                    specifier: import.specifier.clone(),
+                    file: file_graph.path(import.from).to_path_buf(),
+                    line,
+                    column,
+                    span: import.span,
+                }
+            })
+            .collect();
+        unresolved.sort_by(|a, b| (&a.file, a.span).cmp(&(&b.file, b.span)));
+        unresolved
+    }
+
+    /// Find exports that are never referenced, each with whether it is a type
+    ///
+    /// With `jsdoc_tags`, exports tagged `@public` are kept as intentional API.
     fn find_unused_exports(
         symbol_graph: &SymbolUsageGraph,
         file_graph: &FileImportGraph,
-    ) -> Vec<UnusedExport> {
+        reachable: &HashSet<FileId>,
+        used: &UsedExports,
+        jsdoc_tags: bool,
+    ) -> Vec<(UnusedExport, bool)> {
         let mut unused = Vec::new();
+        let template_names = symbol_graph.template_names();
 
         // Only check files that are reachable
-        let reachable = file_graph.reachable_files();
-
-        for file in reachable {
-            let exports_in_file = symbol_graph.unused_exports_in_file(&file);
+        for &id in reachable {
+            let file = file_graph.path(id);
+            let exports_in_file = symbol_graph.unused_exports_in_file(id, used);
+            let references = symbol_graph.references.get(&id).map_or(&[][..], Vec::as_slice);
 
             for export in exports_in_file {
-                unused.push(UnusedExport {
-                    name: export.name.clone(),
-                    file: export.file.clone(),
-                    line: export.span.0,
-                    column: export.span.1,
-                });
+                if (jsdoc_tags && is_public(export)) || is_used_in_template(export, &template_names) {
+                    continue;
+                }
+
+                let (line, column) = symbol_graph.line_col(id, export.span.0);
+                let declaration = export.declaration.unwrap_or(export.span);
+                let unused_export = UnusedExport {
+                    name: file_graph.name(export.name).to_string(),
+                    file: file.to_path_buf(),
+                    line,
+                    column,
+                    span: export.span,
+                    used_in_module: export.used_in_module
+                        || references.iter().any(|reference| reference.symbol == export.name),
+                    lines: symbol_graph.lines.get(&id).map_or(0, |lines| lines.lines_in(declaration)),
+                    bytes: declaration.1 - declaration.0,
+                };
+                unused.push((unused_export, export.is_type));
             }
         }
 
         unused
     }
 
-    /// Find files that are not reachable from any entry point
-    fn find_unused_files(file_graph: &FileImportGraph) -> Vec<UnusedFile> {
-        let reachable = file_graph.reachable_files();
+    /// Find files whose exports are all among `unused`, leaving out entry
+    /// points and files imported or declared for their side effects
+    fn find_removable_files(
+        symbol_graph: &SymbolUsageGraph,
+        file_graph: &FileImportGraph,
+        unused: &[(UnusedExport, bool)],
+    ) -> Vec<RemovableFile> {
+        let mut counts: HashMap<&Path, usize> = HashMap::new();
+        for (export, _) in unused {
+            *counts.entry(&export.file).or_default() += 1;
+        }
+
+        let mut removable: Vec<RemovableFile> = counts
+            .into_iter()
+            .filter(|(file, count)| {
+                file_graph
+                    .file_id(file)
+                    .and_then(|id| symbol_graph.exports.get(&id))
+                    .is_some_and(|exports| exports.len() == *count)
+            })
+            .filter(|(file, _)| {
+                file_graph
+                    .file(file)
+                    .is_some_and(|node| !node.is_entry_point && !node.has_side_effects)
+            })
+            .filter_map(|(file, exports)| {
+                let mut importers = Vec::new();
+                for import in file_graph.imports_to(file) {
+                    // A bare `import './file'` runs it for its side effects
+                    if import.imported_symbols.is_empty() && import.forwarded.is_empty() {
+                        return None;
+                    }
+                    importers.push(file_graph.path(import.from).to_path_buf());
+                }
+                importers.sort();
+                importers.dedup();
+                Some(RemovableFile {
+                    path: file.to_path_buf(),
+                    exports,
+                    importers,
+                })
+            })
+            .collect();
+        removable.sort_by(|a, b| a.path.cmp(&b.path));
+        removable
+    }
+
+    /// Find exports of production files whose every importer is a test file
+    fn find_test_only_exports(
+        symbol_graph: &SymbolUsageGraph,
+        file_graph: &FileImportGraph,
+        reachable: &HashSet<FileId>,
+        used: &UsedExports,
+        jsdoc_tags: bool,
+    ) -> Vec<TestOnlyExport> {
+        let is_test = |file: &FileNode| file.is_test;
+        Self::find_exports_only_used_by(symbol_graph, file_graph, reachable, used, jsdoc_tags, is_test)
+            .into_iter()
+            .map(|(export, line, column, tests)| TestOnlyExport {
+                name: file_graph.name(export.name).to_string(),
+                file: file_graph.path(export.file).to_path_buf(),
+                line,
+                column,
+                span: export.span,
+                tests,
+            })
+            .collect()
+    }
+
+    /// Find exports of other files whose every importer is a Storybook story
+    fn find_story_only_exports(
+        symbol_graph: &SymbolUsageGraph,
+        file_graph: &FileImportGraph,
+        reachable: &HashSet<FileId>,
+        used: &UsedExports,
+        jsdoc_tags: bool,
+    ) -> Vec<StoryOnlyExport> {
+        let is_story = |file: &FileNode| file.is_story;
+        Self::find_exports_only_used_by(symbol_graph, file_graph, reachable, used, jsdoc_tags, is_story)
+            .into_iter()
+            .map(|(export, line, column, stories)| StoryOnlyExport {
+                name: file_graph.name(export.name).to_string(),
+                file: file_graph.path(export.file).to_path_buf(),
+                line,
+                column,
+                span: export.span,
+                stories,
+            })
+            .collect()
+    }
+
+    /// Find exports of files `consumer` rejects whose every importer is a file
+    /// it accepts, each with its line, column, and those importers
+    ///
+    /// Re-exported names are checked at their declaration, where usage through
+    /// the re-exporting file is followed, so each is reported once. Usage by
+    /// other files is worked out once up front; only exports it misses have
+    /// their importers looked up.
+    fn find_exports_only_used_by<'a>(
+        symbol_graph: &'a SymbolUsageGraph,
+        file_graph: &FileImportGraph,
+        reachable: &HashSet<FileId>,
+        used: &UsedExports,
+        jsdoc_tags: bool,
+        consumer: impl Fn(&FileNode) -> bool,
+    ) -> Vec<(&'a Symbol, usize, usize, Vec<PathBuf>)> {
+        let mut only_used = Vec::new();
+        let used_elsewhere = file_graph.used_exports_by(|importer| !consumer(importer));
+        let template_names = symbol_graph.template_names();
+        let is_consumer = |path: &Path| file_graph.file(path).is_some_and(&consumer);
+
+        for &id in reachable {
+            let file = file_graph.path(id);
+            if is_consumer(file) {
+                continue;
+            }
+            let Some(exports) = symbol_graph.exports.get(&id) else {
+                continue;
+            };
+            let forwarded: HashSet<SymbolId> = file_graph
+                .imports_from(file)
+                .flat_map(|import| import.forwarded.iter().map(|&(exported, _)| exported))
+                .collect();
+
+            let mut seen = HashSet::new();
+            for export in exports {
+                if forwarded.contains(&export.name)
+                    || !seen.insert(export.name)
+                    || (jsdoc_tags && is_public(export))
+                    || !used.is_used(id, export.name)
+                    || used_elsewhere.is_used(id, export.name)
+                    || is_used_in_template(export, &template_names)
+                {
+                    continue;
+                }
 
+                let importers: Vec<&Path> = file_graph
+                    .usage_chains(file, file_graph.name(export.name))
+                    .into_iter()
+                    .filter_map(|chain| chain.last().map(|edge| file_graph.path(edge.from)))
+                    .collect();
+                if importers.is_empty() || !importers.iter().all(|path| is_consumer(path)) {
+                    continue;
+                }
+
+                let mut importers: Vec<PathBuf> = importers.into_iter().map(Path::to_path_buf).collect();
+                importers.sort();
+                importers.dedup();
+                let (line, column) = symbol_graph.line_col(id, export.span.0);
+                only_used.push((export, line, column, importers));
+            }
+        }
+
+        only_used.sort_by(|a, b| {
+            (file_graph.path(a.0.file), a.0.span).cmp(&(file_graph.path(b.0.file), b.0.span))
+        });
+        only_used
+    }
+
+    /// Find files that are not reachable from any entry point
+    fn find_unused_files(
+        file_graph: &FileImportGraph,
+        symbol_graph: &SymbolUsageGraph,
+        reachable: &HashSet<FileId>,
+    ) -> Vec<UnusedFile> {
         file_graph
-            .files
-            .values()
-            .filter(|file| !reachable.contains(&file.path) && !file.is_entry_point)
-            .map(|file| UnusedFile {
-                path: file.path.clone(),
+            .files()
+            .filter(|file| !reachable.contains(&file.id) && !file.is_entry_point)
+            .map(|file| {
+                let lines = symbol_graph.lines.get(&file.id);
+                UnusedFile {
+                    path: file.path.to_path_buf(),
+                    has_side_effects: file.has_side_effects,
+                    lines: lines.map_or(0, |lines| lines.lines_in((0, lines.size()))),
+                    bytes: lines.map_or(0, LineIndex::size),
+                }
             })
             .collect()
     }
+
+    /// Group unused files that import one another into chains
+    ///
+    /// Nothing reachable imports an unused file, so each group of unused files
+    /// connected by imports, in either direction, can be deleted at once.
+    fn find_dead_chains(file_graph: &FileImportGraph, unused_files: &[UnusedFile]) -> Vec<DeadChain> {
+        let unused: HashSet<&Path> = unused_files.iter().map(|file| file.path.as_path()).collect();
+        let mut neighbors: HashMap<&Path, Vec<&Path>> = HashMap::new();
+        let mut imported: HashSet<&Path> = HashSet::new();
+        for import in file_graph.imports() {
+            let (from, to) = (file_graph.path(import.from), file_graph.path(import.to));
+            if from != to && unused.contains(from) && unused.contains(to) {
+                neighbors.entry(from).or_default().push(to);
+                neighbors.entry(to).or_default().push(from);
+                imported.insert(to);
+            }
+        }
+
+        let mut visited = HashSet::new();
+        let mut chains = Vec::new();
+        for file in unused_files {
+            let path = file.path.as_path();
+            if !neighbors.contains_key(path) || !visited.insert(path) {
+                continue;
+            }
+
+            let mut files = Vec::new();
+            let mut stack = vec![path];
+            while let Some(current) = stack.pop() {
+                files.push(current.to_path_buf());
+                for &next in &neighbors[current] {
+                    if visited.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+            files.sort();
+
+            // A chain that is one import cycle has no natural root
+            let mut roots: Vec<PathBuf> =
+                files.iter().filter(|path| !imported.contains(path.as_path())).cloned().collect();
+            if roots.is_empty() {
+                roots.push(files[0].clone());
+            }
+
+            chains.push(DeadChain { roots, files });
+        }
+
+        chains.sort_by(|a, b| a.files[0].cmp(&b.files[0]));
+        chains
+    }
+
+    /// Find names declared as exports in more than one file
+    ///
+    /// Re-exports forward another file's declaration rather than duplicate it,
+    /// and every module may have its own `default`, so neither counts.
+    fn find_duplicate_exports(
+        symbol_graph: &SymbolUsageGraph,
+        file_graph: &FileImportGraph,
+    ) -> Vec<DuplicateExport> {
+        let mut declarations: BTreeMap<&str, Vec<(FileId, &Symbol)>> = BTreeMap::new();
+
+        for (&file, exports) in &symbol_graph.exports {
+            let forwarded: HashSet<SymbolId> = file_graph
+                .imports_from(file_graph.path(file))
+                .flat_map(|import| import.forwarded.iter().map(|&(exported, _)| exported))
+                .collect();
+
+            // Overloads and the like declare a name more than once in one file
+            let mut seen = HashSet::new();
+            for export in exports {
+                let name = file_graph.name(export.name);
+                if name == "default"
+                    || name == EXPORT_ASSIGNMENT
+                    || forwarded.contains(&export.name)
+                    || !seen.insert(export.name)
+                {
+                    continue;
+                }
+
+                declarations.entry(name).or_default().push((file, export));
+            }
+        }
+
+        // Usages are only counted for the few names that are duplicated
+        declarations
+            .into_iter()
+            .filter(|(_, declared)| declared.len() > 1)
+            .map(|(name, declared)| {
+                let mut locations: Vec<ExportLocation> = declared
+                    .into_iter()
+                    .map(|(file, export)| {
+                        let (line, column) = symbol_graph.line_col(file, export.span.0);
+                        let file = file_graph.path(file);
+                        ExportLocation {
+                            file: file.to_path_buf(),
+                            line,
+                            column,
+                            span: export.span,
+                            usages: file_graph.usage_chains(file, name).len(),
+                        }
+                    })
+                    .collect();
+                locations.sort_by(|a, b| a.file.cmp(&b.file));
+                DuplicateExport {
+                    name: name.to_string(),
+                    locations,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether an Angular template uses a component or directive by its selector
+fn is_used_in_template(export: &Symbol, template_names: &HashSet<String>) -> bool {
+    export
+        .selector
+        .as_deref()
+        .is_some_and(|selector| angular::selector_matches(selector, template_names))
+}
+
+/// Whether an export is tagged `@public`, and so never reported
+fn is_public(export: &Symbol) -> bool {
+    export.visibility == Some(Visibility::Public)
 }
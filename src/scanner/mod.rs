@@ -1,69 +1,324 @@
 use crate::error::{PurgeError, Result};
-use ignore::Walk;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
+/// Directories that are never part of the analyzed sources
+const DEFAULT_IGNORES: &[&str] = &["node_modules", "dist", "build", "coverage", ".git"];
+
+/// Hidden directories scanned anyway, as they hold config that imports sources
+const HIDDEN_SOURCE_DIRS: &[&str] = &[".storybook"];
+
+/// Gitignore-syntax file, at the root or in any directory, excluding files from analysis only
+const IGNORE_FILENAME: &str = ".sweeprignore";
+
+/// Bytes read from the start of a file to decide whether it is minified
+const MINIFIED_SAMPLE_BYTES: u64 = 8 * 1024;
+
+/// Average line length above which a sample is considered minified
+const MINIFIED_AVERAGE_LINE_LENGTH: usize = 300;
+
 #[derive(Debug, Clone)]
 pub struct FileDiscovery {
     pub files: Vec<PathBuf>,
     pub entry_points: Vec<PathBuf>,
+    /// Discovered files matching the test globs, also listed as entry points
+    pub tests: Vec<PathBuf>,
+    /// Discovered files matching the story globs, also listed as entry points
+    pub stories: Vec<PathBuf>,
+    /// Discovered files their package.json declares as having side effects
+    pub side_effects: Vec<PathBuf>,
+    /// Exports of discovered files that a framework reads, by file
+    pub used_exports: HashMap<PathBuf, Vec<String>>,
+    /// Source files left out of the analysis, and why
+    pub skipped: Vec<SkippedFile>,
+}
+
+/// A source file the scanner found but did not analyze
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedFile {
+    pub path: PathBuf,
+    pub reason: SkipReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SkipReason {
+    /// Larger than the configured `max_file_size`
+    Oversized,
+    /// Bundled or minified output
+    Minified,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SkipReason::Oversized => write!(f, "oversized"),
+            SkipReason::Minified => write!(f, "minified"),
+        }
+    }
 }
 
 pub struct WorkspaceScanner {
     root: PathBuf,
+    /// Directories to walk; the project root when empty
+    roots: Vec<PathBuf>,
+    ignore: Vec<String>,
+    include: Vec<String>,
+    entry_globs: Vec<String>,
+    test_globs: Vec<String>,
+    story_globs: Vec<String>,
+    side_effect_globs: Vec<String>,
+    used_export_globs: Vec<(String, Vec<String>)>,
+    follow_symlinks: bool,
+    max_file_size: Option<u64>,
+    skip_minified: bool,
 }
 
 impl WorkspaceScanner {
     pub fn new(root: PathBuf) -> Self {
-        Self { root }
+        Self {
+            root,
+            roots: Vec::new(),
+            ignore: Vec::new(),
+            include: Vec::new(),
+            entry_globs: Vec::new(),
+            test_globs: Vec::new(),
+            story_globs: Vec::new(),
+            side_effect_globs: Vec::new(),
+            used_export_globs: Vec::new(),
+            follow_symlinks: false,
+            max_file_size: None,
+            skip_minified: false,
+        }
+    }
+
+    /// Walk these directories instead of the whole project root
+    ///
+    /// Entry points and ignore patterns stay relative to the project root;
+    /// files found under several roots are only reported once.
+    pub fn with_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.roots = roots;
+        self
+    }
+
+    /// Directories that are walked when discovering files
+    pub fn roots(&self) -> Vec<PathBuf> {
+        if self.roots.is_empty() {
+            vec![self.root.clone()]
+        } else {
+            self.roots.clone()
+        }
+    }
+
+    /// Exclude files matching these glob patterns (from the config `ignore` list)
+    pub fn with_ignore(mut self, patterns: Vec<String>) -> Self {
+        self.ignore = patterns;
+        self
+    }
+
+    /// Only scan files matching at least one of these glob patterns
+    pub fn with_include(mut self, patterns: Vec<String>) -> Self {
+        self.include = patterns;
+        self
+    }
+
+    /// Treat every discovered file matching these globs (relative to the
+    /// project root) as an entry point, e.g. framework route directories
+    pub fn with_entry_globs(mut self, patterns: Vec<String>) -> Self {
+        self.entry_globs = patterns;
+        self
+    }
+
+    /// Treat every discovered file matching these globs (relative to the
+    /// project root) as a test file, and so as an entry point
+    pub fn with_test_globs(mut self, patterns: Vec<String>) -> Self {
+        self.test_globs = patterns;
+        self
+    }
+
+    /// Treat every discovered file matching these globs (relative to the
+    /// project root) as a Storybook story, and so as an entry point
+    pub fn with_story_globs(mut self, patterns: Vec<String>) -> Self {
+        self.story_globs = patterns;
+        self
+    }
+
+    /// Mark discovered files matching these globs (relative to the project
+    /// root) as having side effects, from the `sideEffects` package.json field
+    pub fn with_side_effect_globs(mut self, patterns: Vec<String>) -> Self {
+        self.side_effect_globs = patterns;
+        self
+    }
+
+    /// Mark the named exports of discovered files matching each glob
+    /// (relative to the project root) as used, e.g. `getServerSideProps` of
+    /// Next.js pages
+    pub fn with_used_export_globs(mut self, patterns: Vec<(String, Vec<String>)>) -> Self {
+        self.used_export_globs = patterns;
+        self
+    }
+
+    /// Descend into symlinked directories
+    ///
+    /// Symlink cycles are skipped, and a file reachable through several links
+    /// is only reported once, under the first path found.
+    pub fn with_follow_symlinks(mut self, follow: bool) -> Self {
+        self.follow_symlinks = follow;
+        self
+    }
+
+    /// Skip files larger than `bytes`
+    pub fn with_max_file_size(mut self, bytes: Option<u64>) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+
+    /// Skip files that look minified (`*.min.js`, or very long average lines)
+    pub fn with_skip_minified(mut self, skip: bool) -> Self {
+        self.skip_minified = skip;
+        self
     }
 
     /// Discover all JavaScript/TypeScript files in the workspace
+    ///
+    /// Files excluded by `.gitignore` (even outside a git repository),
+    /// `.sweeprignore`, the default ignores, or the configured ignore patterns
+    /// are skipped, as are files outside the include globs and oversized or
+    /// minified files when those checks are enabled.
     pub fn discover(&self, entry_points: Vec<String>) -> Result<FileDiscovery> {
         let mut files = Vec::new();
-        let _ignore_rules = self.load_gitignore()?;
+        let mut skipped = Vec::new();
+        let overrides = self.build_overrides()?;
+        let include = build_globset(&self.include, "include")?;
+
+        // Walk every root
+        let roots = self.roots();
+        let mut walk_builder = WalkBuilder::new(&roots[0]);
+        for root in &roots[1..] {
+            walk_builder.add(root);
+        }
+        let walker = walk_builder
+            .add_custom_ignore_filename(IGNORE_FILENAME)
+            .require_git(false)
+            .hidden(false)
+            .filter_entry(|entry| {
+                let name = entry.file_name().to_string_lossy();
+                entry.depth() == 0 || !name.starts_with('.') || HIDDEN_SOURCE_DIRS.contains(&name.as_ref())
+            })
+            .follow_links(self.follow_symlinks)
+            .overrides(overrides)
+            .build();
+
+        // Paths already seen (real paths when following links), so files under
+        // overlapping roots or behind several links are scanned once
+        let mut seen = HashSet::new();
 
-        // Walk the directory
-        for entry in Walk::new(&self.root)
-            .filter(|entry| entry.as_ref().map_or(false, |e| {
-                self.is_js_ts_file(e.path()) && !self.is_in_node_modules(e.path())
-            }))
+        // Unreadable entries and symlink loops surface as errors and are skipped
+        for entry in walker.filter(|entry| entry.as_ref().is_ok_and(|e| is_source_file(e.path())))
         {
-            let entry = entry.map_err(|e| PurgeError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                e.to_string(),
-            )))?;
+            let entry = entry.map_err(|e| PurgeError::Io(std::io::Error::other(e.to_string())))?;
+
+            let key = if self.follow_symlinks {
+                entry.path().canonicalize()?
+            } else {
+                entry.path().to_path_buf()
+            };
+            if !seen.insert(key) {
+                continue;
+            }
+
+            let relative = entry.path().strip_prefix(&self.root).unwrap_or(entry.path());
+            if !self.include.is_empty() && !include.is_match(relative) {
+                continue;
+            }
+
+            if let Some(reason) = self.skip_reason(entry.path())? {
+                skipped.push(SkippedFile {
+                    path: entry.path().to_path_buf(),
+                    reason,
+                });
+                continue;
+            }
 
             files.push(entry.path().to_path_buf());
         }
 
         // Resolve entry points
-        let resolved_entry_points = entry_points
+        let mut resolved_entry_points = entry_points
             .iter()
             .map(|ep| self.resolve_entry_point(ep))
             .collect::<Result<Vec<PathBuf>>>()?;
 
+        // Add files that are entry points by convention, tests included
+        let entry_globs = build_globset(&self.entry_globs, "entry")?;
+        let test_globs = build_globset(&self.test_globs, "test")?;
+        let story_globs = build_globset(&self.story_globs, "story")?;
+        let side_effect_globs = build_globset(&self.side_effect_globs, "sideEffects")?;
+        let export_globs: Vec<String> = self.used_export_globs.iter().map(|(glob, _)| glob.clone()).collect();
+        let export_globs = build_globset(&export_globs, "entry")?;
+        let mut known: HashSet<PathBuf> = resolved_entry_points.iter().cloned().collect();
+        let mut tests = Vec::new();
+        let mut stories = Vec::new();
+        let mut side_effects = Vec::new();
+        let mut used_exports: HashMap<PathBuf, Vec<String>> = HashMap::new();
+        for file in &files {
+            let relative = file.strip_prefix(&self.root).unwrap_or(file);
+            if side_effect_globs.is_match(relative) {
+                side_effects.push(file.clone());
+            }
+            for matched in export_globs.matches(relative) {
+                let names = used_exports.entry(file.clone()).or_default();
+                for name in &self.used_export_globs[matched].1 {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+            }
+            let is_test = test_globs.is_match(relative);
+            if is_test {
+                tests.push(file.clone());
+            }
+            let is_story = !is_test && story_globs.is_match(relative);
+            if is_story {
+                stories.push(file.clone());
+            }
+            if (is_test || is_story || entry_globs.is_match(relative)) && known.insert(file.clone()) {
+                resolved_entry_points.push(file.clone());
+            }
+        }
+
         Ok(FileDiscovery {
             files,
             entry_points: resolved_entry_points,
+            tests,
+            stories,
+            side_effects,
+            used_exports,
+            skipped,
         })
     }
 
-    fn is_js_ts_file(&self, path: &Path) -> bool {
-        match path.extension().and_then(|ext| ext.to_str()) {
-            Some(ext) => matches!(
-                ext,
-                "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs"
-            ),
-            None => false,
+    fn skip_reason(&self, path: &Path) -> Result<Option<SkipReason>> {
+        if let Some(max_file_size) = self.max_file_size {
+            if std::fs::metadata(path)?.len() > max_file_size {
+                return Ok(Some(SkipReason::Oversized));
+            }
         }
-    }
 
-    fn is_in_node_modules(&self, path: &Path) -> bool {
-        path.components()
-            .any(|c| c.as_os_str() == "node_modules")
+        if self.skip_minified && looks_minified(path)? {
+            return Ok(Some(SkipReason::Minified));
+        }
+
+        Ok(None)
     }
 
-    fn resolve_entry_point(&self, entry: &str) -> Result<PathBuf> {
+    pub fn resolve_entry_point(&self, entry: &str) -> Result<PathBuf> {
         let path = self.root.join(entry);
 
         if path.exists() {
@@ -89,13 +344,21 @@ impl WorkspaceScanner {
         }
     }
 
-    fn load_gitignore(&self) -> Result<ignore::overrides::Override> {
-        let mut override_builder = ignore::overrides::OverrideBuilder::new(&self.root);
+    /// Default and configured ignores as negated override globs
+    ///
+    /// Override globs act as a whitelist unless negated, so every pattern is
+    /// added with a leading `!` to exclude matches instead.
+    fn build_overrides(&self) -> Result<Override> {
+        let mut override_builder = OverrideBuilder::new(&self.root);
+
+        let patterns = DEFAULT_IGNORES
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .chain(self.ignore.iter().cloned());
 
-        // Add default ignores
-        for pattern in &["node_modules", "dist", "build", ".git"] {
+        for pattern in patterns {
             override_builder
-                .add(pattern)
+                .add(&format!("!{}", pattern))
                 .map_err(|e| PurgeError::Config(e.to_string()))?;
         }
 
@@ -104,3 +367,46 @@ impl WorkspaceScanner {
             .map_err(|e| PurgeError::Config(e.to_string()))
     }
 }
+
+/// Match paths relative to the project root against `patterns`
+/// Whether `path` has the extension of a file sweepr scans and parses
+pub fn is_source_file(path: &Path) -> bool {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) => matches!(
+            ext,
+            "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "astro" | "svelte" | "vue"
+        ),
+        None => false,
+    }
+}
+
+pub fn build_globset(patterns: &[String], kind: &str) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| PurgeError::Config(format!("Invalid {} pattern: {}", kind, e)))?;
+        builder.add(glob);
+    }
+
+    builder
+        .build()
+        .map_err(|e| PurgeError::Config(e.to_string()))
+}
+
+/// Whether a file is named like bundled output or starts with very long lines
+fn looks_minified(path: &Path) -> Result<bool> {
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    if name.contains(".min.") {
+        return Ok(true);
+    }
+
+    let mut sample = Vec::new();
+    std::fs::File::open(path)?
+        .take(MINIFIED_SAMPLE_BYTES)
+        .read_to_end(&mut sample)?;
+
+    let lines = sample.split(|&byte| byte == b'\n').count();
+    Ok(sample.len() / lines > MINIFIED_AVERAGE_LINE_LENGTH)
+}
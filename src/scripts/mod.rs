@@ -0,0 +1,138 @@
+use crate::workspaces::WorkspacePackage;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Commands that run the binary named after them, e.g. `npx tsc` or `yarn jest`
+const RUNNERS: &[&str] = &[
+    "npx", "bunx", "pnpx", "npm", "yarn", "pnpm", "bun", "exec", "dlx", "run", "cross-env",
+    "dotenv", "--",
+];
+
+/// Binaries of well-known packages not named after the package, e.g. `tsc`
+/// of `typescript`, for dependencies that aren't installed
+const KNOWN_BINARIES: &[(&str, &[&str])] = &[
+    ("@angular/cli", &["ng"]),
+    ("@babel/cli", &["babel"]),
+    ("@babel/node", &["babel-node"]),
+    ("@biomejs/biome", &["biome"]),
+    ("@changesets/cli", &["changeset"]),
+    ("@commitlint/cli", &["commitlint"]),
+    ("@graphql-codegen/cli", &["graphql-codegen", "gql-gen"]),
+    ("@microsoft/api-extractor", &["api-extractor"]),
+    ("@nestjs/cli", &["nest"]),
+    ("@playwright/test", &["playwright"]),
+    ("@remix-run/dev", &["remix"]),
+    ("@rspack/cli", &["rspack"]),
+    ("@sentry/cli", &["sentry-cli"]),
+    ("@storybook/cli", &["sb", "storybook"]),
+    ("@sveltejs/kit", &["svelte-kit"]),
+    ("@swc/cli", &["swc"]),
+    ("@tauri-apps/cli", &["tauri"]),
+    ("@vercel/ncc", &["ncc"]),
+    ("@vue/cli-service", &["vue-cli-service"]),
+    ("@web/test-runner", &["web-test-runner", "wtr"]),
+    ("concurrently", &["concurrently", "conc"]),
+    ("npm-run-all", &["npm-run-all", "run-p", "run-s"]),
+    ("npm-run-all2", &["npm-run-all", "run-p", "run-s"]),
+    ("nuxt", &["nuxt", "nuxi"]),
+    ("storybook", &["storybook", "sb"]),
+    ("typescript", &["tsc", "tsserver"]),
+    ("webpack-cli", &["webpack", "webpack-cli"]),
+];
+
+/// Declared dependencies whose binaries the package's `scripts` invoke, each
+/// with the package.json naming them
+///
+/// Binaries are matched through the `bin` field of each dependency's
+/// installed package.json. A dependency that isn't installed provides the
+/// binaries `KNOWN_BINARIES` lists for it, or else one named after itself,
+/// as most do.
+pub fn referenced_packages(package: &WorkspacePackage) -> Vec<(String, PathBuf)> {
+    let Some(manifest) = std::fs::read_to_string(&package.manifest)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    else {
+        return Vec::new();
+    };
+    let Some(scripts) = manifest.get("scripts").and_then(|scripts| scripts.as_object()) else {
+        return Vec::new();
+    };
+
+    let mut binaries: HashMap<String, Vec<&str>> = HashMap::new();
+    for (name, _, _) in &package.dependencies {
+        for binary in binary_names(&package.dir, name) {
+            binaries.entry(binary).or_default().push(name);
+        }
+    }
+
+    let mut references = Vec::new();
+    for script in scripts.values().filter_map(|script| script.as_str()) {
+        for command in commands(script) {
+            if let Some(packages) = binaries.get(command) {
+                references.extend(
+                    packages
+                        .iter()
+                        .map(|name| (name.to_string(), package.manifest.clone())),
+                );
+            }
+        }
+    }
+
+    references
+}
+
+/// Names of the binaries a dependency installs, from its `bin` field
+fn binary_names(dir: &Path, package: &str) -> Vec<String> {
+    let unscoped = package.rsplit('/').next().unwrap_or(package).to_string();
+
+    // Hoisted installs put the package in a node_modules further up
+    let manifest = dir
+        .ancestors()
+        .map(|dir| dir.join("node_modules").join(package).join("package.json"))
+        .find(|path| path.is_file())
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+
+    let Some(manifest) = manifest else {
+        return match KNOWN_BINARIES.iter().find(|(name, _)| *name == package) {
+            Some((_, binaries)) => binaries.iter().map(|binary| binary.to_string()).collect(),
+            None => vec![unscoped],
+        };
+    };
+    match manifest.get("bin") {
+        // `"bin": "cli.js"` is named after the package
+        Some(serde_json::Value::String(_)) => vec![unscoped],
+        Some(serde_json::Value::Object(bins)) => bins.keys().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Programs a script runs: the first word of each command, looking through
+/// environment assignments, flags, and runners such as `npx`
+fn commands(script: &str) -> Vec<&str> {
+    let mut commands = Vec::new();
+
+    for command in script.split(['&', '|', ';', '(', ')', '\n']) {
+        let words = command
+            .split_whitespace()
+            .filter(|word| !is_env_assignment(word))
+            .map(|word| word.rsplit('/').next().unwrap_or(word));
+        for word in words {
+            // Runners are binaries too, e.g. `cross-env`
+            commands.push(word);
+            if !RUNNERS.contains(&word) && !word.starts_with('-') {
+                break;
+            }
+        }
+    }
+
+    commands.sort_unstable();
+    commands.dedup();
+    commands
+}
+
+/// `NODE_ENV=production` and the like, set before the program name
+fn is_env_assignment(word: &str) -> bool {
+    word.split_once('=')
+        .is_some_and(|(name, _)| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+}
@@ -0,0 +1,149 @@
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Files at least this large are memory-mapped instead of read into a buffer
+const MMAP_THRESHOLD: u64 = 256 * 1024;
+
+/// Whether large files may be memory-mapped; see `disable_mmap`
+static MMAP_ENABLED: AtomicBool = AtomicBool::new(true);
+
+thread_local! {
+    /// Read buffer reused by every file a worker thread reads
+    static BUFFER: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Read every file into a buffer from now on, never memory-mapping it
+///
+/// For long-running modes such as `--watch`, the LSP server, and the daemon,
+/// which run while files are being edited: another process truncating a
+/// mapped file would crash sweepr with SIGBUS rather than fail the read.
+pub fn disable_mmap() {
+    MMAP_ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Call `f` with the UTF-8 source of the file at `path`
+///
+/// Large files are memory-mapped unless `disable_mmap` was called; others are read into a per-thread buffer
+/// that keeps its capacity between files, so parsing many files in parallel
+/// doesn't allocate a fresh string for each. The source is only borrowed for
+/// the duration of `f`.
+pub fn with_source<R>(path: &Path, f: impl FnOnce(&str) -> R) -> io::Result<R> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    if len >= MMAP_THRESHOLD && MMAP_ENABLED.load(Ordering::Relaxed) {
+        // SAFETY: the map is read-only and dropped before returning. Sweepr
+        // never writes a file while parsing it, and the modes that run while
+        // other processes edit files disable mapping
+        let map = unsafe { Mmap::map(&file)? };
+        return Ok(f(utf8(&map)?));
+    }
+
+    BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        buffer.clear();
+        file.read_to_end(&mut buffer)?;
+        Ok(f(utf8(&buffer)?))
+    })
+}
+
+fn utf8(bytes: &[u8]) -> io::Result<&str> {
+    std::str::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Maps byte offsets in a source file to 1-based line and column numbers
+///
+/// Only line starts and the offsets of non-ASCII characters are kept, so the
+/// index answers for a file without its source text, e.g. from the cache.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+    /// Byte offset and UTF-8 length of every character longer than a byte
+    wide_chars: Vec<(usize, u8)>,
+    len: usize,
+}
+
+impl LineIndex {
+    pub fn new(source: &str) -> Self {
+        let mut line_starts = vec![0];
+        let mut wide_chars = Vec::new();
+        for (i, c) in source.char_indices() {
+            if c == '\n' {
+                line_starts.push(i + 1);
+            } else if !c.is_ascii() {
+                wide_chars.push((i, c.len_utf8() as u8));
+            }
+        }
+        Self {
+            line_starts,
+            wide_chars,
+            len: source.len(),
+        }
+    }
+
+    /// 1-based line and column (in characters) of a byte offset
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let (line, start, offset) = self.line_of(offset);
+        let column = offset - start - self.extra_bytes(start, offset, |len| len - 1);
+        (line + 1, column + 1)
+    }
+
+    /// 0-based line and column (in UTF-16 code units) of a byte offset, as
+    /// editors count them
+    pub fn utf16_position(&self, offset: usize) -> (usize, usize) {
+        let (line, start, offset) = self.line_of(offset);
+        // Characters outside the Basic Multilingual Plane take two units
+        let column = offset - start - self.extra_bytes(start, offset, |len| len - len / 4 - 1);
+        (line, column)
+    }
+
+    /// 0-based line of a byte offset, with the line's start offset and the
+    /// offset clamped to the source
+    fn line_of(&self, offset: usize) -> (usize, usize, usize) {
+        let offset = offset.min(self.len);
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        (line, self.line_starts[line], offset)
+    }
+
+    /// Bytes the wide characters between `start` and `end` take beyond one
+    /// column unit each, with `extra` giving that for a UTF-8 length
+    fn extra_bytes(&self, start: usize, end: usize, extra: impl Fn(usize) -> usize) -> usize {
+        let first = self.wide_chars.partition_point(|&(at, _)| at < start);
+        self.wide_chars[first..]
+            .iter()
+            .take_while(|&&(at, _)| at < end)
+            .map(|&(_, len)| extra(len as usize))
+            .sum()
+    }
+
+    /// Text of a 1-based line, without its line terminator
+    pub fn line_text<'a>(&self, source: &'a str, line: usize) -> Option<&'a str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self.line_starts.get(line).map_or(source.len(), |&next| next - 1);
+        source.get(start..end).map(|text| text.trim_end_matches('\r'))
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Size of the source in bytes
+    pub fn size(&self) -> usize {
+        self.len
+    }
+
+    /// Number of lines a byte range spans, none for an empty range
+    pub fn lines_in(&self, span: (usize, usize)) -> usize {
+        if span.1 <= span.0 {
+            return 0;
+        }
+        let (first, _, _) = self.line_of(span.0);
+        let (last, _, _) = self.line_of(span.1 - 1);
+        last - first + 1
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// How long one phase of a run took
+///
+/// Sub-phases are named `parent/child`, e.g. `rules/unused-files`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhaseTiming {
+    pub phase: String,
+    /// Wall-clock milliseconds
+    pub ms: f64,
+}
+
+impl PhaseTiming {
+    pub fn new(phase: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            phase: phase.into(),
+            ms: duration.as_secs_f64() * 1000.0,
+        }
+    }
+}
+
+/// Run `f`, returning its result and how long it took
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Render timings as a table, sub-phases indented under their phase
+///
+/// The total only adds up top-level phases, since sub-phases such as rules
+/// may have run in parallel within them.
+pub fn table(timings: &[PhaseTiming]) -> String {
+    let label = |timing: &PhaseTiming| match timing.phase.split_once('/') {
+        Some((_, child)) => format!("  {}", child),
+        None => timing.phase.clone(),
+    };
+    let width = timings.iter().map(|timing| label(timing).len()).max().unwrap_or(0).max(5);
+
+    let mut table = String::new();
+    for timing in timings {
+        let _ = writeln!(table, "  {:<width$}  {:>9.1}ms", label(timing), timing.ms);
+    }
+    let total: f64 = timings
+        .iter()
+        .filter(|timing| !timing.phase.contains('/'))
+        .map(|timing| timing.ms)
+        .sum();
+    let _ = writeln!(table, "  {:<width$}  {:>9.1}ms", "total", total);
+    table
+}
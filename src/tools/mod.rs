@@ -0,0 +1,432 @@
+use crate::workspaces::{command_files, WorkspacePackage};
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{PropertyKey, StringLiteral, TemplateLiteral};
+use oxc_ast::visit::walk;
+use oxc_ast::Visit;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use std::path::{Path, PathBuf};
+
+/// A tool whose config files name packages it loads, such as plugins and presets
+#[derive(Debug)]
+pub struct Tool {
+    pub name: &'static str,
+    /// Package providing the tool, used whenever one of its config files exists
+    pub package: &'static str,
+    /// Config file names, relative to the package directory
+    pub config_files: &'static [&'static str],
+    /// package.json field holding inline config
+    pub manifest_field: Option<&'static str>,
+    /// Prefixes the tool adds to shorthand names, e.g. `react` for `eslint-plugin-react`
+    pub prefixes: &'static [&'static str],
+    /// Whether the tool loads its config files and the local modules they
+    /// name, e.g. a test runner's setup files or Electron Forge's entries
+    pub runs_files: bool,
+}
+
+/// Tools whose config files are scanned for package references
+pub const TOOLS: &[Tool] = &[
+    Tool {
+        name: "eslint",
+        package: "eslint",
+        config_files: &[
+            ".eslintrc",
+            ".eslintrc.json",
+            ".eslintrc.yaml",
+            ".eslintrc.yml",
+            ".eslintrc.js",
+            ".eslintrc.cjs",
+            "eslint.config.js",
+            "eslint.config.mjs",
+            "eslint.config.cjs",
+            "eslint.config.ts",
+        ],
+        manifest_field: Some("eslintConfig"),
+        prefixes: &["eslint-config-", "eslint-plugin-"],
+        runs_files: false,
+    },
+    Tool {
+        name: "prettier",
+        package: "prettier",
+        config_files: &[
+            ".prettierrc",
+            ".prettierrc.json",
+            ".prettierrc.yaml",
+            ".prettierrc.yml",
+            ".prettierrc.js",
+            ".prettierrc.cjs",
+            ".prettierrc.mjs",
+            "prettier.config.js",
+            "prettier.config.cjs",
+            "prettier.config.mjs",
+        ],
+        manifest_field: Some("prettier"),
+        prefixes: &["prettier-plugin-"],
+        runs_files: false,
+    },
+    Tool {
+        name: "babel",
+        package: "@babel/core",
+        config_files: &[
+            ".babelrc",
+            ".babelrc.json",
+            ".babelrc.js",
+            ".babelrc.cjs",
+            "babel.config.json",
+            "babel.config.js",
+            "babel.config.cjs",
+            "babel.config.mjs",
+        ],
+        manifest_field: Some("babel"),
+        // `@babel/env` is short for `@babel/preset-env`
+        prefixes: &["babel-preset-", "babel-plugin-", "preset-", "plugin-"],
+        runs_files: false,
+    },
+    Tool {
+        name: "jest",
+        package: "jest",
+        config_files: &[
+            "jest.config.js",
+            "jest.config.ts",
+            "jest.config.mjs",
+            "jest.config.cjs",
+            "jest.config.json",
+        ],
+        manifest_field: Some("jest"),
+        prefixes: &["jest-environment-", "jest-runner-", "jest-watch-"],
+        runs_files: true,
+    },
+    Tool {
+        name: "vitest",
+        package: "vitest",
+        config_files: &[
+            "vitest.config.ts",
+            "vitest.config.mts",
+            "vitest.config.js",
+            "vitest.config.mjs",
+            "vitest.config.cjs",
+            "vitest.workspace.ts",
+            "vitest.workspace.js",
+            "vitest.workspace.json",
+        ],
+        manifest_field: None,
+        prefixes: &["vitest-environment-"],
+        runs_files: true,
+    },
+    Tool {
+        name: "electron-forge",
+        package: "@electron-forge/cli",
+        config_files: &["forge.config.js", "forge.config.cjs", "forge.config.mjs", "forge.config.ts"],
+        manifest_field: None,
+        prefixes: &[],
+        runs_files: true,
+    },
+    Tool {
+        name: "electron-builder",
+        package: "electron-builder",
+        config_files: &[
+            "electron-builder.json",
+            "electron-builder.json5",
+            "electron-builder.yml",
+            "electron-builder.yaml",
+            "electron-builder.js",
+            "electron-builder.cjs",
+            "electron-builder.config.js",
+        ],
+        manifest_field: Some("build"),
+        prefixes: &[],
+        runs_files: true,
+    },
+    Tool {
+        name: "serverless",
+        package: "serverless",
+        config_files: &["serverless.yml", "serverless.yaml", "serverless.json", "serverless.js", "serverless.ts"],
+        manifest_field: None,
+        prefixes: &[],
+        runs_files: true,
+    },
+    Tool {
+        name: "postcss",
+        package: "postcss",
+        config_files: &[
+            ".postcssrc",
+            ".postcssrc.json",
+            ".postcssrc.yaml",
+            ".postcssrc.yml",
+            "postcss.config.js",
+            "postcss.config.cjs",
+            "postcss.config.mjs",
+            "postcss.config.ts",
+        ],
+        manifest_field: Some("postcss"),
+        prefixes: &[],
+        runs_files: false,
+    },
+    Tool {
+        name: "tailwind",
+        package: "tailwindcss",
+        config_files: &[
+            "tailwind.config.js",
+            "tailwind.config.cjs",
+            "tailwind.config.mjs",
+            "tailwind.config.ts",
+        ],
+        manifest_field: None,
+        prefixes: &[],
+        runs_files: false,
+    },
+    Tool {
+        name: "stylelint",
+        package: "stylelint",
+        config_files: &[
+            ".stylelintrc",
+            ".stylelintrc.json",
+            ".stylelintrc.yaml",
+            ".stylelintrc.yml",
+            ".stylelintrc.js",
+            ".stylelintrc.cjs",
+            "stylelint.config.js",
+            "stylelint.config.cjs",
+            "stylelint.config.mjs",
+        ],
+        manifest_field: Some("stylelint"),
+        prefixes: &["stylelint-config-", "stylelint-"],
+        runs_files: false,
+    },
+];
+
+/// Tool config files that exist in a package's directory
+pub fn config_files(package: &WorkspacePackage) -> Vec<PathBuf> {
+    TOOLS
+        .iter()
+        .flat_map(|tool| tool.config_files.iter())
+        .map(|name| package.dir.join(name))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Package names a package's tool config may refer to, each with the file
+/// referring to it: its config files, or package.json for inline config
+///
+/// Shorthand names expand to every name the tool could mean, so callers
+/// should only count names that are actually declared as dependencies.
+pub fn referenced_packages(package: &WorkspacePackage) -> Vec<(String, PathBuf)> {
+    let manifest: Option<serde_json::Value> = std::fs::read_to_string(&package.manifest)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+
+    let mut references = Vec::new();
+    for tool in TOOLS {
+        let inline = tool
+            .manifest_field
+            .and_then(|field| manifest.as_ref()?.get(field))
+            .map(|value| (package.manifest.clone(), json_strings(value)));
+
+        let files = tool
+            .config_files
+            .iter()
+            .map(|name| package.dir.join(name))
+            .filter(|path| path.is_file())
+            .map(|path| {
+                let strings = config_strings(&path).unwrap_or_default();
+                (path, strings)
+            });
+
+        for (file, strings) in inline.into_iter().chain(files) {
+            references.push((tool.package.to_string(), file.clone()));
+            for string in &strings {
+                references.extend(
+                    candidate_names(string, tool.prefixes)
+                        .into_iter()
+                        .map(|name| (name, file.clone())),
+                );
+            }
+        }
+    }
+
+    references
+}
+
+/// Files a package's tools load, relative to the package directory: their
+/// config files and the local modules those name, such as jest's
+/// `setupFilesAfterEnv`, vitest's `globalSetup`, or a Forge plugin's
+/// `preload`, along with the scripts of HTML pages they name
+pub fn loaded_files(package: &WorkspacePackage) -> Vec<String> {
+    let manifest: Option<serde_json::Value> = std::fs::read_to_string(&package.manifest)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+
+    let mut strings = Vec::new();
+    for tool in TOOLS.iter().filter(|tool| tool.runs_files) {
+        if let Some(value) = tool.manifest_field.and_then(|field| manifest.as_ref()?.get(field)) {
+            strings.extend(json_strings(value));
+        }
+        for name in tool.config_files {
+            let path = package.dir.join(name);
+            if path.is_file() {
+                strings.push(name.to_string());
+                strings.extend(config_strings(&path).unwrap_or_default());
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for string in &strings {
+        // Jest paths may start from `<rootDir>`, the package directory
+        let string = string.trim_start_matches("<rootDir>/");
+        let scripts = if string.ends_with(".html") {
+            html_scripts(&package.dir, string)
+        } else {
+            Vec::new()
+        };
+        for file in command_files(string).chain(scripts) {
+            if package.dir.join(&file).is_file() && !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    files
+}
+
+/// Scripts an HTML page in the package loads, e.g. a renderer's
+/// `<script type="module" src="./renderer.ts">`, relative to the package
+/// directory; `/`-rooted sources start from the package directory too
+fn html_scripts(dir: &Path, page: &str) -> Vec<String> {
+    let page = page.trim_start_matches("./");
+    let Ok(html) = std::fs::read_to_string(dir.join(page)) else {
+        return Vec::new();
+    };
+    let page_dir = Path::new(page).parent().unwrap_or(Path::new(""));
+
+    html.split("<script")
+        .skip(1)
+        .filter_map(|tag| {
+            let tag = tag.split('>').next()?;
+            let src = tag.split("src=").nth(1)?;
+            let quote = src.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+            let src = src[1..].split(quote).next()?;
+            let path = match src.strip_prefix('/') {
+                Some(rooted) => PathBuf::from(rooted),
+                None => page_dir.join(src),
+            };
+            Some(crate::resolver::normalize(&path).to_string_lossy().to_string())
+        })
+        .collect()
+}
+
+/// String values in a config file; `None` when it can't be read or parsed,
+/// which still leaves the tool itself in use
+fn config_strings(path: &Path) -> Option<Vec<String>> {
+    let content = std::fs::read_to_string(path).ok()?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("js" | "cjs" | "mjs" | "ts") => Some(script_strings(path, &content)),
+        // Extensionless rc files are JSON or YAML
+        _ => serde_json::from_str(&content)
+            .ok()
+            .or_else(|| {
+                let yaml: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+                serde_json::to_value(yaml).ok()
+            })
+            .map(|value| json_strings(&value)),
+    }
+}
+
+/// String values and object keys, which name plugins in e.g. postcss's `{ "autoprefixer": {} }`
+fn json_strings(value: &serde_json::Value) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut stack = vec![value];
+
+    while let Some(value) = stack.pop() {
+        match value {
+            serde_json::Value::String(string) => strings.push(string.clone()),
+            serde_json::Value::Array(values) => stack.extend(values),
+            serde_json::Value::Object(map) => {
+                strings.extend(map.keys().cloned());
+                stack.extend(map.values());
+            }
+            _ => {}
+        }
+    }
+
+    strings
+}
+
+/// String literals and property names in a JS or TS config, including
+/// `require` and `import` sources
+fn script_strings(path: &Path, source: &str) -> Vec<String> {
+    let Ok(source_type) = SourceType::from_path(path) else {
+        return Vec::new();
+    };
+    let allocator = Allocator::default();
+    let result = Parser::new(&allocator, source, source_type).parse();
+
+    let mut collector = StringCollector::default();
+    collector.visit_program(&result.program);
+    collector.strings
+}
+
+#[derive(Default)]
+struct StringCollector {
+    strings: Vec<String>,
+}
+
+impl<'a> Visit<'a> for StringCollector {
+    fn visit_property_key(&mut self, it: &PropertyKey<'a>) {
+        if let PropertyKey::StaticIdentifier(identifier) = it {
+            self.strings.push(identifier.name.to_string());
+        }
+        walk::walk_property_key(self, it);
+    }
+
+    fn visit_string_literal(&mut self, it: &StringLiteral<'a>) {
+        self.strings.push(it.value.to_string());
+    }
+
+    fn visit_template_literal(&mut self, it: &TemplateLiteral<'a>) {
+        if it.expressions.is_empty() {
+            self.strings.extend(
+                it.quasis
+                    .iter()
+                    .filter_map(|quasi| quasi.value.cooked.as_ref().map(|s| s.to_string())),
+            );
+        }
+    }
+}
+
+/// Package names a config string may stand for
+///
+/// `plugin:react/recommended` may mean `react` or `eslint-plugin-react`,
+/// and `@typescript-eslint` may mean `@typescript-eslint/eslint-plugin`.
+fn candidate_names(reference: &str, prefixes: &[&str]) -> Vec<String> {
+    let reference = reference
+        .trim_start_matches("plugin:")
+        .trim_start_matches("module:");
+    if reference.is_empty() || reference.starts_with('.') || reference.starts_with('/') {
+        return Vec::new();
+    }
+
+    let mut segments = reference.split('/');
+    let first = segments.next().unwrap_or_default();
+    let mut names = Vec::new();
+
+    if first.starts_with('@') {
+        // `@scope` alone names the scope's unprefixed package
+        for prefix in prefixes {
+            names.push(format!("{}/{}", first, prefix.trim_end_matches('-')));
+        }
+        if let Some(name) = segments.next() {
+            names.push(format!("{}/{}", first, name));
+            for prefix in prefixes {
+                names.push(format!("{}/{}{}", first, prefix, name));
+            }
+        }
+    } else {
+        names.push(first.to_string());
+        for prefix in prefixes {
+            names.push(format!("{}{}", prefix, first));
+        }
+    }
+
+    names
+}
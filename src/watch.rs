@@ -0,0 +1,62 @@
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+use sweepr::analyzer::Workspace;
+use sweepr::error::{PurgeError, Result};
+
+/// Quiet period that ends a burst of events, e.g. an editor saving several files
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watch the scanned roots, update the graphs for changed files, and call
+/// `on_change` after each batch of changes. Runs until interrupted.
+///
+/// A changed manifest replaces the workspace with one from `rebuild`.
+pub fn watch(
+    workspace: &mut Workspace,
+    rebuild: impl Fn() -> Result<Workspace>,
+    mut on_change: impl FnMut(&Workspace) -> Result<()>,
+) -> Result<()> {
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+    for root in workspace.scanner.roots() {
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(watch_error)?;
+    }
+
+    // The watcher stays alive for as long as events are received
+    while let Ok(event) = receiver.recv() {
+        let mut changed = HashSet::new();
+        collect_paths(event, &mut changed);
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+            collect_paths(event, &mut changed);
+        }
+
+        let affected = if workspace.manifests_changed(&changed) {
+            *workspace = rebuild()?;
+            true
+        } else {
+            workspace.apply_changes(&changed)?
+        };
+        if affected {
+            on_change(workspace)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn collect_paths(event: notify::Result<notify::Event>, changed: &mut HashSet<PathBuf>) {
+    match event {
+        // Reading files while re-parsing produces access events of its own
+        Ok(event) if matches!(event.kind, EventKind::Access(_)) => {}
+        Ok(event) => changed.extend(event.paths),
+        Err(e) => eprintln!("⚠️  Watch error: {}", e),
+    }
+}
+
+fn watch_error(e: notify::Error) -> PurgeError {
+    PurgeError::Io(std::io::Error::other(e))
+}
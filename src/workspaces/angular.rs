@@ -0,0 +1,49 @@
+use super::nx::target_files;
+use super::{merge_project, read_manifest, WorkspacePackage};
+use crate::error::Result;
+use std::path::Path;
+
+/// Add the projects of angular.json, with the source files their targets
+/// are configured with, such as `main`, `browser`, or `server`, as entry points
+///
+/// A project whose root has a package.json adds to that package; others make
+/// a package of their own, without dependencies.
+pub fn add_projects(root: &Path, packages: &mut Vec<WorkspacePackage>) -> Result<()> {
+    let manifest = root.join("angular.json");
+    let Some(workspace) = read_manifest(&manifest)? else {
+        return Ok(());
+    };
+    let Some(projects) = workspace.get("projects").and_then(|projects| projects.as_object()) else {
+        return Ok(());
+    };
+
+    for (name, project) in projects {
+        let project_root = project.get("root").and_then(|root| root.as_str()).unwrap_or("");
+        let dir = match project_root.trim_end_matches('/') {
+            "" | "." => root.to_path_buf(),
+            project_root => root.join(project_root),
+        };
+        // Older configs call targets the architect
+        let entry_points = project
+            .get("architect")
+            .or_else(|| project.get("targets"))
+            .map(|targets| target_files(root, &dir, targets))
+            .unwrap_or_default();
+
+        merge_project(
+            packages,
+            WorkspacePackage {
+                name: Some(name.clone()),
+                is_root: dir == root,
+                dir,
+                manifest: manifest.clone(),
+                dependencies: Vec::new(),
+                entry_points,
+                side_effects: Vec::new(),
+                implicit_dependencies: Vec::new(),
+            },
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use super::{linked_dir, read_manifest, workspace_alias, WorkspacePackage};
+use crate::resolver;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Conditions of an `exports` entry tried first, preferring source files
+/// over builds; the rest are tried after them in order
+const CONDITIONS: [&str; 6] = ["source", "development", "import", "module", "default", "require"];
+
+/// Directories builds are commonly written to, mapped back to `src` when a
+/// package's entry points at a build that doesn't exist
+const BUILD_DIRS: [&str; 4] = ["dist", "build", "lib", "out"];
+
+/// Packages of the project importable by name, so imports of them link to
+/// their source files instead of counting only as package imports
+#[derive(Debug, Clone, Default)]
+pub struct LocalPackages {
+    /// Directory and package.json of each package, by name
+    packages: HashMap<String, (PathBuf, Value)>,
+}
+
+impl LocalPackages {
+    /// Packages by their own names, and by the names dependencies declare
+    /// them under with `file:`, `link:`, or aliasing `workspace:` versions
+    pub fn new(packages: &[WorkspacePackage]) -> Self {
+        let manifests: Vec<(&WorkspacePackage, Value)> = packages
+            .iter()
+            .filter_map(|package| {
+                let manifest = read_manifest(&package.dir.join("package.json")).ok()??;
+                Some((package, manifest))
+            })
+            .collect();
+
+        let mut by_name: HashMap<String, (PathBuf, Value)> = manifests
+            .iter()
+            .filter_map(|(package, manifest)| {
+                Some((package.name.clone()?, (package.dir.clone(), manifest.clone())))
+            })
+            .collect();
+
+        for package in packages {
+            for (name, version, _) in &package.dependencies {
+                if by_name.contains_key(name) {
+                    continue;
+                }
+                let target = match (linked_dir(&package.dir, version), workspace_alias(version)) {
+                    (Some(dir), _) => manifests.iter().find(|(linked, _)| linked.dir == dir),
+                    (None, Some(alias)) => manifests
+                        .iter()
+                        .find(|(aliased, _)| aliased.name.as_deref() == Some(alias)),
+                    (None, None) => None,
+                };
+                if let Some((target, manifest)) = target {
+                    by_name.insert(name.clone(), (target.dir.clone(), manifest.clone()));
+                }
+            }
+        }
+
+        Self { packages: by_name }
+    }
+
+    /// The file a bare specifier such as `@repo/core` or `@repo/core/utils`
+    /// imports, when it names a local package
+    ///
+    /// Follows the package's `exports`, then `source`, `module`, and `main`
+    /// for the root, and the package directory for other subpaths. Entries
+    /// pointing at a build that doesn't exist fall back to `src`.
+    pub fn resolve(&self, specifier: &str) -> Option<PathBuf> {
+        let name = resolver::package_name(specifier)?;
+        let (dir, manifest) = self.packages.get(name)?;
+        let subpath = match &specifier[name.len()..] {
+            "" => ".".to_string(),
+            rest => format!(".{}", rest),
+        };
+
+        if let Some(exports) = manifest.get("exports") {
+            let target = export_target(exports, &subpath)?;
+            return resolve_in(dir, &target);
+        }
+        if subpath == "." {
+            return ["source", "module", "main"]
+                .iter()
+                .filter_map(|field| manifest.get(*field)?.as_str())
+                .find_map(|target| resolve_in(dir, target))
+                .or_else(|| resolve_in(dir, "index"))
+                .or_else(|| resolve_in(dir, "src/index"));
+        }
+        resolve_in(dir, &subpath)
+    }
+}
+
+/// The target `exports` maps a subpath to, e.g. `./src/utils.ts` for
+/// `./utils`, with `*` patterns filled in
+fn export_target(exports: &Value, subpath: &str) -> Option<String> {
+    let is_subpath_map = exports
+        .as_object()
+        .is_some_and(|map| map.keys().any(|key| key.starts_with('.')));
+    if !is_subpath_map {
+        return (subpath == ".").then(|| condition_target(exports)).flatten();
+    }
+
+    let map = exports.as_object()?;
+    if let Some(value) = map.get(subpath) {
+        return condition_target(value);
+    }
+    map.iter().find_map(|(key, value)| {
+        let (prefix, suffix) = key.split_once('*')?;
+        let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        Some(condition_target(value)?.replace('*', matched))
+    })
+}
+
+/// The path a conditional export resolves to, preferring source conditions
+fn condition_target(value: &Value) -> Option<String> {
+    match value {
+        Value::String(target) => Some(target.clone()),
+        Value::Array(targets) => targets.iter().find_map(condition_target),
+        Value::Object(conditions) => CONDITIONS
+            .iter()
+            .filter_map(|condition| conditions.get(*condition))
+            .chain(conditions.iter().filter(|(key, _)| key.as_str() != "types").map(|(_, value)| value))
+            .find_map(condition_target),
+        _ => None,
+    }
+}
+
+/// Resolve a path relative to a package directory like a relative import,
+/// mapping a missing build output back to its source under `src`
+fn resolve_in(dir: &Path, target: &str) -> Option<PathBuf> {
+    let target = target.trim_start_matches("./");
+    let manifest = dir.join("package.json");
+    resolver::resolve_relative(&manifest, &format!("./{}", target)).or_else(|| {
+        let (first, rest) = target.split_once('/')?;
+        if !BUILD_DIRS.contains(&first) {
+            return None;
+        }
+        let source = Path::new(rest).with_extension("");
+        resolver::resolve_relative(&manifest, &format!("./src/{}", source.display()))
+    })
+}
@@ -0,0 +1,327 @@
+mod angular;
+mod local;
+mod nx;
+mod turbo;
+
+pub use local::LocalPackages;
+
+use crate::error::{PurgeError, Result};
+use crate::graph::DependencyKind;
+use crate::resolver;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// A package of the project: the root package or a workspace member
+#[derive(Debug, Clone)]
+pub struct WorkspacePackage {
+    pub name: Option<String>,
+    pub dir: PathBuf,
+    /// package.json, or project.json for an Nx project without one
+    pub manifest: PathBuf,
+    /// `(name, version, kind)` for every declared dependency
+    pub dependencies: Vec<(String, String, DependencyKind)>,
+    /// Files named by `main`, `module`, `types`, `bin`, and `exports`,
+    /// relative to `dir`
+    pub entry_points: Vec<String>,
+    /// Globs, relative to `dir`, of files the `sideEffects` field says have
+    /// side effects: `**` for `true`, none when it is `false` or missing
+    pub side_effects: Vec<String>,
+    /// Packages this one depends on without importing them, from Nx's
+    /// `implicitDependencies`
+    pub implicit_dependencies: Vec<String>,
+    pub is_root: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct PnpmWorkspace {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+/// Find the root package and every workspace member declared through
+/// `pnpm-workspace.yaml` or the `workspaces` field of the root package.json
+///
+/// Angular and Nx projects count as packages too, and their targets and
+/// Turborepo tasks add the files they run to their package's entry points.
+/// So do directories inside the project that `file:` and `link:`
+/// dependencies point at.
+pub fn discover_packages(root: &Path) -> Result<Vec<WorkspacePackage>> {
+    let mut packages = Vec::new();
+
+    let root_manifest = read_manifest(&root.join("package.json"))?;
+    if let Some(json) = &root_manifest {
+        packages.push(package_from_manifest(root, json, true));
+    }
+
+    let patterns = workspace_patterns(root, root_manifest.as_ref())?;
+    if !patterns.is_empty() {
+        let (include, exclude) = build_globsets(&patterns)?;
+        for dir in dirs_containing(root, "package.json")? {
+            let relative = dir.strip_prefix(root).unwrap_or(&dir);
+            if !include.is_match(relative) || exclude.is_match(relative) {
+                continue;
+            }
+
+            if let Some(json) = read_manifest(&dir.join("package.json"))? {
+                packages.push(package_from_manifest(&dir, &json, false));
+            }
+        }
+    }
+
+    angular::add_projects(root, &mut packages)?;
+    nx::add_projects(root, &mut packages)?;
+    turbo::add_task_entries(root, &mut packages)?;
+    add_linked_packages(root, &mut packages)?;
+
+    Ok(packages)
+}
+
+/// Add the packages that `file:` and `link:` dependencies point at inside
+/// the project, so their own dependencies and entry points are checked
+///
+/// Linked packages may link others in turn; ones outside the project and
+/// tarballs are left as the packages they are installed as.
+fn add_linked_packages(root: &Path, packages: &mut Vec<WorkspacePackage>) -> Result<()> {
+    let root = resolver::normalize(root);
+    let mut index = 0;
+    while index < packages.len() {
+        let package = &packages[index];
+        let dirs: Vec<PathBuf> = package
+            .dependencies
+            .iter()
+            .filter_map(|(_, version, _)| linked_dir(&package.dir, version))
+            .collect();
+
+        for dir in dirs {
+            if !dir.starts_with(&root) || packages.iter().any(|package| package.dir == dir) {
+                continue;
+            }
+            if let Some(json) = read_manifest(&dir.join("package.json"))? {
+                packages.push(package_from_manifest(&dir, &json, false));
+            }
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Directory a `file:`, `link:`, or `portal:` dependency version points at,
+/// relative to the directory of the package declaring it
+pub(crate) fn linked_dir(dir: &Path, version: &str) -> Option<PathBuf> {
+    let path = ["file:", "link:", "portal:"]
+        .iter()
+        .find_map(|protocol| version.strip_prefix(protocol))?;
+    Some(resolver::normalize(&dir.join(path)))
+}
+
+/// Package a `workspace:` dependency version names, e.g. `@repo/core` for
+/// the alias `workspace:@repo/core@*`; plain ranges name the dependency itself
+pub(crate) fn workspace_alias(version: &str) -> Option<&str> {
+    let range = version.strip_prefix("workspace:")?;
+    range.rsplit_once('@').map(|(name, _)| name).filter(|name| !name.is_empty())
+}
+
+/// Workspace globs from pnpm-workspace.yaml, falling back to package.json
+fn workspace_patterns(root: &Path, manifest: Option<&serde_json::Value>) -> Result<Vec<String>> {
+    let pnpm_path = root.join("pnpm-workspace.yaml");
+    if pnpm_path.exists() {
+        let content = std::fs::read_to_string(&pnpm_path).map_err(PurgeError::Io)?;
+        let pnpm: PnpmWorkspace = serde_yaml::from_str(&content)
+            .map_err(|e| PurgeError::Config(format!("Invalid pnpm-workspace.yaml: {}", e)))?;
+        return Ok(pnpm.packages);
+    }
+
+    // Either `"workspaces": [..]` or Yarn's `"workspaces": { "packages": [..] }`
+    let workspaces = manifest.and_then(|json| json.get("workspaces"));
+    let list = workspaces
+        .and_then(|w| w.as_array())
+        .or_else(|| workspaces?.get("packages")?.as_array());
+
+    Ok(list
+        .into_iter()
+        .flatten()
+        .filter_map(|pattern| pattern.as_str().map(str::to_string))
+        .collect())
+}
+
+/// Include and exclude (`!`-prefixed) globs matching package directories
+fn build_globsets(patterns: &[String]) -> Result<(GlobSet, GlobSet)> {
+    let mut include = GlobSetBuilder::new();
+    let mut exclude = GlobSetBuilder::new();
+
+    for pattern in patterns {
+        let (builder, pattern) = match pattern.strip_prefix('!') {
+            Some(negated) => (&mut exclude, negated),
+            None => (&mut include, pattern.as_str()),
+        };
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| PurgeError::Config(format!("Invalid workspace pattern: {}", e)))?;
+        builder.add(glob);
+    }
+
+    let build = |builder: GlobSetBuilder| {
+        builder
+            .build()
+            .map_err(|e| PurgeError::Config(e.to_string()))
+    };
+    Ok((build(include)?, build(exclude)?))
+}
+
+/// Directories below the root that contain a file named `file_name`
+fn dirs_containing(root: &Path, file_name: &str) -> Result<Vec<PathBuf>> {
+    let mut overrides = OverrideBuilder::new(root);
+    overrides
+        .add("!node_modules")
+        .map_err(|e| PurgeError::Config(e.to_string()))?;
+    let overrides = overrides
+        .build()
+        .map_err(|e| PurgeError::Config(e.to_string()))?;
+
+    let walker = WalkBuilder::new(root)
+        .require_git(false)
+        .overrides(overrides)
+        .build();
+
+    let mut dirs: Vec<PathBuf> = walker
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name() == file_name && entry.depth() > 1)
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .collect();
+    dirs.sort();
+
+    Ok(dirs)
+}
+
+fn read_manifest(path: &Path) -> Result<Option<serde_json::Value>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = std::fs::read_to_string(path).map_err(PurgeError::Io)?;
+    let json = serde_json::from_str(&content).map_err(|e| {
+        PurgeError::Config(format!("Invalid package.json ({}): {}", path.display(), e))
+    })?;
+
+    Ok(Some(json))
+}
+
+fn package_from_manifest(dir: &Path, json: &serde_json::Value, is_root: bool) -> WorkspacePackage {
+    let mut dependencies = Vec::new();
+
+    for kind in DependencyKind::ALL {
+        if let Some(deps) = json.get(kind.field()).and_then(|d| d.as_object()) {
+            for (name, version) in deps {
+                if let Some(version_str) = version.as_str() {
+                    dependencies.push((name.clone(), version_str.to_string(), kind));
+                }
+            }
+        }
+    }
+
+    let mut entry_points = Vec::new();
+    for field in ["main", "module", "types", "typings", "bin", "exports"] {
+        if let Some(value) = json.get(field) {
+            collect_entry_paths(value, &mut entry_points);
+        }
+    }
+
+    WorkspacePackage {
+        name: json
+            .get("name")
+            .and_then(|name| name.as_str())
+            .map(str::to_string),
+        dir: dir.to_path_buf(),
+        manifest: dir.join("package.json"),
+        dependencies,
+        entry_points,
+        side_effects: side_effect_globs(json.get("sideEffects")),
+        implicit_dependencies: json
+            .pointer("/nx/implicitDependencies")
+            .map(nx::implicit_dependencies)
+            .unwrap_or_default(),
+        is_root,
+    }
+}
+
+/// Globs for the `sideEffects` field, which bundlers match like `.gitignore`
+/// entries: a pattern without a `/` matches at any depth
+fn side_effect_globs(value: Option<&serde_json::Value>) -> Vec<String> {
+    match value {
+        Some(serde_json::Value::Bool(true)) => vec!["**".to_string()],
+        Some(serde_json::Value::Array(patterns)) => patterns
+            .iter()
+            .filter_map(|pattern| pattern.as_str())
+            .map(|pattern| match pattern.strip_prefix("./") {
+                Some(relative) => relative.to_string(),
+                None if !pattern.contains('/') => format!("**/{}", pattern),
+                None => pattern.to_string(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Source file paths in a manifest field, including nested `bin` and
+/// `exports` maps; wildcard subpath patterns and non-code files are skipped
+fn collect_entry_paths(value: &serde_json::Value, paths: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(path) => {
+            let is_code = Path::new(path).extension().is_none() || is_code_file(path);
+            let path = path.trim_start_matches("./").to_string();
+            if is_code && !path.contains('*') && !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                collect_entry_paths(value, paths);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for value in map.values() {
+                collect_entry_paths(value, paths);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Add the entry points and implicit dependencies of a project from a
+/// workspace tool to the package in the same directory, or add the project
+/// as a package of its own
+fn merge_project(packages: &mut Vec<WorkspacePackage>, project: WorkspacePackage) {
+    let Some(package) = packages.iter_mut().find(|package| package.dir == project.dir) else {
+        packages.push(project);
+        return;
+    };
+    for entry in project.entry_points {
+        if !package.entry_points.contains(&entry) {
+            package.entry_points.push(entry);
+        }
+    }
+    package.implicit_dependencies.extend(project.implicit_dependencies);
+}
+
+fn is_code_file(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext, "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts"))
+}
+
+/// Source files a command runs or passes along, e.g. `scripts/build.ts` in
+/// `tsx scripts/build.ts --watch`
+pub(crate) fn command_files(command: &str) -> impl Iterator<Item = String> + '_ {
+    command
+        .split(|c: char| c.is_whitespace() || matches!(c, '&' | '|' | ';' | '='))
+        .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+        .filter(|word| !word.starts_with('-') && !word.contains("://") && is_code_file(word))
+        .map(|word| word.trim_start_matches("./").to_string())
+}
@@ -0,0 +1,116 @@
+use super::{command_files, dirs_containing, merge_project, read_manifest, WorkspacePackage};
+use crate::error::Result;
+use std::path::Path;
+
+/// Add the projects of an Nx workspace, one per project.json, with the
+/// source files their targets are configured with as entry points
+///
+/// A project.json next to a package.json adds to that package; one without
+/// makes a package of its own, without dependencies.
+pub fn add_projects(root: &Path, packages: &mut Vec<WorkspacePackage>) -> Result<()> {
+    if !root.join("nx.json").is_file() {
+        return Ok(());
+    }
+
+    let mut dirs = dirs_containing(root, "project.json")?;
+    if root.join("project.json").is_file() {
+        dirs.insert(0, root.to_path_buf());
+    }
+
+    for dir in dirs {
+        let manifest = dir.join("project.json");
+        let Some(project) = read_manifest(&manifest)? else {
+            continue;
+        };
+
+        let entry_points = project
+            .get("targets")
+            .map(|targets| target_files(root, &dir, targets))
+            .unwrap_or_default();
+        let implicit = project
+            .get("implicitDependencies")
+            .map(implicit_dependencies)
+            .unwrap_or_default();
+
+        merge_project(
+            packages,
+            WorkspacePackage {
+                name: project
+                    .get("name")
+                    .and_then(|name| name.as_str())
+                    .map(str::to_string),
+                is_root: dir == root,
+                dir,
+                manifest,
+                dependencies: Vec::new(),
+                entry_points,
+                side_effects: Vec::new(),
+                implicit_dependencies: implicit,
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Project names in an `implicitDependencies` list; `!`-negated ones remove
+/// a dependency Nx would otherwise infer, so they are left out
+pub fn implicit_dependencies(value: &serde_json::Value) -> Vec<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|name| name.as_str())
+        .filter(|name| !name.starts_with('!'))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Source files named in the options of a project's targets, e.g. `main`
+/// of a build or `jestConfig` of a test, and in the commands they run,
+/// relative to the project directory
+///
+/// Paths in Nx and Angular options are relative to the workspace root, Nx's
+/// with `{projectRoot}` and `{workspaceRoot}` tokens; files outside the
+/// project are left to the project they belong to.
+pub(super) fn target_files(root: &Path, dir: &Path, targets: &serde_json::Value) -> Vec<String> {
+    let Some(targets) = targets.as_object() else {
+        return Vec::new();
+    };
+    let project_root = dir.strip_prefix(root).unwrap_or(dir).to_string_lossy().to_string();
+
+    let mut values = Vec::new();
+    for target in targets.values() {
+        for field in ["options", "configurations"] {
+            if let Some(value) = target.get(field) {
+                collect_strings(value, &mut values);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    for value in &values {
+        let value = value
+            .replace("{projectRoot}", &project_root)
+            .replace("{workspaceRoot}/", "");
+        // A path option is a command of one word
+        for path in command_files(&value) {
+            if let Ok(relative) = root.join(&path).strip_prefix(dir) {
+                let relative = relative.to_string_lossy().to_string();
+                if !files.contains(&relative) {
+                    files.push(relative);
+                }
+            }
+        }
+    }
+    files
+}
+
+fn collect_strings(value: &serde_json::Value, strings: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(string) => strings.push(string.clone()),
+        serde_json::Value::Array(values) => values.iter().for_each(|value| collect_strings(value, strings)),
+        serde_json::Value::Object(map) => map.values().for_each(|value| collect_strings(value, strings)),
+        _ => {}
+    }
+}
@@ -0,0 +1,67 @@
+use super::{command_files, read_manifest, WorkspacePackage};
+use crate::error::Result;
+use std::path::Path;
+
+/// Add the source files that Turborepo tasks run to their package's entry
+/// points
+///
+/// A task runs the package.json script of the same name, so files its
+/// command names, e.g. `scripts/build.ts` in `tsx scripts/build.ts`, are
+/// entry points. Tasks come from the root turbo.json and a package's own
+/// turbo.json; `package#task` tasks only apply to that package.
+pub fn add_task_entries(root: &Path, packages: &mut [WorkspacePackage]) -> Result<()> {
+    let Some(root_turbo) = read_manifest(&root.join("turbo.json"))? else {
+        return Ok(());
+    };
+    let root_tasks = task_names(&root_turbo);
+
+    for package in packages.iter_mut() {
+        let Some(manifest) = read_manifest(&package.dir.join("package.json"))? else {
+            continue;
+        };
+        let Some(scripts) = manifest.get("scripts").and_then(|scripts| scripts.as_object()) else {
+            continue;
+        };
+
+        let mut tasks = root_tasks.clone();
+        if !package.is_root {
+            if let Some(turbo) = read_manifest(&package.dir.join("turbo.json"))? {
+                tasks.extend(task_names(&turbo));
+            }
+        }
+
+        for (scope, task) in &tasks {
+            if scope.as_ref().is_some_and(|scope| package.name.as_ref() != Some(scope)) {
+                continue;
+            }
+            let Some(script) = scripts.get(task).and_then(|script| script.as_str()) else {
+                continue;
+            };
+            for file in command_files(script) {
+                if !package.entry_points.contains(&file) {
+                    package.entry_points.push(file);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Tasks of a turbo.json, `tasks` in Turborepo 2 and `pipeline` before, each
+/// with the package it is scoped to
+fn task_names(turbo: &serde_json::Value) -> Vec<(Option<String>, String)> {
+    let tasks = turbo
+        .get("tasks")
+        .or_else(|| turbo.get("pipeline"))
+        .and_then(|tasks| tasks.as_object());
+
+    tasks
+        .into_iter()
+        .flat_map(|tasks| tasks.keys())
+        .map(|name| match name.rsplit_once('#') {
+            Some((scope, task)) => (Some(scope.to_string()), task.to_string()),
+            None => (None, name.clone()),
+        })
+        .collect()
+}
@@ -25,3 +25,3696 @@ fn test_dependencies_fixture() {
     // TODO: Run actual sweepr analysis and verify results
     // For now, just verify the fixture structure
 }
+
+/// Run the sweepr binary inside a fixture directory
+fn run_sweepr(fixture: &str, args: &[&str]) -> std::process::Output {
+    run_sweepr_in(&PathBuf::from("tests/fixtures").join(fixture), args)
+}
+
+fn run_sweepr_in(dir: &std::path::Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new(env!("CARGO_BIN_EXE_sweepr"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("failed to run sweepr")
+}
+
+/// Create a throwaway project from `(path, contents)` pairs
+fn write_project(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (path, contents) in files {
+        let path = dir.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, contents).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn test_html_report_is_written_to_output_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let report = dir.path().join("report.html");
+
+    let output = run_sweepr(
+        "dependencies",
+        &["check", "--entry", "entry.ts", "--format", "html", "--output", report.to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    let html = std::fs::read_to_string(&report).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("unused-module.ts"));
+    assert!(html.contains("const GRAPH = "));
+}
+
+#[test]
+fn test_csv_report_quotes_fields_with_delimiters_quotes_and_line_breaks() {
+    let project = write_project(&[
+        ("src/index.ts", "export {};\n"),
+        ("src/a,b.ts", "export {};\n"),
+        ("src/say \"hi\".ts", "export {};\n"),
+        ("src/two\nlines.ts", "export {};\n"),
+    ]);
+
+    let output = run_sweepr_in(
+        project.path(),
+        &["check", "--entry", "src/index.ts", "--format", "csv", "--no-cache", "--sort", "path"],
+    );
+    let csv = String::from_utf8_lossy(&output.stdout);
+
+    // Split records and fields the way a CSV reader does
+    let mut records = vec![vec![String::new()]];
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+    while let Some(c) = chars.next() {
+        let record = records.last_mut().unwrap();
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                record.last_mut().unwrap().push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => record.push(String::new()),
+            '\n' if !quoted => records.push(vec![String::new()]),
+            _ => record.last_mut().unwrap().push(c),
+        }
+    }
+    records.pop();
+
+    assert_eq!(records[0], ["rule", "path", "symbol", "line", "column", "code"]);
+    let paths: Vec<String> = records[1..]
+        .iter()
+        .inspect(|record| assert_eq!(record.len(), 6, "{:?}", record))
+        .map(|record| record[1].clone())
+        .collect();
+    let expected: Vec<String> = ["src/a,b.ts", "src/say \"hi\".ts", "src/two\nlines.ts"]
+        .iter()
+        .map(|path| project.path().join(path).to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(paths, expected);
+}
+
+#[test]
+fn test_compact_and_quiet_reports_print_one_plain_line_per_finding() {
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\n"),
+        ("src/stale.ts", "console.log(1);\n"),
+    ]);
+    let root = project.path().display();
+
+    let output = run_sweepr_in(project.path(), &["check", "--entry", "src/index.ts", "--format", "compact", "--no-cache"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!(
+            "{0}/src/lib.ts:2:14 SWP002 unused-exports Unused export 'unused'\n\
+             {0}/src/stale.ts:1:1 SWP004 unused-files Unused file\n",
+            root
+        )
+    );
+
+    let output = run_sweepr_in(project.path(), &["check", "--entry", "src/index.ts", "--quiet", "--no-cache"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        format!(
+            "Unused exports (1) [SWP002]\n  unused in {0}/src/lib.ts:2\n\
+             Unused files (1) [SWP004]\n  {0}/src/stale.ts\n",
+            root
+        )
+    );
+}
+
+#[test]
+fn test_html_report_leaves_out_graphs_too_large_to_lay_out() {
+    let dir = write_project(&[("package.json", "{ \"name\": \"app\" }\n"), ("index.ts", "export {};\n")]);
+    for index in 0..1500 {
+        std::fs::write(dir.path().join(format!("file{}.ts", index)), "export const value = 1;\n").unwrap();
+    }
+    let report = dir.path().join("report.html");
+
+    let output = run_sweepr_in(
+        dir.path(),
+        &["check", "--entry", "index.ts", "--format", "html", "--output", report.to_str().unwrap()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    // The findings are all there, only the graph is not drawn
+    let html = std::fs::read_to_string(&report).unwrap();
+    assert!(html.contains("file1499.ts"));
+    assert!(html.contains("The graph of 1501 files is too large to draw here"));
+    assert!(!html.contains("const GRAPH = "));
+}
+
+#[test]
+fn test_fail_on_only_considers_selected_rules() {
+    // The fixture reports unused dependencies and files, but no unused exports
+    let output = run_sweepr("dependencies", &["check", "--entry", "entry.ts", "--quiet"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run_sweepr(
+        "dependencies",
+        &["check", "--entry", "entry.ts", "--quiet", "--fail-on", "unused-exports"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = run_sweepr(
+        "dependencies",
+        &["check", "--entry", "entry.ts", "--quiet", "--fail-on", "unused-exports,unused-files"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_max_issues_only_fails_beyond_the_budget() {
+    let project = write_project(&[
+        ("src/index.ts", "import './lib';\n"),
+        ("src/lib.ts", "export const a = 1;\nexport const b = 2;\n"),
+    ]);
+
+    let check = |budget: &str| {
+        run_sweepr_in(project.path(), &["check", "--quiet", "--no-cache", "--max-issues", budget])
+            .status
+            .code()
+    };
+    assert_eq!(check("2"), Some(0));
+    assert_eq!(check("1"), Some(1));
+}
+
+#[test]
+fn test_filter_reports_only_matching_files() {
+    let project = write_project(&[
+        ("src/index.ts", "import './components/button';\nimport './lib';\n"),
+        ("src/components/button.ts", "export const Button = 1;\n"),
+        ("src/lib.ts", "export const helper = 1;\n"),
+        ("src/components/stale.ts", "export const Stale = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(
+        project.path(),
+        &["check", "--format", "json", "--no-cache", "--filter", "src/components/**"],
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let exports: Vec<&str> = json["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(exports, ["Button"]);
+    // button.ts is still reachable through index.ts, outside the filter
+    let files = json["unused_files"].as_array().unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(files[0]["path"].as_str().unwrap().ends_with("stale.ts"));
+}
+
+#[test]
+fn test_changed_reports_only_files_modified_since_ref() {
+    let project = write_project(&[
+        ("src/index.ts", "import './old';\nimport './edited';\n"),
+        ("src/old.ts", "export const old = 1;\n"),
+        ("src/edited.ts", "export const kept = 1;\n"),
+    ]);
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .current_dir(project.path())
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "base"]);
+
+    std::fs::write(project.path().join("src/edited.ts"), "export const kept = 1;\nexport const added = 2;\n").unwrap();
+    std::fs::write(project.path().join("src/new.ts"), "export const fresh = 1;\n").unwrap();
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache", "--changed", "HEAD"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let mut exports: Vec<&str> = json["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    exports.sort_unstable();
+    assert_eq!(exports, ["added", "kept"]);
+    let files = json["unused_files"].as_array().unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(files[0]["path"].as_str().unwrap().ends_with("new.ts"));
+}
+
+#[test]
+fn test_since_reports_only_findings_introduced_after_ref() {
+    let project = write_project(&[
+        ("app/package.json", r#"{ "name": "app", "main": "src/index.ts" }"#),
+        ("app/src/index.ts", "export const old = 1;\n"),
+        ("app/src/stale.ts", "console.log('stale');\n"),
+    ]);
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .current_dir(project.path())
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "base"]);
+
+    // Moving the old export down doesn't make it new
+    let app = project.path().join("app");
+    std::fs::write(app.join("src/index.ts"), "\n\nexport const old = 1;\nexport const fresh = 2;\n").unwrap();
+    std::fs::write(app.join("src/new.ts"), "console.log('new');\n").unwrap();
+
+    let output = run_sweepr_in(&app, &["check", "--format", "json", "--no-cache", "--since", "HEAD"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let exports: Vec<&str> = json["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(exports, ["fresh"]);
+    let files = json["unused_files"].as_array().unwrap();
+    assert_eq!(files.len(), 1, "{}", json);
+    assert!(files[0]["path"].as_str().unwrap().ends_with("new.ts"));
+
+    // Nothing new once the changes are gone, and the checkout is cleaned up
+    std::fs::remove_file(app.join("src/new.ts")).unwrap();
+    std::fs::write(app.join("src/index.ts"), "export const old = 1;\n").unwrap();
+    let output = run_sweepr_in(&app, &["check", "--quiet", "--no-cache", "--since", "HEAD"]);
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stdout));
+    let worktrees = std::process::Command::new("git")
+        .current_dir(project.path())
+        .args(["worktree", "list"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&worktrees.stdout).lines().count(), 1);
+}
+
+#[test]
+fn test_since_reads_template_files_of_the_earlier_checkout() {
+    let project = write_project(&[
+        (
+            "angular.json",
+            r#"{ "projects": { "site": { "root": "", "architect": {
+                "build": { "options": { "browser": "src/main.ts" } }
+            } } } }"#,
+        ),
+        ("src/main.ts", "import { AppComponent } from './app.component';\nimport './card';\nbootstrap(AppComponent);\n"),
+        (
+            "src/app.component.ts",
+            "@Component({ selector: 'app-root', templateUrl: './app.component.html' })\nexport class AppComponent {}\n",
+        ),
+        ("src/app.component.html", "<app-card></app-card>\n"),
+        ("src/card.ts", "@Component({ selector: 'app-card', template: '' })\nexport class CardComponent {}\n"),
+    ]);
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .current_dir(project.path())
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "base"]);
+
+    // The card was used by the template before, so its export is newly unused
+    std::fs::write(project.path().join("src/app.component.html"), "<p>empty</p>\n").unwrap();
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache", "--since", "HEAD"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused: Vec<&str> = stdout.lines().filter(|line| line.contains("unused-")).collect();
+    assert_eq!(unused.len(), 1, "{}", stdout);
+    assert!(unused[0].contains("'CardComponent'"), "{}", stdout);
+}
+
+#[test]
+fn test_threads_flag_limits_parsing_without_changing_results() {
+    let project = write_project(&[
+        ("src/index.ts", "import './lib';\n"),
+        ("src/lib.ts", "export const a = 1;\n"),
+    ]);
+
+    let check = |extra: &[&str]| {
+        let mut args = vec!["check", "--format", "json", "--no-cache"];
+        args.extend_from_slice(extra);
+        run_sweepr_in(project.path(), &args)
+    };
+    assert_eq!(check(&["--threads", "1"]).stdout, check(&[]).stdout);
+    assert_eq!(check(&["--threads", "0"]).status.code(), Some(3));
+}
+
+#[test]
+fn test_stdin_analyzes_unsaved_buffer_for_one_file() {
+    use std::io::Write;
+
+    let project = write_project(&[
+        ("src/index.ts", "import { a } from './lib';\nconsole.log(a);\n"),
+        ("src/lib.ts", "export const a = 1;\n"),
+        ("src/other.ts", "export const stale = 1;\n"),
+    ]);
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_sweepr"))
+        .current_dir(project.path())
+        .args(["check", "--format", "json", "--no-cache", "--stdin", "--stdin-path", "./src/lib.ts"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"export const a = 1;\nexport const draft = 2;\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let exports: Vec<&str> = json["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(exports, ["draft"]);
+    // other.ts is unused too, but only the buffer's findings are reported
+    assert!(json["unused_files"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_stdin_path_with_unsupported_extension_is_rejected() {
+    let project = write_project(&[("src/index.ts", "console.log(1);\n")]);
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_sweepr"))
+        .current_dir(project.path())
+        .args(["check", "--no-cache", "--stdin", "--stdin-path", "src/notes.txt"])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("--stdin-path src/notes.txt is not a JavaScript"), "{}", stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+}
+
+/// Send one Language Server Protocol message
+fn send_lsp(stdin: &mut impl std::io::Write, message: serde_json::Value) {
+    let body = message.to_string();
+    write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+    stdin.flush().unwrap();
+}
+
+/// Read Language Server Protocol messages until a diagnostics notification for `uri`
+fn read_diagnostics(stdout: &mut impl std::io::BufRead, uri: &str) -> Vec<serde_json::Value> {
+    loop {
+        let mut length = 0;
+        let mut header = String::new();
+        while stdout.read_line(&mut header).unwrap() > 2 {
+            if let Some(value) = header.trim().strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+            header.clear();
+        }
+
+        let mut body = vec![0; length];
+        stdout.read_exact(&mut body).unwrap();
+        let message: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        if message["method"] == "textDocument/publishDiagnostics" && message["params"]["uri"] == uri {
+            return message["params"]["diagnostics"].as_array().unwrap().clone();
+        }
+    }
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_for_open_documents() {
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\n"),
+        ("src/orphan.ts", "export {};\n"),
+    ]);
+    let root = project.path().canonicalize().unwrap();
+    let uri = |path: &str| format!("file://{}", root.join(path).display());
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_sweepr"))
+        .args(["lsp", "--no-cache"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+
+    send_lsp(&mut stdin, serde_json::json!({
+        "jsonrpc": "2.0", "id": 1, "method": "initialize",
+        "params": { "rootUri": format!("file://{}", root.display()), "capabilities": {} }
+    }));
+    send_lsp(&mut stdin, serde_json::json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }));
+
+    // Unused files are reported without being opened
+    let diagnostics = read_diagnostics(&mut stdout, &uri("src/orphan.ts"));
+    assert_eq!(diagnostics[0]["message"], "Unused file");
+
+    send_lsp(&mut stdin, serde_json::json!({
+        "jsonrpc": "2.0", "method": "textDocument/didOpen",
+        "params": { "textDocument": {
+            "uri": uri("src/lib.ts"), "languageId": "typescript", "version": 1,
+            "text": "export const used = 1;\nexport const unused = 2;\n"
+        } }
+    }));
+    let diagnostics = read_diagnostics(&mut stdout, &uri("src/lib.ts"));
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0]["message"], "Unused export 'unused'");
+    assert_eq!(diagnostics[0]["range"]["start"], serde_json::json!({ "line": 1, "character": 13 }));
+
+    // Unsaved edits are analyzed as they are typed
+    send_lsp(&mut stdin, serde_json::json!({
+        "jsonrpc": "2.0", "method": "textDocument/didChange",
+        "params": {
+            "textDocument": { "uri": uri("src/lib.ts"), "version": 2 },
+            "contentChanges": [{ "text": "export const used = 1;\n" }]
+        }
+    }));
+    assert!(read_diagnostics(&mut stdout, &uri("src/lib.ts")).is_empty());
+
+    send_lsp(&mut stdin, serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }));
+    send_lsp(&mut stdin, serde_json::json!({ "jsonrpc": "2.0", "method": "exit" }));
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn test_daemon_answers_json_rpc_requests_from_memory() {
+    use std::io::{BufRead, Write};
+
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\n"),
+    ]);
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_sweepr"))
+        .current_dir(project.path())
+        .args(["daemon", "--no-cache"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+    let mut request = |request: serde_json::Value| {
+        writeln!(stdin, "{}", request).unwrap();
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+        serde_json::from_str::<serde_json::Value>(&line).unwrap()
+    };
+    let unused_exports = |response: &serde_json::Value| response["result"]["unused_exports"].as_array().unwrap().len();
+
+    let response = request(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "analyze" }));
+    assert_eq!(response["id"], 1);
+    assert_eq!(unused_exports(&response), 1);
+
+    std::fs::write(project.path().join("src/lib.ts"), "export const used = 1;\n").unwrap();
+    let response = request(serde_json::json!({
+        "jsonrpc": "2.0", "id": 2, "method": "invalidate", "params": { "file": "src/lib.ts" }
+    }));
+    assert_eq!(response["result"]["affected"], true);
+    let response = request(serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "analyze" }));
+    assert_eq!(unused_exports(&response), 0);
+
+    let response = request(serde_json::json!({
+        "jsonrpc": "2.0", "id": 4, "method": "why", "params": { "target": "used" }
+    }));
+    assert_eq!(response["result"]["found"], true);
+    assert!(response["result"]["explanation"].as_str().unwrap().contains("used by src/index.ts"));
+
+    let response = request(serde_json::json!({ "jsonrpc": "2.0", "id": 5, "method": "format" }));
+    assert_eq!(response["error"]["code"], -32601);
+
+    request(serde_json::json!({ "jsonrpc": "2.0", "id": 6, "method": "shutdown" }));
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn test_daemon_reloads_dependencies_after_a_manifest_edit() {
+    use std::io::{BufRead, Write};
+
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "import pad from 'left-pad';\nconsole.log(pad);\n"),
+    ]);
+
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_sweepr"))
+        .current_dir(project.path())
+        .args(["daemon", "--no-cache"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    let mut stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+    let mut request = |request: serde_json::Value| {
+        writeln!(stdin, "{}", request).unwrap();
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+        serde_json::from_str::<serde_json::Value>(&line).unwrap()
+    };
+    let unlisted = |response: &serde_json::Value| response["result"]["unlisted_dependencies"].as_array().unwrap().len();
+
+    let response = request(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": "analyze" }));
+    assert_eq!(unlisted(&response), 1, "{}", response);
+
+    std::fs::write(
+        project.path().join("package.json"),
+        r#"{ "name": "app", "main": "src/index.ts", "dependencies": { "left-pad": "^1.3.0" } }"#,
+    )
+    .unwrap();
+    let response = request(serde_json::json!({
+        "jsonrpc": "2.0", "id": 2, "method": "invalidate", "params": { "file": "package.json" }
+    }));
+    assert_eq!(response["result"]["affected"], true, "{}", response);
+    let response = request(serde_json::json!({ "jsonrpc": "2.0", "id": 3, "method": "analyze" }));
+    assert_eq!(unlisted(&response), 0, "{}", response);
+    assert!(response["result"]["unused_dependencies"].as_array().unwrap().is_empty(), "{}", response);
+
+    request(serde_json::json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }));
+    assert!(child.wait().unwrap().success());
+}
+
+#[test]
+fn test_timing_reports_phases_on_stderr_and_in_json() {
+    let project = write_project(&[
+        ("src/index.ts", "import './lib';\n"),
+        ("src/lib.ts", "export const a = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache", "--timing"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let phases: Vec<&str> = json["timings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|timing| timing["phase"].as_str().unwrap())
+        .collect();
+    for phase in ["scan", "parse", "graph", "rules", "rules/unused-files"] {
+        assert!(phases.contains(&phase), "{:?}", phases);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("report") && stderr.contains("total"), "{}", stderr);
+
+    // Without the flag, the report stays free of run-to-run noise
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("timings").is_none());
+}
+
+#[test]
+fn test_show_fixes_renders_code_frame_and_diff() {
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './lib.ts';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\n\nexport function unused() {}\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--show-fixes"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains(" > 3 | export function unused() {}"));
+    assert!(stdout.contains("    - export function unused() {}"));
+    assert!(stdout.contains("    + function unused() {}"));
+}
+
+#[test]
+fn test_group_by_groups_findings_by_rule_directory_or_package() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "dependencies": { "left-pad": "1" } }"#),
+        ("tools/package.json", r#"{ "name": "tools" }"#),
+        ("src/index.ts", "import { used } from './lib/util';\nimport '../tools/run';\nconsole.log(used);\n"),
+        ("src/lib/util.ts", "export const used = 1;\nexport const unused = 2;\n"),
+        ("src/stale.ts", "console.log(1);\n"),
+        ("tools/run.ts", "export const tool = 1;\n"),
+    ]);
+    let root = project.path().display().to_string();
+    let grouped = |group_by: &str| {
+        let output = run_sweepr_in(
+            project.path(),
+            &["check", "--entry", "src/index.ts", "--quiet", "--no-cache", "--group-by", group_by],
+        );
+        assert_eq!(output.status.code(), Some(1));
+        String::from_utf8_lossy(&output.stdout).replace(&root, "<root>")
+    };
+
+    let dependency = "  Unused dependency 'left-pad' (SWP001 unused-deps) <root>/package.json\n";
+    let export = "  Unused export 'unused' (SWP002 unused-exports) <root>/src/lib/util.ts:2\n";
+    let tool = "  Unused export 'tool' (SWP002 unused-exports) <root>/tools/run.ts:1\n";
+    let file = "  Unused file (SWP004 unused-files) <root>/src/stale.ts\n";
+    assert_eq!(
+        grouped("rule"),
+        [
+            "SWP001 unused-deps (1)\n", dependency,
+            "SWP002 unused-exports (2)\n", export, tool,
+            "SWP004 unused-files (1)\n", file,
+        ]
+        .concat()
+    );
+    assert_eq!(
+        grouped("dir"),
+        [
+            "<root> (1)\n", dependency,
+            "<root>/src (1)\n", file,
+            "<root>/src/lib (1)\n", export,
+            "<root>/tools (1)\n", tool,
+        ]
+        .concat()
+    );
+    assert_eq!(grouped("package"), ["app (3)\n", dependency, export, file, "tools (1)\n", tool].concat());
+
+    // The full report ends with the number of groups
+    let output = run_sweepr_in(project.path(), &["check", "--entry", "src/index.ts", "--no-cache", "--group-by", "rule"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("📁 SWP002 unused-exports (2)\n"), "{}", stdout);
+    assert!(stdout.contains("📊 Summary: 4 issues found in 3 groups"), "{}", stdout);
+}
+
+#[test]
+fn test_code_frames_show_context_and_underline_the_name() {
+    let project = write_project(&[
+        ("src/index.ts", "import './lib';\n"),
+        ("src/lib.ts", "const greeting = 'héllo';\n\texport const unused = greeting;\nexport const café = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Tabs stay in the underline's padding so it lines up with the name
+    let tabbed = concat!(
+        "   1 | const greeting = 'héllo';\n",
+        " > 2 | \texport const unused = greeting;\n",
+        "     | \t             ^^^^^^\n",
+        "   3 | export const café = 1;\n",
+        "\n",
+    );
+    assert!(stdout.contains(tabbed), "{}", stdout);
+    // Columns count characters, and the empty line after the last newline isn't shown
+    let wide = concat!(
+        "   1 | const greeting = 'héllo';\n",
+        "   2 | \texport const unused = greeting;\n",
+        " > 3 | export const café = 1;\n",
+        "     |              ^^^^\n",
+        "\n",
+    );
+    assert!(stdout.contains(wide), "{}", stdout);
+}
+
+#[test]
+fn test_dependency_fix_skips_scripts_with_the_same_name() {
+    let project = write_project(&[
+        (
+            "package.json",
+            "{\n  \"name\": \"app\",\n  \"scripts\": {\n    \"lodash\": \"node src/index.ts\"\n  },\n  \"dependencies\": {\n    \"lodash\": \"^4.0.0\"\n  }\n}\n",
+        ),
+        ("src/index.ts", "console.log('app');\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--show-fixes", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("    -     \"lodash\": \"^4.0.0\""), "{}", stdout);
+    assert!(!stdout.contains("    -     \"lodash\": \"node src/index.ts\""), "{}", stdout);
+}
+
+#[test]
+fn test_reporter_registry_dispatches_custom_formats() {
+    use sweepr::graph::FileImportGraph;
+    use sweepr::reporter::{ReportContext, ReportOptions, Reporter, ReporterRegistry};
+    use sweepr::rules::{AnalysisReport, UnusedFile};
+
+    struct CountReporter;
+
+    impl Reporter for CountReporter {
+        fn report(&self, report: &AnalysisReport, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+            writeln!(out, "{} findings", report.findings().len())
+        }
+    }
+
+    let mut registry = ReporterRegistry::default();
+    registry.register("count", |_| Box::new(CountReporter));
+    assert!(registry.names().any(|name| name == "count"));
+    assert!(registry.names().any(|name| name == "json"));
+
+    let report = AnalysisReport {
+        unused_dependencies: Vec::new(),
+        unused_exports: Vec::new(),
+        unused_types: Vec::new(),
+        module_only_exports: Vec::new(),
+        unused_files: vec![UnusedFile {
+            path: PathBuf::from("src/dead.ts"),
+            has_side_effects: false,
+            lines: 0,
+            bytes: 0,
+        }],
+        dead_chains: Vec::new(),
+        duplicate_exports: Vec::new(),
+        unlisted_dependencies: Vec::new(),
+        unresolved_imports: Vec::new(),
+        test_only_exports: Vec::new(),
+        story_only_exports: Vec::new(),
+        unused_peer_dependencies: Vec::new(),
+        orphaned_types: Vec::new(),
+        type_only_dependencies: Vec::new(),
+        unused_private_members: Vec::new(),
+        unused_locals: Vec::new(),
+        unused_imports: Vec::new(),
+        removable_files: Vec::new(),
+        skipped_files: Vec::new(),
+        parse_failures: Vec::new(),
+        timings: Vec::new(),
+        packages: Vec::new(),
+        sort_by: Default::default(),
+    };
+    let file_graph = FileImportGraph::new();
+    let options = ReportOptions::default();
+    let context = ReportContext {
+        root: std::path::Path::new("."),
+        file_graph: &file_graph,
+        options: &options,
+    };
+
+    let mut out = Vec::new();
+    let reporter = registry.create("count", &context).unwrap();
+    reporter.report(&report, &mut out).unwrap();
+    assert_eq!(String::from_utf8(out).unwrap(), "1 findings\n");
+    assert!(registry.create("missing", &context).is_none());
+}
+
+#[test]
+fn test_summary_prints_counts_per_rule_instead_of_findings() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "dependencies": { "left-pad": "1" } }"#),
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\nexport const spare = 3;\n"),
+        ("src/stale.ts", "console.log(1);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--entry", "src/index.ts", "--summary", "--quiet", "--no-cache"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 18, "{}", stdout);
+    assert_eq!(lines[0], "  SWP001 unused-deps            1");
+    assert_eq!(lines[1], "  SWP002 unused-exports         2");
+    assert_eq!(lines[3], "  SWP004 unused-files           1");
+    assert_eq!(lines[16], "  SWP017 removable-files        0");
+    assert_eq!(lines[17], "  total                         4");
+    assert!(!stdout.contains("unused in"), "{}", stdout);
+
+    let output = run_sweepr_in(
+        project.path(),
+        &["check", "--entry", "src/index.ts", "--summary", "--format", "json", "--no-cache"],
+    );
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let summary = summary.as_object().unwrap();
+    // Every rule, the total, and the savings, but no findings
+    assert_eq!(summary.len(), 19, "{:?}", summary);
+    assert_eq!(summary["unused-deps"], 1);
+    assert_eq!(summary["unused-exports"], 2);
+    assert_eq!(summary["unused-files"], 1);
+    assert_eq!(summary["unused-types"], 0);
+    assert_eq!(summary["total"], 4);
+    assert!(summary.get("findings").is_none());
+}
+
+#[test]
+fn test_graph_command_dumps_dot() {
+    let project = write_project(&[
+        (
+            "src/index.ts",
+            "import { a } from './a';\nimport type { T } from './types';\nimport './missing';\nconsole.log(a);\n",
+        ),
+        ("src/a.ts", "export const a = 1;\n"),
+        ("src/types.ts", "export type T = 1;\n"),
+        ("src/dead.ts", "export const d = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["graph", "--entry", "src/index.ts", "--format", "dot", "--no-cache"]);
+    assert!(output.status.success());
+    // Entry points are green, unused files red, type-only imports and
+    // unresolved targets dashed
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "digraph imports {
+  rankdir=LR;
+  node [shape=box, style=\"rounded,filled\", fillcolor=white, fontname=monospace];
+  \"src/a.ts\" [label=\"src/a.ts\"];
+  \"src/dead.ts\" [label=\"src/dead.ts\", fillcolor=\"#f9c6c6\"];
+  \"src/index.ts\" [label=\"src/index.ts\", fillcolor=\"#c8f7c5\", penwidth=2];
+  \"src/types.ts\" [label=\"src/types.ts\"];
+  \"src/missing\" [label=\"src/missing\", style=\"rounded,dashed\"];
+  \"src/index.ts\" -> \"src/a.ts\";
+  \"src/index.ts\" -> \"src/types.ts\" [style=dashed];
+  \"src/index.ts\" -> \"src/missing\";
+}
+"
+    );
+}
+
+#[test]
+fn test_graph_command_dumps_mermaid() {
+    let project = write_project(&[
+        (
+            "src/index.ts",
+            "import { a } from './a';\nimport type { T } from './types';\nimport './missing';\nconsole.log(a);\n",
+        ),
+        ("src/a.ts", "export const a = 1;\n"),
+        ("src/types.ts", "export type T = 1;\n"),
+        ("src/dead.ts", "export const d = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["graph", "--entry", "src/index.ts", "--format", "mermaid", "--no-cache"]);
+    assert!(output.status.success());
+    // Paths are labels of generated ids; type-only imports are dotted
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "graph TD
+  classDef entry fill:#c8f7c5,stroke:#1a7f37,stroke-width:2px
+  classDef unused fill:#f9c6c6,stroke:#cf222e
+  classDef unresolved stroke-dasharray:4 3
+  n0[\"src/a.ts\"]
+  n1[\"src/dead.ts\"]
+  class n1 unused
+  n2[\"src/index.ts\"]
+  class n2 entry
+  n3[\"src/missing\"]
+  class n3 unresolved
+  n4[\"src/types.ts\"]
+  n2 --> n0
+  n2 -.-> n4
+  n2 --> n3
+"
+    );
+}
+
+#[test]
+fn test_analyzer_runs_checks_as_a_library() {
+    use sweepr::analyzer::Analyzer;
+    use sweepr::config::Config;
+
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\n"),
+        ("src/orphan.ts", "export {};\n"),
+    ]);
+
+    let report = Analyzer::new(Config::default())
+        .with_root(project.path().to_path_buf())
+        .with_cache(false)
+        .run()
+        .unwrap();
+
+    let unused_exports: Vec<&str> = report.unused_exports.iter().map(|export| export.name.as_str()).collect();
+    assert_eq!(unused_exports, ["unused"]);
+    assert_eq!(report.unused_files.len(), 1);
+    assert!(report.unused_files[0].path.ends_with("src/orphan.ts"));
+    assert!(!project.path().join(".sweepr").exists());
+}
+
+#[test]
+fn test_scanner_respects_gitignore_defaults_and_config_ignores() {
+    let project = write_project(&[
+        (".gitignore", "generated/\n"),
+        (
+            "sweepr.config.json",
+            r#"{ "entry": ["src/index.ts"], "ignore": ["**/*.test.ts"] }"#,
+        ),
+        ("src/index.ts", "export {};\n"),
+        ("src/orphan.ts", "const x = 1;\n"),
+        ("src/orphan.test.ts", "const x = 1;\n"),
+        ("generated/api.ts", "const x = 1;\n"),
+        ("dist/bundle.js", "const x = 1;\n"),
+        ("coverage/lcov.js", "const x = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("orphan.ts"));
+    assert!(!stdout.contains("orphan.test.ts"));
+    assert!(!stdout.contains("api.ts"));
+    assert!(!stdout.contains("bundle.js"));
+    assert!(!stdout.contains("lcov.js"));
+}
+
+#[test]
+fn test_watch_re_analyzes_after_a_file_is_edited() {
+    use std::io::BufRead;
+    use std::time::Duration;
+
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app" }"#),
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\n"),
+    ]);
+    let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_sweepr"))
+        .current_dir(project.path())
+        .args(["check", "--entry", "src/index.ts", "--format", "compact", "--no-cache", "--watch"])
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to run sweepr");
+
+    let (sender, lines) = std::sync::mpsc::channel();
+    let stdout = child.stdout.take().unwrap();
+    std::thread::spawn(move || {
+        for line in std::io::BufReader::new(stdout).lines().map_while(Result::ok) {
+            let _ = sender.send(line);
+        }
+    });
+    let wait_for = |needle: &str, timeout: Duration| {
+        let deadline = std::time::Instant::now() + timeout;
+        while let Some(left) = deadline.checked_duration_since(std::time::Instant::now()) {
+            match lines.recv_timeout(left) {
+                Ok(line) if line.contains(needle) => return true,
+                Ok(_) => {}
+                Err(_) => return false,
+            }
+        }
+        false
+    };
+
+    assert!(wait_for("Unused export 'unused'", Duration::from_secs(30)));
+
+    // The watcher starts after the first report, so keep editing until it notices
+    let mut reported = false;
+    for attempt in 0..10 {
+        let source = format!("export const used = 1;\nexport const unused = 2;\nexport const fresh{} = 3;\n", attempt);
+        std::fs::write(project.path().join("src/lib.ts"), source).unwrap();
+        if wait_for("Unused export 'fresh", Duration::from_secs(2)) {
+            reported = true;
+            break;
+        }
+    }
+    assert!(reported, "the edit was never re-analyzed");
+
+    // A manifest edit reloads the dependencies it declares
+    std::fs::write(
+        project.path().join("package.json"),
+        r#"{ "name": "app", "dependencies": { "left-pad": "^1.3.0" } }"#,
+    )
+    .unwrap();
+    let reloaded = wait_for("Unused dependency 'left-pad'", Duration::from_secs(10));
+    let _ = child.kill();
+    let _ = child.wait();
+    assert!(reloaded, "the package.json edit was never re-analyzed");
+}
+
+#[test]
+fn test_eslint_format_groups_findings_by_file() {
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        ("src/lib.ts", "export const used = 1;\nexport const unused = 2;\n"),
+        ("src/orphan.ts", "const x = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "eslint", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    let orphan = lines.iter().position(|line| line.ends_with("src/orphan.ts")).unwrap();
+    assert_eq!(lines[orphan + 1], "  0:0  warning  Unused file  unused-files");
+    let lib = lines.iter().position(|line| line.ends_with("src/lib.ts")).unwrap();
+    assert!(lines[lib + 1].contains("  warning  Unused export 'unused'  unused-exports"));
+    assert!(stdout.ends_with("\n✖ 2 problems (0 errors, 2 warnings)\n\n"), "{}", stdout);
+}
+
+#[test]
+fn test_ts_prune_format_marks_exports_used_in_their_module() {
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        (
+            "src/lib.ts",
+            "export const used = 1;\nexport const local = 2;\nconsole.log(local);\nexport type Alias = string;\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "ts-prune", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 2, "{}", stdout);
+    assert_eq!(lines, ["src/lib.ts:2 - local (used in module)", "src/lib.ts:4 - Alias"]);
+}
+
+#[test]
+fn test_depcheck_prints_depcheck_shaped_json() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+                "dependencies": { "lodash": "^4.0.0", "left-pad": "^1.0.0" },
+                "devDependencies": { "jest": "^29.0.0" }
+            }"#,
+        ),
+        ("src/index.ts", "import _ from 'lodash';\nimport chalk from 'chalk';\nexport const unused = [_, chalk];\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["depcheck", "--no-cache"]);
+    assert_eq!(output.status.code(), Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let index = project.path().join("src/index.ts");
+
+    assert_eq!(json["dependencies"], serde_json::json!(["left-pad"]));
+    assert_eq!(json["devDependencies"], serde_json::json!(["jest"]));
+    assert_eq!(json["missing"]["chalk"], serde_json::json!([index]));
+    assert_eq!(json["using"]["lodash"], serde_json::json!([index]));
+    assert_eq!(json["using"]["chalk"], serde_json::json!([index]));
+    assert!(json.get("unused_exports").is_none());
+}
+
+#[test]
+fn test_depcheck_lists_files_that_fail_to_parse_as_invalid() {
+    let project = write_project(&[
+        ("package.json", r#"{ "dependencies": { "lodash": "^4.0.0" } }"#),
+        ("src/index.ts", "import _ from 'lodash';\nexport default _;\n"),
+        ("src/broken.ts", "export const = ;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["depcheck", "--no-cache"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let broken = project.path().join("src/broken.ts");
+
+    let invalid = json["invalidFiles"].as_object().unwrap();
+    assert_eq!(invalid.len(), 1);
+    assert!(invalid[broken.to_str().unwrap()].as_str().is_some_and(|message| !message.is_empty()));
+    assert_eq!(json["dependencies"], serde_json::json!([]));
+}
+
+#[test]
+fn test_knip_config_is_used_without_a_sweepr_config() {
+    let project = write_project(&[
+        (
+            "knip.json",
+            r#"{
+                "entry": ["src/main.ts", "scripts/*.ts!"],
+                "project": ["src/**/*.ts", "scripts/**", "!src/legacy/**"],
+                "ignore": "src/generated/**",
+                "ignoreDependencies": ["left-pad"]
+            }"#,
+        ),
+        ("package.json", r#"{ "dependencies": { "left-pad": "^1.0.0", "lodash": "^4.0.0" } }"#),
+        ("src/main.ts", "export {};\n"),
+        ("src/orphan.ts", "export {};\n"),
+        ("src/legacy/old.ts", "export {};\n"),
+        ("src/generated/api.ts", "export {};\n"),
+        ("scripts/build.ts", "export {};\n"),
+        ("other/tool.ts", "export {};\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("src/orphan.ts"), "{}", stdout);
+    assert!(stdout.contains("lodash"), "{}", stdout);
+    for kept in ["build.ts", "old.ts", "api.ts", "tool.ts", "left-pad"] {
+        assert!(!stdout.contains(kept), "{}", stdout);
+    }
+}
+
+#[test]
+fn test_workspace_packages_are_discovered_with_their_dependencies() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "root", "workspaces": ["packages/*", "!packages/legacy"] }"#),
+        ("packages/a/package.json", r#"{ "name": "a", "dependencies": { "left-pad": "^1.0.0" } }"#),
+        ("packages/a/index.ts", "export {};\n"),
+        ("packages/legacy/package.json", r#"{ "name": "legacy" }"#),
+        ("node_modules/left-pad/package.json", r#"{ "name": "left-pad" }"#),
+    ]);
+
+    let packages = sweepr::workspaces::discover_packages(project.path()).unwrap();
+    let names: Vec<_> = packages.iter().filter_map(|p| p.name.as_deref()).collect();
+    assert_eq!(names, ["root", "a"]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--entry", "index.ts", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("packages/a/package.json"), "{}", stdout);
+    assert!(!stdout.contains("packages/a/index.ts"), "{}", stdout);
+}
+
+#[test]
+fn test_exports_are_used_across_workspace_packages() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "root", "private": true, "workspaces": ["packages/*"] }"#),
+        (
+            "packages/core/package.json",
+            r#"{
+                "name": "@repo/core",
+                "exports": { ".": { "types": "./dist/index.d.ts", "import": "./dist/index.js" }, "./utils/*": "./src/utils/*.ts" }
+            }"#,
+        ),
+        (
+            "packages/core/src/index.ts",
+            "export const used = 1;\nexport const unused = 2;\nexport * from './more';\n",
+        ),
+        ("packages/core/src/more.ts", "export const more = 1;\nexport const noMore = 2;\n"),
+        ("packages/core/src/utils/format.ts", "export const format = 1;\n"),
+        (
+            "packages/web/package.json",
+            r#"{ "name": "web", "main": "src/index.ts", "dependencies": { "@repo/core": "*" } }"#,
+        ),
+        (
+            "packages/web/src/index.ts",
+            "import { used, more } from '@repo/core';\nimport { format } from '@repo/core/utils/format';\n\
+             console.log(used, more, format);\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_dependencies"].as_array().unwrap().is_empty(), "{}", report);
+
+    // Exports no package imports are still reported
+    let mut unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    unused.sort();
+    assert_eq!(unused, vec!["noMore", "unused"], "{}", report);
+}
+
+#[test]
+fn test_file_and_workspace_protocol_dependencies_link_local_packages() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*"], "dependencies": { "my-lib": "file:./vendor/lib" } }"#,
+        ),
+        ("src/index.ts", "import { used } from 'my-lib';\nconsole.log(used);\n"),
+        (
+            "vendor/lib/package.json",
+            r#"{ "name": "@acme/lib", "main": "src/index.ts", "dependencies": { "left-pad": "^1.0.0", "lodash": "^4.0.0" } }"#,
+        ),
+        (
+            "vendor/lib/src/index.ts",
+            "import lodash from 'lodash';\nexport const used = lodash;\nexport const unused = 2;\n",
+        ),
+        ("packages/core/package.json", r#"{ "name": "@repo/core", "main": "src/index.ts" }"#),
+        ("packages/core/src/index.ts", "export const shared = 1;\n"),
+        (
+            "packages/web/package.json",
+            r#"{ "name": "web", "main": "src/index.ts", "dependencies": { "core": "workspace:@repo/core@*" } }"#,
+        ),
+        ("packages/web/src/index.ts", "import { shared } from 'core';\nconsole.log(shared);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    // The linked package's dependencies are checked against its own package.json
+    let deps = report["unused_dependencies"].as_array().unwrap();
+    assert_eq!(deps.len(), 1, "{}", report);
+    assert_eq!(deps[0]["name"], "left-pad");
+    assert!(deps[0]["manifest"].as_str().unwrap().ends_with("vendor/lib/package.json"));
+    assert!(report["unlisted_dependencies"].as_array().unwrap().is_empty(), "{}", report);
+
+    let unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(unused, vec!["unused"], "{}", report);
+}
+
+#[test]
+fn test_exports_used_only_in_their_own_module() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "main": "src/index.ts" }"#),
+        ("src/index.ts", "import { run } from './lib';\nrun();\n"),
+        (
+            "src/lib.ts",
+            "export function helper() {\n  return 1;\n}\nexport const run = () => helper();\nexport const stale = 2;\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names = |key: &str| -> Vec<String> {
+        report[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|export| export["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(names("module_only_exports"), vec!["helper"], "{}", report);
+    assert_eq!(names("unused_exports"), vec!["stale"], "{}", report);
+
+    // The fix keeps the declaration the module uses
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache", "--show-fixes"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Module-only Exports (1)"), "{}", stdout);
+    assert!(stdout.contains("+ function helper() {"), "{}", stdout);
+
+    // With the rule off, they are unused exports again
+    std::fs::write(
+        project.path().join("sweepr.config.json"),
+        r#"{ "rules": { "module_only_exports": false } }"#,
+    )
+    .unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["unused_exports"].as_array().unwrap().len(), 2, "{}", report);
+}
+
+#[test]
+fn test_default_imports_use_the_default_export_through_their_local_names() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "main": "src/index.tsx" }"#),
+        (
+            "src/index.tsx",
+            "import anything from './helper';\nimport { default as Renamed } from './button';\n\
+             import type Config from './config';\nimport forgotten from './forgotten';\n\
+             anything();\nconst config: Config = 1;\nconsole.log(<Renamed />, config);\n",
+        ),
+        ("src/helper.ts", "export default function helper() {}\n"),
+        ("src/button.tsx", "const Button = () => null;\nexport default Button;\n"),
+        ("src/config.ts", "type Config = number;\nexport default Config;\n"),
+        ("src/forgotten.ts", "export default 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused: Vec<(&str, &str)> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .chain(report["unused_types"].as_array().unwrap())
+        .map(|export| {
+            let file = export["file"].as_str().unwrap();
+            (file.rsplit('/').next().unwrap(), export["name"].as_str().unwrap())
+        })
+        .collect();
+    // `forgotten` is imported but never referenced
+    assert_eq!(unused, vec![("forgotten.ts", "default")], "{}", report);
+}
+
+#[test]
+fn test_barrel_usage_is_attributed_to_original_definitions() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "main": "src/index.ts" }"#),
+        (
+            "src/index.ts",
+            "import { Button, Theme } from './components';\nconsole.log(Button, Theme);\n",
+        ),
+        (
+            "src/components/index.ts",
+            "import { Button } from './Button';\nimport { Modal } from './Modal';\n\
+             import { theme, spacing } from './theme';\nimport Card from './Card';\n\
+             export { Button, Modal, theme as Theme };\nexport default Card;\nconsole.log(spacing);\n",
+        ),
+        ("src/components/Button.ts", "export const Button = 1;\n"),
+        ("src/components/Modal.ts", "export const Modal = 1;\n"),
+        ("src/components/theme.ts", "export const theme = 1;\nexport const spacing = 2;\n"),
+        ("src/components/Card.ts", "export default 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut unused: Vec<(&str, &str)> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| {
+            let file = export["file"].as_str().unwrap();
+            (file.rsplit('/').next().unwrap(), export["name"].as_str().unwrap())
+        })
+        .collect();
+    unused.sort();
+    // The barrel importing `Modal` and `Card` only to re-export them doesn't
+    // keep the originals alive
+    assert_eq!(
+        unused,
+        vec![
+            ("Card.ts", "default"),
+            ("Modal.ts", "Modal"),
+            ("index.ts", "Modal"),
+            ("index.ts", "default"),
+        ],
+        "{}",
+        report
+    );
+}
+
+#[test]
+fn test_import_equals_require_declarations() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts", "dependencies": { "lodash": "^4.0.0" } }"#,
+        ),
+        (
+            "src/index.ts",
+            "import utils = require('./utils');\nimport _ = require('lodash');\n\
+             export import legacy = require('./legacy');\nconsole.log(utils.formatDate(), _);\n",
+        ),
+        ("src/utils.ts", "export const formatDate = () => '';\nexport const parseDate = () => 0;\n"),
+        ("src/legacy.ts", "export const old = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+    assert!(report["unused_dependencies"].as_array().unwrap().is_empty(), "{}", report);
+    let mut unused: Vec<(&str, &str)> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| {
+            let file = export["file"].as_str().unwrap();
+            (file.rsplit('/').next().unwrap(), export["name"].as_str().unwrap())
+        })
+        .collect();
+    unused.sort();
+    // Members accessed on the binding are used, like a namespace import's,
+    // and `export import` forwards the module like `export * as`
+    assert_eq!(
+        unused,
+        vec![("index.ts", "legacy"), ("legacy.ts", "old"), ("utils.ts", "parseDate")],
+        "{}",
+        report
+    );
+}
+
+#[test]
+fn test_export_assignments_are_used_by_any_import() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "main": "src/index.ts" }"#),
+        (
+            "src/index.ts",
+            "import api = require('./api');\nimport legacy from './legacy';\nimport './setup';\n\
+             console.log(api.hello(), legacy);\n",
+        ),
+        ("src/api.ts", "const api = { hello() {} };\nexport = api;\n"),
+        ("src/legacy.ts", "export = 1;\n"),
+        ("src/setup.ts", "export = 2;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused: Vec<(&str, &str)> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| {
+            let file = export["file"].as_str().unwrap();
+            (file.rsplit('/').next().unwrap(), export["name"].as_str().unwrap())
+        })
+        .collect();
+    // A side-effect import uses none of the module's exports
+    assert_eq!(unused, vec![("setup.ts", "export=")], "{}", report);
+}
+
+#[test]
+fn test_ambient_declarations_are_used_with_what_they_declare() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts", "dependencies": { "express": "^4.0.0" } }"#,
+        ),
+        (
+            "src/index.ts",
+            "import express from 'express';\nimport logo from './logo.svg';\n\
+             window.appVersion = '1';\nconsole.log(express, logo);\n",
+        ),
+        ("src/logo.svg", "<svg></svg>\n"),
+        (
+            "src/types/express.d.ts",
+            "declare module 'express' {\n  export interface Request { user?: string }\n}\n",
+        ),
+        ("src/types/assets.d.ts", "declare module '*.svg' {\n  const url: string;\n  export default url;\n}\n"),
+        ("src/types/window.d.ts", "interface Window { appVersion: string }\n"),
+        ("src/globals.ts", "export {};\ndeclare global {\n  var counter: number;\n}\n"),
+        ("src/types/left-pad.d.ts", "declare module 'left-pad' {\n  export default function leftPad(s: string): string;\n}\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused_files: Vec<&str> = report["unused_files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["path"].as_str().unwrap().rsplit('/').next().unwrap())
+        .collect();
+    // Nothing imports `left-pad`, which its declaration describes
+    assert_eq!(unused_files, vec!["left-pad.d.ts"], "{}", report);
+    // Declarations inside the blocks aren't exports of the files
+    assert!(report["unused_exports"].as_array().unwrap().is_empty(), "{}", report);
+    assert!(report["unused_types"].as_array().unwrap().is_empty(), "{}", report);
+}
+
+#[test]
+fn test_unused_private_class_members() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "main": "src/index.ts" }"#),
+        (
+            "src/index.ts",
+            "export class Cache {\n  #hits = 0;\n  #store = new Map();\n  private retries = 3;\n  \
+             private readonly label = 'cache';\n  private helper() {}\n  private format() { return this.label; }\n  \
+             @Input() private decorated = 1;\n  get(key: string) {\n    this.#hits = 1;\n    \
+             const { retries } = this;\n    return [this.#store.get(key), this.format(), retries];\n  }\n}\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused: Vec<(&str, &str, u64)> = report["unused_private_members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| {
+            (
+                member["class"].as_str().unwrap(),
+                member["name"].as_str().unwrap(),
+                member["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    // `#hits` is only assigned; decorated members are left to frameworks
+    assert_eq!(unused, vec![("Cache", "#hits", 2), ("Cache", "helper", 6)], "{}", report);
+
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Unused Private Members (2)"), "{}", stdout);
+    assert!(stdout.contains("Cache.#hits"), "{}", stdout);
+}
+
+#[test]
+fn test_unused_locals_are_reported_when_enabled() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        (
+            "src/index.ts",
+            "import { helper } from './helper';\n\
+             const unused = 1;\n\
+             let written = 0;\n\
+             written = 2;\n\
+             const _ignored = 3;\n\
+             const { picked, ...rest } = { picked: 1, other: 2 };\n\
+             function run() {\n\
+             \x20 const inner = helper();\n\
+             \x20 return 1;\n\
+             }\n\
+             function unusedFn() {}\n\
+             class Unused {}\n\
+             export const kept = run() + rest.other;\n",
+        ),
+        ("src/helper.ts", "export function helper() { return 1; }\n"),
+    ]);
+
+    // Off by default
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_locals"].as_array().unwrap().is_empty(), "{}", report);
+
+    std::fs::write(
+        project.path().join("sweepr.config.json"),
+        r#"{ "rules": { "unused_locals": true } }"#,
+    )
+    .unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let locals: Vec<&str> = report["unused_locals"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|local| local["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(locals, vec!["unused", "written", "inner", "unusedFn", "Unused"], "{}", report);
+}
+
+#[test]
+fn test_unused_import_specifiers_are_reported_with_fixes() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.tsx" }"#,
+        ),
+        (
+            "src/index.tsx",
+            "import React from 'react';\n\
+             import { a, b, type Shape } from './lib';\n\
+             import c from './lib';\n\
+             import { Button, Icon } from './ui';\n\
+             export { Icon };\n\
+             export const value: Shape = a + 1;\n\
+             export const view = <Button />;\n",
+        ),
+        ("src/lib.ts", "export const a = 1;\nexport const b = 2;\nexport type Shape = number;\nexport default 3;\n"),
+        ("src/ui.tsx", "export const Button = () => null;\nexport const Icon = () => null;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let imports: Vec<(&str, &str)> = report["unused_imports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|import| (import["name"].as_str().unwrap(), import["source"].as_str().unwrap()))
+        .collect();
+    assert_eq!(imports, vec![("b", "./lib"), ("c", "./lib")], "{}", report);
+
+    let output = run_sweepr_in(project.path(), &["check", "--show-fixes", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("    - import { a, b, type Shape } from './lib';"), "{}", stdout);
+    assert!(stdout.contains("    + import { a, type Shape } from './lib';"), "{}", stdout);
+    assert!(stdout.contains("    - import c from './lib';"), "{}", stdout);
+}
+
+#[test]
+fn test_reachable_files_with_only_unused_exports_are_removable() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "import { a } from './barrel';\nimport './polyfill';\nconsole.log(a);\n"),
+        ("src/barrel.ts", "export * from './a';\nexport * from './b';\n"),
+        ("src/a.ts", "export const a = 1;\n"),
+        ("src/b.ts", "export const b = 2;\nexport type B = number;\n"),
+        ("src/polyfill.ts", "export const installed = true;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+    let removable = report["removable_files"].as_array().unwrap();
+    assert_eq!(removable.len(), 1, "{}", report);
+    assert!(removable[0]["path"].as_str().unwrap().ends_with("src/b.ts"));
+    assert_eq!(removable[0]["exports"], 2);
+    assert!(removable[0]["importers"][0].as_str().unwrap().ends_with("src/barrel.ts"));
+}
+
+#[test]
+fn test_savings_estimate_lines_and_bytes_of_unused_code() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "import { used } from './lib';\nconsole.log(used);\n"),
+        (
+            "src/lib.ts",
+            "export const used = 1, spare = 2;\n\
+             export function unused() {\n\
+             \x20 return 1;\n\
+             }\n\
+             interface Shape {}\n\
+             export type { Shape };\n",
+        ),
+        ("src/dead.ts", "const dead = 1;\nconsole.log(dead);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let sizes = |key: &str| -> Vec<(String, u64, u64)> {
+        report[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|finding| {
+                let name = finding.get("name").unwrap_or(&finding["path"]);
+                (
+                    name.as_str().unwrap().rsplit('/').next().unwrap().to_string(),
+                    finding["lines"].as_u64().unwrap(),
+                    finding["bytes"].as_u64().unwrap(),
+                )
+            })
+            .collect()
+    };
+    let mut exports = sizes("unused_exports");
+    exports.sort();
+    assert_eq!(
+        exports,
+        vec![("spare".to_string(), 1, 9), ("unused".to_string(), 3, 40)],
+        "{}",
+        report
+    );
+    assert_eq!(sizes("unused_types"), vec![("Shape".to_string(), 1, 18)]);
+    assert_eq!(sizes("unused_files"), vec![("dead.ts".to_string(), 2, 35)]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("📊 Summary: 4 issues found (~7 LOC, 102 B removable)"), "{}", stdout);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--summary", "--no-cache"]);
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["savings"], serde_json::json!({ "lines": 7, "bytes": 102 }));
+}
+
+#[test]
+fn test_sort_orders_findings_by_path_name_size_or_rule() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "import './b';\nimport './a';\n"),
+        ("src/a.ts", "export const zeta = 1;\nexport function alpha() {\n  return 1;\n}\nexport type Beta = number;\n"),
+        ("src/b.ts", "export function mid() {\n  return 1;\n  return 2;\n}\n"),
+    ]);
+
+    let exports = |sort: &str| -> Vec<String> {
+        let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache", "--sort", sort]);
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        report["unused_exports"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|export| export["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(exports("rule"), vec!["zeta", "alpha", "mid"]);
+    assert_eq!(exports("path"), vec!["zeta", "alpha", "mid"]);
+    assert_eq!(exports("name"), vec!["alpha", "mid", "zeta"]);
+    assert_eq!(exports("size"), vec!["mid", "alpha", "zeta"]);
+
+    // Flat formats keep each rule's findings together unless sorted by path or name
+    let symbols = |sort: &str| -> Vec<String> {
+        let output = run_sweepr_in(project.path(), &["check", "--format", "csv", "--no-cache", "--sort", sort]);
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').nth(2).unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(symbols("rule"), vec!["zeta", "alpha", "mid", "Beta"]);
+    assert_eq!(symbols("path"), vec!["zeta", "alpha", "Beta", "mid"]);
+    assert_eq!(symbols("name"), vec!["Beta", "alpha", "mid", "zeta"]);
+}
+
+#[test]
+fn test_config_profiles_override_settings() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        (
+            "sweepr.config.json",
+            r#"{
+                "rules": { "unused_exports": false },
+                "profiles": {
+                    "ci": { "entry": ["src/index.ts", "scripts/seed.ts"], "rules": { "unused_locals": true } }
+                }
+            }"#,
+        ),
+        ("src/index.ts", "export const unused = 1;\nconst local = 2;\n"),
+        ("scripts/seed.ts", "console.log('seed');\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_locals"].as_array().unwrap().is_empty(), "{}", report);
+    assert_eq!(report["unused_files"].as_array().unwrap().len(), 1, "{}", report);
+
+    // The profile turns a rule on and adds an entry, keeping the base rules it doesn't mention
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache", "--profile", "ci"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["unused_locals"][0]["name"], "local", "{}", report);
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+    assert!(report["unused_exports"].as_array().unwrap().is_empty(), "{}", report);
+
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache", "--profile", "strict"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unknown profile 'strict': the config defines ci"), "{}", stderr);
+}
+
+#[test]
+fn test_config_profiles_accept_rule_codes() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        (
+            "sweepr.config.json",
+            r#"{ "profiles": { "ci": { "rules": { "SWP002": false, "SWP015": true } } } }"#,
+        ),
+        ("src/index.ts", "export const unused = 1;\nconst local = 2;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache", "--profile", "ci"]);
+    assert_eq!(output.status.code(), Some(1), "{}", String::from_utf8_lossy(&output.stderr));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_exports"].as_array().unwrap().is_empty(), "{}", report);
+    assert_eq!(report["unused_locals"][0]["name"], "local", "{}", report);
+}
+
+#[test]
+fn test_file_recheck_uses_cached_graphs() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "import { used } from './a';\nimport { other } from './b';\nconsole.log(used, other);\n"),
+        ("src/a.ts", "export const used = 1;\n"),
+        ("src/b.ts", "export const other = 2;\nexport const stale = 3;\n"),
+    ]);
+
+    // A full run saves the graphs the recheck starts from
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["unused_exports"].as_array().unwrap().len(), 1, "{}", report);
+
+    std::fs::write(project.path().join("src/a.ts"), "export const used = 1;\nexport const added = 2;\n").unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--file", "src/a.ts"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    // Only the file's own findings are reported, with its edits re-parsed
+    assert_eq!(unused, ["added"], "{}", report);
+}
+
+#[test]
+fn test_file_recheck_rejects_files_outside_the_project() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "console.log(1);\n"),
+        ("README.md", "# app\n"),
+    ]);
+    let outside = tempfile::tempdir().unwrap();
+    std::fs::write(outside.path().join("other.ts"), "export const other = 1;\n").unwrap();
+    let outside_file = outside.path().join("other.ts");
+    let outside_file = outside_file.to_str().unwrap();
+
+    let check = |args: &[&str]| {
+        let output = run_sweepr_in(project.path(), &[&["check"], args].concat());
+        assert_eq!(output.status.code(), Some(3), "{:?}", args);
+        String::from_utf8_lossy(&output.stderr).into_owned()
+    };
+
+    // Without a snapshot the recheck falls back to a full run, which checks the file the same way
+    for cache in [&["--no-cache"][..], &[]] {
+        let stderr = check(&[cache, &["--file", "src/nope.ts"]].concat());
+        assert!(stderr.contains("File not found: "), "{}", stderr);
+        let stderr = check(&[cache, &["--file", outside_file]].concat());
+        assert!(stderr.contains("is outside the project"), "{}", stderr);
+        let stderr = check(&[cache, &["--file", "README.md"]].concat());
+        assert!(stderr.contains("README.md is not a JavaScript"), "{}", stderr);
+
+        // A full run saves the snapshot the second pass rechecks against
+        assert!(run_sweepr_in(project.path(), &["check"]).status.success());
+    }
+}
+
+#[test]
+fn test_parse_failures_are_reported_without_aborting_the_run() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "import { a } from './a';\nimport { b } from './b';\nconsole.log(a, b);\n"),
+        ("src/a.ts", "export const a = 1;\nexport const unused = 2;\n"),
+        ("src/b.ts", "export const b = ;\n"),
+        ("src/broken.ts", "function (\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    // The other files are still analyzed, and broken ones aren't reported as unused
+    assert_eq!(report["unused_exports"][0]["name"], "unused", "{}", report);
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+    let failures: Vec<(String, String)> = report["parse_failures"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|failure| {
+            let path = failure["path"].as_str().unwrap();
+            let name = path.rsplit('/').next().unwrap().to_string();
+            (name, failure["message"].as_str().unwrap().to_string())
+        })
+        .collect();
+    assert_eq!(failures.len(), 2, "{}", report);
+    assert_eq!(failures[0], ("b.ts".to_string(), "Unexpected token at 1:18".to_string()));
+    assert_eq!(failures[1].0, "broken.ts");
+
+    // Once fixed, the file joins the graphs reused from the last run
+    std::fs::write(project.path().join("src/b.ts"), "export const b = 2;\n").unwrap();
+    std::fs::remove_file(project.path().join("src/broken.ts")).unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(report["parse_failures"].as_array().unwrap().is_empty(), "{}", report);
+}
+
+#[test]
+fn test_unwritable_output_exits_with_io_status() {
+    let project = write_project(&[("src/index.ts", "console.log(1);\n")]);
+
+    let output = run_sweepr_in(
+        project.path(),
+        &["check", "--no-cache", "--format", "json", "--output", "/nonexistent/dir/out.json"],
+    );
+    // Distinct from a parse failure (2) or bad configuration (3)
+    assert_eq!(output.status.code(), Some(4), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("IO error"));
+}
+
+#[test]
+fn test_findings_carry_stable_rule_codes() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "export const unused = 1;\n"),
+        ("src/orphan.ts", "console.log('orphan');\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("SWP004 unused-files Unused file"), "{}", stdout);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let codes: Vec<(&str, &str)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| (finding["code"].as_str().unwrap(), finding["rule"].as_str().unwrap()))
+        .collect();
+    assert_eq!(codes, [("SWP002", "unused-exports"), ("SWP004", "unused-files")], "{}", report);
+
+    // Codes select rules wherever names do
+    let output = run_sweepr_in(project.path(), &["check", "--quiet", "--no-cache", "--fail-on", "SWP001"]);
+    assert_eq!(output.status.code(), Some(0));
+    let output = run_sweepr_in(project.path(), &["check", "--quiet", "--no-cache", "--fail-on", "SWP004"]);
+    assert_eq!(output.status.code(), Some(1));
+
+    std::fs::write(project.path().join("sweepr.config.json"), r#"{ "rules": { "SWP004": false } }"#).unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+    assert_eq!(report["unused_exports"].as_array().unwrap().len(), 1, "{}", report);
+}
+
+#[test]
+fn test_ignore_comments_silence_findings_by_code() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        (
+            "src/index.ts",
+            "// sweepr-ignore-next-line SWP002 loaded by the plugin host\n\
+             export const activate = 1;\n\
+             // sweepr-ignore-next-line SWP004\n\
+             export const wrongCode = 2;\n\
+             /* sweepr-ignore-next-line */\n\
+             export const anyCode = 3;\n\
+             export const reported = 4;\n",
+        ),
+        ("src/orphan.ts", "// sweepr-ignore-file SWP004, SWP015\nconst local = 1;\n"),
+        ("src/stale.ts", "// sweepr-ignore-file SWP015\nexport {};\n"),
+        ("sweepr.config.json", r#"{ "rules": { "SWP015": true } }"#),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings: Vec<(&str, &str)> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| {
+            let path = finding["path"].as_str().unwrap();
+            (finding["code"].as_str().unwrap(), path.rsplit('/').next().unwrap())
+        })
+        .collect();
+    let exports: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(exports, ["wrongCode", "reported"], "{}", report);
+    // Only the file comment naming the rule silences the unused file
+    assert_eq!(
+        findings,
+        [("SWP002", "index.ts"), ("SWP002", "index.ts"), ("SWP004", "stale.ts")],
+        "{}",
+        report
+    );
+}
+
+#[test]
+fn test_baseline_hides_recorded_findings() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts" }"#,
+        ),
+        ("src/index.ts", "export const legacy = 1;\n"),
+        ("src/old.ts", "console.log('old');\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--quiet", "--no-cache", "--baseline", "baseline.json"]);
+    assert_eq!(output.status.code(), Some(3));
+
+    let output = run_sweepr_in(
+        project.path(),
+        &["check", "--quiet", "--no-cache", "--baseline", "baseline.json", "--update-baseline"],
+    );
+    assert_eq!(output.status.code(), Some(0));
+    let baseline: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(project.path().join("baseline.json")).unwrap()).unwrap();
+    assert_eq!(
+        baseline,
+        serde_json::json!({ "findings": [
+            { "code": "SWP002", "path": "src/index.ts", "symbol": "legacy" },
+            { "code": "SWP004", "path": "src/old.ts" },
+        ] })
+    );
+
+    // Moving a recorded export within its file keeps it hidden; new findings are reported
+    std::fs::write(project.path().join("src/index.ts"), "\nexport const added = 2;\nexport const legacy = 1;\n").unwrap();
+    let output = run_sweepr_in(
+        project.path(),
+        &["check", "--format", "json", "--no-cache", "--baseline", "baseline.json"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let findings: Vec<&str> = report["findings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|finding| finding["symbol"].as_str().unwrap())
+        .collect();
+    assert_eq!(findings, ["added"], "{}", report);
+}
+
+#[test]
+fn test_sarif_format_reports_findings_under_rule_codes() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts", "dependencies": { "left-pad": "^1.3.0" } }"#,
+        ),
+        ("src/index.ts", "export const unused = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "sarif", "--no-cache"]);
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "sweepr");
+    assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "SWP002", "{}", sarif);
+    assert_eq!(run["tool"]["driver"]["rules"][1]["name"], "unused-exports", "{}", sarif);
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2, "{}", sarif);
+    assert_eq!(results[0]["ruleId"], "SWP001");
+    assert_eq!(results[0]["message"]["text"], "Unused dependency 'left-pad'");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "package.json");
+    assert!(location.get("region").is_none(), "{}", sarif);
+
+    assert_eq!(results[1]["ruleId"], "SWP002");
+    assert_eq!(results[1]["ruleIndex"], 1);
+    let location = &results[1]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "src/index.ts");
+    assert_eq!(location["artifactLocation"]["uriBaseId"], "%SRCROOT%");
+    assert_eq!(location["region"]["startLine"], 1);
+    assert_eq!(location["region"]["startColumn"], 14);
+}
+
+#[test]
+fn test_deps_lists_the_files_importing_each_dependency() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+                "name": "app",
+                "main": "src/index.ts",
+                "dependencies": { "lodash": "^4.17.21", "react": "^18.0.0" },
+                "devDependencies": { "left-pad": "^1.3.0" }
+            }"#,
+        ),
+        (
+            "src/index.ts",
+            "import { map } from 'lodash';\nimport debounce from 'lodash/debounce';\nimport type { FC } from 'react';\nimport './b';\nconsole.log(map, debounce);\n",
+        ),
+        ("src/b.ts", "import { uniq } from 'lodash';\nconsole.log(uniq);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["deps", "--no-cache"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("left-pad@^1.3.0 (devDependencies in package.json): not imported"), "{}", stdout);
+    assert!(
+        stdout.contains("lodash@^4.17.21 (dependencies in package.json): 3 imports in 2 files\n  src/index.ts (2)\n  src/b.ts (1)\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("src/index.ts (1, types only)"), "{}", stdout);
+
+    let output = run_sweepr_in(project.path(), &["deps", "--no-cache", "--json", "--package", "lodash"]);
+    let usages: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(usages.as_array().unwrap().len(), 1, "{}", usages);
+    assert_eq!(usages[0]["imports"], 3);
+    assert_eq!(usages[0]["files"][0]["imports"], 2);
+    assert_eq!(usages[0]["files"][0]["type_only"], false);
+
+    let output = run_sweepr_in(project.path(), &["deps", "--no-cache", "--package", "moment"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("moment is not declared in any package.json"));
+}
+
+#[test]
+fn test_namespace_imports_use_only_accessed_members() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.tsx" }"#,
+        ),
+        (
+            "src/index.tsx",
+            "import * as utils from './utils';\nimport * as ui from './ui';\nimport * as all from './all';\n\
+             export const date: utils.DateLike = utils.formatDate();\nexport const page = <ui.Button />;\n\
+             console.log(Object.keys(all));\n",
+        ),
+        (
+            "src/utils.ts",
+            "export type DateLike = string;\nexport const formatDate = () => '';\nexport const parseDate = () => 0;\n",
+        ),
+        ("src/ui.tsx", "export const Button = () => null;\nexport const Card = () => null;\n"),
+        ("src/all.ts", "export const a = 1;\nexport const b = 2;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .filter(|name| !["date", "page"].contains(name))
+        .collect();
+    unused.sort();
+    // `all` escapes into a call, so every export of it stays used
+    assert_eq!(unused, vec!["Card", "parseDate"], "{}", report);
+    assert!(report["unused_types"].as_array().unwrap().is_empty(), "{}", report);
+}
+
+#[test]
+fn test_namespace_re_exports() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "main": "src/index.ts" }"#),
+        ("src/index.ts", "import { math } from './lib';\nconsole.log(math.add(1, 2));\n"),
+        ("src/lib.ts", "export * as math from './math';\nexport * as strings from './strings';\n"),
+        ("src/math.ts", "export const add = (a: number, b: number) => a + b;\nexport const sub = 0;\n"),
+        ("src/strings.ts", "export const upper = (s: string) => s.toUpperCase();\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    unused.sort();
+    // Using `math` keeps all of math.ts alive; nothing uses `strings`
+    assert_eq!(unused, vec!["strings", "upper"], "{}", report);
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+}
+
+#[test]
+fn test_dependencies_imported_only_as_types() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "app", "main": "src/index.ts", "dependencies": { "zod": "^3.0.0", "express": "^4.0.0", "ky": "^1.0.0" } }"#,
+        ),
+        (
+            "src/index.ts",
+            "import type { ZodType } from 'zod';\nimport type { Request } from 'express';\n\
+             import express from 'express';\nimport type { Options } from 'ky';\n\
+             export const app = express();\nexport let schema: ZodType | Request | Options;\n",
+        ),
+        ("src/client.ts", "import ky from 'ky';\nexport const client = ky;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = report["type_only_dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dep| dep["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["zod"], "{}", report);
+
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Type-only Dependencies (1)"), "{}", stdout);
+    assert!(stdout.contains("consider moving them to devDependencies"), "{}", stdout);
+}
+
+#[test]
+fn test_monorepo_findings_are_grouped_per_package() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "root", "private": true, "workspaces": ["packages/*"], "main": "index.ts" }"#,
+        ),
+        ("index.ts", "export {};\n"),
+        (
+            "packages/api/package.json",
+            r#"{ "name": "api", "main": "src/index.ts", "dependencies": { "left-pad": "^1.0.0" } }"#,
+        ),
+        ("packages/api/src/index.ts", "export const handler = 1;\n"),
+        ("packages/api/src/dead.ts", "export {};\n"),
+        ("packages/web/package.json", r#"{ "name": "web", "main": "src/index.ts" }"#),
+        ("packages/web/src/index.ts", "export {};\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let packages = report["packages"].as_array().unwrap();
+    let api = packages.iter().find(|package| package["name"] == "api").unwrap();
+    assert_eq!(api["total"], 3, "{}", report);
+    assert_eq!(api["counts"]["unused-deps"], 1, "{}", report);
+    assert_eq!(api["counts"]["unused-files"], 1, "{}", report);
+    let web = packages.iter().find(|package| package["name"] == "web").unwrap();
+    assert_eq!(web["total"], 0, "{}", report);
+
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("📦 api (3: unused-deps 1, unused-exports 1, unused-files 1)"), "{}", stdout);
+    assert!(!stdout.contains("📦 web"), "{}", stdout);
+    assert!(stdout.contains("in 3 packages"), "{}", stdout);
+}
+
+#[test]
+fn test_nx_targets_and_turbo_tasks_seed_entry_points() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*"], "dependencies": { "left-pad": "^1.0.0" } }"#,
+        ),
+        ("nx.json", "{}"),
+        ("turbo.json", r#"{ "tasks": { "build": {}, "docs#gen": {} } }"#),
+        (
+            "apps/web/project.json",
+            r#"{
+                "name": "web",
+                "implicitDependencies": ["left-pad", "!lodash"],
+                "targets": { "build": { "options": { "main": "{projectRoot}/src/main.ts" } } }
+            }"#,
+        ),
+        ("apps/web/src/main.ts", "export {};\n"),
+        ("apps/web/src/orphan.ts", "export {};\n"),
+        (
+            "packages/docs/package.json",
+            r#"{
+                "name": "docs",
+                "scripts": { "build": "tsx scripts/build.ts --out dist", "gen": "tsx scripts/gen.ts" },
+                "dependencies": { "lodash": "^4.0.0" }
+            }"#,
+        ),
+        ("packages/docs/scripts/build.ts", "export {};\n"),
+        ("packages/docs/scripts/gen.ts", "export {};\n"),
+    ]);
+
+    let packages = sweepr::workspaces::discover_packages(project.path()).unwrap();
+    let web = packages.iter().find(|p| p.name.as_deref() == Some("web")).unwrap();
+    assert_eq!(web.entry_points, ["src/main.ts"]);
+    assert_eq!(web.implicit_dependencies, ["left-pad"]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("apps/web/src/orphan.ts"), "{}", stdout);
+    assert!(stdout.contains("lodash"), "{}", stdout);
+    for kept in ["main.ts", "build.ts", "gen.ts", "left-pad"] {
+        assert!(!stdout.contains(kept), "{}", stdout);
+    }
+}
+
+#[test]
+fn test_pnpm_workspace_yaml_takes_precedence() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "root", "workspaces": ["ignored/*"] }"#),
+        ("pnpm-workspace.yaml", "packages:\n  - 'apps/*'\n"),
+        ("apps/web/package.json", r#"{ "name": "web" }"#),
+        ("ignored/x/package.json", r#"{ "name": "x" }"#),
+    ]);
+
+    let packages = sweepr::workspaces::discover_packages(project.path()).unwrap();
+    let names: Vec<_> = packages.iter().filter_map(|p| p.name.as_deref()).collect();
+    assert_eq!(names, ["root", "web"]);
+}
+
+#[test]
+fn test_parse_cache_reuses_unchanged_files_and_picks_up_edits() {
+    let project = write_project(&[
+        ("src/index.ts", "import { a } from './a';\nconsole.log(a);\n"),
+        ("src/a.ts", "export const a = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Parsed 2 files"));
+    assert!(!project.path().join(".sweepr/cache").exists());
+
+    let output = run_sweepr_in(project.path(), &["check"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("(0 unchanged, from cache)"));
+    assert!(project.path().join(".sweepr/cache/parse.bin").exists());
+
+    // Without the graph snapshot, every file's parse still comes from the cache
+    std::fs::remove_file(project.path().join(".sweepr/cache/graphs.bin")).unwrap();
+    let output = run_sweepr_in(project.path(), &["check"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("(2 unchanged, from cache)"));
+
+    // An edited file is parsed again rather than served from the cache
+    std::fs::write(project.path().join("src/a.ts"), "export const = ;\n").unwrap();
+    let output = run_sweepr_in(project.path(), &["check"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Parse Failures (1)"));
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_large_files_are_parsed_like_small_ones() {
+    // Large enough to be memory-mapped rather than read into a buffer
+    let mut large = String::from("export const used = 1;\n");
+    while large.len() < 300 * 1024 {
+        large.push_str("// padding comment to grow the file past the mapping threshold\n");
+    }
+    large.push_str("export const unused = 2;\n");
+    let project = write_project(&[
+        ("src/index.ts", "import { used } from './large';\nconsole.log(used);\n"),
+        ("src/large.ts", &large),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let exports: Vec<&str> = json["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(exports, ["unused"]);
+}
+
+#[test]
+fn test_cache_subcommand_reports_and_clears_the_cache() {
+    let project = write_project(&[
+        ("src/index.ts", "import './lib';\n"),
+        ("src/lib.ts", "export {};\n"),
+    ]);
+    let run = |args: &[&str]| String::from_utf8_lossy(&run_sweepr_in(project.path(), args).stdout).to_string();
+
+    run(&["check"]);
+    let path = run(&["cache", "path"]);
+    assert!(path.trim_end().ends_with(".sweepr/cache"));
+    let stats = run(&["cache", "stats"]);
+    assert!(stats.contains("Parsed files: 2"));
+    assert!(stats.contains("Graph snapshot: yes"));
+
+    assert!(run(&["cache", "clear"]).contains("Cleared"));
+    assert!(!project.path().join(".sweepr/cache").exists());
+    assert!(run(&["cache", "stats"]).contains("Parsed files: 0"));
+}
+
+#[test]
+fn test_config_errors_exit_with_status_3() {
+    let project = write_project(&[
+        ("src/index.ts", "export {};\n"),
+        ("sweepr.config.json", "{ \"entry\": "),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check"]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "nope"]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_init_writes_a_config_from_the_detected_project() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "main": "./src/main.ts", "dependencies": { "next": "14" }, "devDependencies": { "cypress": "13" } }"#,
+        ),
+        ("src/main.ts", "console.log(1);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["init", "--yes"]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let content = std::fs::read_to_string(project.path().join("sweepr.config.json")).unwrap();
+    let config: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(config["entry"], serde_json::json!(["src/main.ts"]));
+    assert_eq!(config["framework"], "next");
+    assert!(config["tests"].as_array().unwrap().contains(&serde_json::json!("cypress/**")));
+
+    // The written config is picked up, and never silently replaced
+    let output = run_sweepr_in(project.path(), &["check", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Entry points: 1"), "{}", stdout);
+    let output = run_sweepr_in(project.path(), &["init", "--yes"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_graph_command_dumps_json_from_a_file_to_a_depth() {
+    let project = write_project(&[
+        ("src/index.ts", "import { a } from './a';\nconsole.log(a);\n"),
+        ("src/a.ts", "import { b } from './b';\nimport { x } from './missing';\nexport const a = b + x;\n"),
+        ("src/b.ts", "import { c } from './c';\nexport const b = c;\n"),
+        ("src/c.ts", "export const c = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["graph", "--format", "json", "--from", "src/a.ts", "--depth", "1"]);
+    assert!(output.status.success());
+    let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let files: Vec<&str> = graph["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| file["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(files, vec!["src/a.ts", "src/b.ts"]);
+
+    let imports: Vec<(&str, bool)> = graph["imports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|import| (import["specifier"].as_str().unwrap(), import["resolved"].as_bool().unwrap()))
+        .collect();
+    assert_eq!(imports, vec![("./b", true), ("./missing", false)]);
+}
+
+#[test]
+fn test_graph_snapshot_is_reused_and_updated_for_changed_files() {
+    let project = write_project(&[
+        ("package.json", r#"{ "dependencies": { "left-pad": "1.0.0" } }"#),
+        ("src/index.ts", "import { a } from './a';\nimport { b } from './b';\nconsole.log(a, b);\n"),
+        ("src/a.ts", "export const a = 1;\nexport const unused = 2;\n"),
+    ]);
+
+    let first = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    assert!(project.path().join(".sweepr/cache/graphs.bin").exists());
+
+    let output = run_sweepr_in(project.path(), &["check"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Reused analysis graphs (0 files changed)"));
+    let second = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    assert_eq!(first.stdout, second.stdout);
+
+    // A new file resolves the dangling import, and an edit drops the unused export
+    std::fs::write(project.path().join("src/b.ts"), "export const b = 1;\n").unwrap();
+    std::fs::write(project.path().join("src/a.ts"), "export const a = 1;\n").unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("unused-exports"), "{}", stdout);
+
+    // A changed manifest invalidates the snapshot
+    std::fs::write(project.path().join("package.json"), "{}").unwrap();
+    let output = run_sweepr_in(project.path(), &["check"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Built analysis graphs"), "{}", stdout);
+    assert!(!stdout.contains("left-pad"), "{}", stdout);
+}
+
+#[test]
+fn test_duplicate_exports_report_each_file_and_its_usages() {
+    let project = write_project(&[
+        (
+            "src/index.ts",
+            "import { format } from './a';\nimport { x } from './barrel';\nconsole.log(format, x);\n",
+        ),
+        ("src/a.ts", "export function format() {}\nexport default 1;\n"),
+        ("src/b.ts", "export const format = 1;\nexport const x = 2;\nexport default 2;\n"),
+        ("src/barrel.ts", "export { x } from './b';\nexport * from './a';\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let duplicates = report["duplicate_exports"].as_array().unwrap();
+
+    // Re-exports and default exports are not duplicates
+    assert_eq!(duplicates.len(), 1, "{}", report);
+    assert_eq!(duplicates[0]["name"], "format");
+    let usages: Vec<(String, u64)> = duplicates[0]["locations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|location| {
+            let file = location["file"].as_str().unwrap();
+            (file.rsplit('/').next().unwrap().to_string(), location["usages"].as_u64().unwrap())
+        })
+        .collect();
+    assert_eq!(usages, vec![("a.ts".to_string(), 1), ("b.ts".to_string(), 0)]);
+}
+
+#[test]
+fn test_tool_config_files_mark_their_plugins_as_used() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+  "devDependencies": {
+    "eslint": "9",
+    "eslint-plugin-react": "7",
+    "@typescript-eslint/eslint-plugin": "8",
+    "@babel/preset-env": "7",
+    "jest-environment-jsdom": "29",
+    "autoprefixer": "10",
+    "left-pad": "1"
+  },
+  "jest": { "testEnvironment": "jsdom" }
+}"#,
+        ),
+        (".eslintrc.yml", "extends:\n  - plugin:react/recommended\n  - plugin:@typescript-eslint/recommended\n"),
+        (".babelrc", r#"{ "presets": ["@babel/env"] }"#),
+        ("postcss.config.js", "module.exports = { plugins: { autoprefixer: {} } };\n"),
+        ("src/index.ts", "export {};\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused: Vec<&str> = stdout.lines().filter(|line| line.contains("unused-deps")).collect();
+
+    assert_eq!(unused.len(), 1, "{}", stdout);
+    assert!(unused[0].contains("'left-pad'"), "{}", stdout);
+}
+
+#[test]
+fn test_unlisted_dependencies_are_reported_per_import() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+  "dependencies": { "react": "18", "left-pad": "1" },
+  "devDependencies": { "@types/express": "4" },
+  "peerDependencies": { "vue": "3", "react-dom": "18" }
+}"#,
+        ),
+        (
+            "src/index.ts",
+            "import React from 'react';\nimport fs from 'node:fs';\nimport path from 'path';\n\
+             import { x } from '@scope/thing/sub';\nimport e from 'express';\nimport v from 'vue';\n\
+             import a from '@/alias';\nimport chalk from 'chalk';\nconsole.log(React, fs, path, x, e, v, a, chalk);\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names = |key: &str| -> Vec<String> {
+        report[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Built-ins, aliases, peer dependencies, and packages typed by `@types/*` count as listed
+    assert_eq!(names("unlisted_dependencies"), vec!["@scope/thing", "chalk"]);
+    assert_eq!(names("unused_dependencies"), vec!["left-pad"]);
+    assert_eq!(names("unused_peer_dependencies"), vec!["react-dom"]);
+}
+
+#[test]
+fn test_unresolved_imports_are_reported_with_their_specifier() {
+    let project = write_project(&[
+        (".gitignore", "generated/\n"),
+        (
+            "src/index.ts",
+            "import { a } from './utlis';\nimport { api } from '../generated/api';\nconsole.log(a, api);\n",
+        ),
+        ("src/utils.ts", "export const a = 1;\n"),
+        ("generated/api.ts", "export const api = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unresolved: Vec<&str> = stdout.lines().filter(|line| line.contains("unresolved-imports")).collect();
+
+    // An ignored file that exists is not a broken import
+    assert_eq!(unresolved.len(), 1, "{}", stdout);
+    assert!(unresolved[0].contains("index.ts"), "{}", stdout);
+    assert!(unresolved[0].contains("Unresolved import './utlis'"), "{}", stdout);
+}
+
+#[test]
+fn test_findings_are_located_by_line_and_column() {
+    let project = write_project(&[
+        ("src/index.ts", "// héllo\nimport { a } from './lib';\n  import { b } from './missing';\nconsole.log(a, b);\n"),
+        ("src/lib.ts", "export const a = 1;\n/* ünïcode */ export const unused = 2;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let export = &json["unused_exports"][0];
+    assert_eq!(export["name"], "unused");
+    assert_eq!((export["line"].as_u64(), export["column"].as_u64()), (Some(2), Some(28)));
+    let import = &json["unresolved_imports"][0];
+    assert_eq!((import["line"].as_u64(), import["column"].as_u64()), (Some(3), Some(21)));
+}
+
+#[test]
+fn test_unused_files_importing_each_other_are_grouped_into_chains() {
+    let project = write_project(&[
+        ("src/index.ts", "console.log(1);\n"),
+        ("src/old/a.ts", "import { b } from './b';\nimport { c } from './c';\nconsole.log(b, c);\n"),
+        ("src/old/b.ts", "import { c } from './c';\nexport const b = c;\n"),
+        ("src/old/c.ts", "export const c = 1;\n"),
+        ("src/lonely.ts", "export const l = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(report["unused_files"].as_array().unwrap().len(), 4, "{}", report);
+
+    let chains = report["dead_chains"].as_array().unwrap();
+    assert_eq!(chains.len(), 1, "{}", report);
+    let roots = chains[0]["roots"].as_array().unwrap();
+    assert_eq!(roots.len(), 1);
+    assert!(roots[0].as_str().unwrap().ends_with("a.ts"));
+    assert_eq!(chains[0]["files"].as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_exports_used_only_by_tests_are_reported() {
+    let project = write_project(&[
+        ("src/index.ts", "import { format } from './lib';\nconsole.log(format(1));\n"),
+        (
+            "src/lib.ts",
+            "export const format = (n: number) => `${n}`;\nexport const parse = (s: string) => Number(s);\n\
+             export const unused = 1;\n",
+        ),
+        (
+            "src/lib.test.ts",
+            "import { format, parse } from './lib';\nconsole.log(format(1), parse('1'));\n",
+        ),
+        ("src/__tests__/parse.ts", "import { parse } from '../lib';\nconsole.log(parse('2'));\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    // Test files are entry points, so their imports keep `parse` out of the unused exports
+    let test_only = report["test_only_exports"].as_array().unwrap();
+    assert_eq!(test_only.len(), 1, "{}", report);
+    assert_eq!(test_only[0]["name"], "parse");
+    assert_eq!(test_only[0]["tests"].as_array().unwrap().len(), 2);
+
+    let unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(unused, vec!["unused"]);
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+}
+
+#[test]
+fn test_storybook_stories_are_entry_points() {
+    let project = write_project(&[
+        ("src/index.ts", "console.log(1);\n"),
+        ("src/Button.tsx", "export const Button = () => null;\nexport const unused = 1;\n"),
+        (
+            "src/Button.stories.tsx",
+            "import { Button } from './Button';\nexport default { component: Button };\n",
+        ),
+        (".storybook/preview.ts", "import '../src/theme';\n"),
+        ("src/theme.ts", "export {};\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+    let unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(unused, vec!["unused"], "{}", report);
+    assert!(report["story_only_exports"].as_array().unwrap().is_empty());
+
+    // Strict mode reports what only stories keep alive
+    std::fs::write(
+        project.path().join("sweepr.config.json"),
+        r#"{ "rules": { "story_only_exports": true } }"#,
+    )
+    .unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let story_only = report["story_only_exports"].as_array().unwrap();
+    assert_eq!(story_only.len(), 1, "{}", report);
+    assert_eq!(story_only[0]["name"], "Button");
+    assert!(story_only[0]["stories"][0].as_str().unwrap().ends_with("Button.stories.tsx"));
+}
+
+#[test]
+fn test_runner_setup_files_and_environments_are_used() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+                "name": "app",
+                "main": "src/index.ts",
+                "devDependencies": {
+                    "vitest": "1.0.0",
+                    "happy-dom": "1.0.0",
+                    "jest": "29.0.0",
+                    "jest-environment-jsdom": "29.0.0",
+                    "left-pad": "1.0.0"
+                },
+                "jest": { "testEnvironment": "jsdom", "setupFiles": ["<rootDir>/jest.setup.js"] }
+            }"#,
+        ),
+        ("src/index.ts", "console.log(1);\n"),
+        (
+            "vitest.config.ts",
+            "import { defineConfig } from 'vitest/config';\n\
+             export default defineConfig({ test: { environment: 'happy-dom', setupFiles: ['./test/setup.ts'], \
+             globalSetup: './test/global.ts' } });\n",
+        ),
+        ("test/setup.ts", "import './helpers';\n"),
+        ("test/helpers.ts", "export {};\n"),
+        ("test/global.ts", "export default () => {};\n"),
+        ("jest.setup.js", "globalThis.ready = true;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+    let unused: Vec<&str> = report["unused_dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dep| dep["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(unused, vec!["left-pad"], "{}", report);
+    assert!(report["unused_exports"].as_array().unwrap().is_empty(), "{}", report);
+}
+
+#[test]
+fn test_jsdoc_public_exports_are_kept_unless_tags_are_disabled() {
+    let project = write_project(&[
+        ("src/index.ts", "import './lib';\n"),
+        (
+            "src/lib.ts",
+            "/** Formats a value.\n * @public\n */\nexport function format() {}\n\
+             /** @public */\nexport interface Options {}\n\
+             /** @public @internal */\nexport const helper = 1;\n\
+             /** @internal */\nexport const secret = 2;\n",
+        ),
+    ]);
+
+    let unused = |config: &str| -> Vec<String> {
+        std::fs::write(project.path().join("sweepr.config.json"), config).unwrap();
+        let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        ["unused_exports", "unused_types"]
+            .iter()
+            .flat_map(|key| report[*key].as_array().unwrap().clone())
+            .map(|export| export["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let mut tagged = unused(r#"{ "entry": ["src/index.ts"] }"#);
+    tagged.sort();
+    assert_eq!(tagged, vec!["helper", "secret"]);
+
+    let mut untagged = unused(r#"{ "entry": ["src/index.ts"], "rules": { "jsdoc_tags": false } }"#);
+    untagged.sort();
+    assert_eq!(untagged, vec!["Options", "format", "helper", "secret"]);
+}
+
+#[test]
+fn test_dependencies_run_by_package_scripts_are_used() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+                "name": "app",
+                "scripts": {
+                    "build": "NODE_ENV=production tsup src/index.ts && npx tsc --noEmit",
+                    "lint": "cross-env CI=1 biome check .",
+                    "test": "yarn vitest run"
+                },
+                "devDependencies": {
+                    "tsup": "8.0.0",
+                    "typescript": "5.0.0",
+                    "cross-env": "7.0.0",
+                    "@biomejs/biome": "1.0.0",
+                    "vitest": "1.0.0",
+                    "rimraf": "5.0.0"
+                }
+            }"#,
+        ),
+        ("node_modules/typescript/package.json", r#"{ "bin": { "tsc": "bin/tsc", "tsserver": "bin/tsserver" } }"#),
+        ("node_modules/@biomejs/biome/package.json", r#"{ "bin": { "biome": "bin/biome" } }"#),
+        ("src/index.ts", "console.log(1);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused: Vec<&str> = report["unused_dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dep| dep["name"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(unused, vec!["rimraf"]);
+}
+
+#[test]
+fn test_scripts_match_known_binaries_of_packages_that_are_not_installed() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+                "name": "app",
+                "scripts": {
+                    "build": "tsc --noEmit && ng build",
+                    "e2e": "playwright test",
+                    "dev": "run-p watch serve"
+                },
+                "devDependencies": {
+                    "typescript": "5.0.0",
+                    "@angular/cli": "17.0.0",
+                    "@playwright/test": "1.40.0",
+                    "npm-run-all": "4.1.5",
+                    "rimraf": "5.0.0"
+                }
+            }"#,
+        ),
+        ("src/index.ts", "console.log(1);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused: Vec<&str> = report["unused_dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dep| dep["name"].as_str().unwrap())
+        .collect();
+
+    assert_eq!(unused, vec!["rimraf"]);
+}
+
+#[test]
+fn test_types_packages_follow_their_runtime_package() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+  "scripts": { "build": "tsc" },
+  "dependencies": { "lodash": "4", "@babel/core": "7", "moment": "2" },
+  "devDependencies": {
+    "typescript": "5",
+    "@types/lodash": "4",
+    "@types/babel__core": "7",
+    "@types/moment": "2",
+    "@types/typescript": "2",
+    "@types/jquery": "3",
+    "@types/node": "20"
+  }
+}"#,
+        ),
+        (".babelrc", r#"{ "presets": [] }"#),
+        ("node_modules/typescript/package.json", r#"{ "bin": { "tsc": "bin/tsc" } }"#),
+        ("src/index.ts", "import _ from 'lodash';\nconsole.log(_);\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names = |key: &str| -> Vec<String> {
+        let mut names: Vec<String> = report[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    // Types of imported, configured, and script-run packages are used along with them
+    assert_eq!(names("unused_dependencies"), vec!["@types/moment", "@types/node", "moment"]);
+    assert_eq!(names("orphaned_types"), vec!["@types/jquery"]);
+}
+
+#[test]
+fn test_unused_files_with_declared_side_effects_are_marked() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "sideEffects": ["./src/polyfill.ts", "*.css.ts"] }"#),
+        ("src/index.ts", "console.log(1);\n"),
+        ("src/polyfill.ts", "console.log('patched');\n"),
+        ("src/theme/button.css.ts", "console.log('styled');\n"),
+        ("src/dead.ts", "export const dead = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let mut files: Vec<(String, bool)> = report["unused_files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| {
+            let path = file["path"].as_str().unwrap();
+            let name = path.rsplit('/').next().unwrap().to_string();
+            (name, file["has_side_effects"].as_bool().unwrap())
+        })
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            ("button.css.ts".to_string(), true),
+            ("dead.ts".to_string(), false),
+            ("polyfill.ts".to_string(), true),
+        ]
+    );
+
+    let output = run_sweepr_in(project.path(), &["check", "--show-fixes", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("package.json declares side effects for this file"), "{}", stdout);
+}
+
+#[test]
+fn test_unused_types_are_reported_apart_from_exports_and_can_be_disabled() {
+    let project = write_project(&[
+        (
+            "src/index.ts",
+            "import { helper, type Used } from './lib';\nconst u: Used = helper();\nconsole.log(u);\n",
+        ),
+        (
+            "src/lib.ts",
+            "export interface Used {}\nexport interface Props {}\nexport type Alias = string;\n\
+             type Local = number;\nexport type { Local };\nexport function helper(): Used { return {}; }\n\
+             export const unusedValue = 1;\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names = |report: &serde_json::Value, key: &str| -> Vec<String> {
+        let mut names: Vec<String> = report[key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|entry| entry["file"].as_str().unwrap().ends_with("lib.ts"))
+            .map(|entry| entry["name"].as_str().unwrap().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    assert_eq!(names(&report, "unused_types"), vec!["Alias", "Local", "Props"]);
+    assert_eq!(names(&report, "unused_exports"), vec!["unusedValue"]);
+
+    std::fs::write(
+        project.path().join("sweepr.config.json"),
+        r#"{ "rules": { "unused_types": false } }"#,
+    )
+    .unwrap();
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(names(&report, "unused_types").is_empty());
+    assert_eq!(names(&report, "unused_exports"), vec!["unusedValue"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_follow_symlinks_scans_linked_directories_once() {
+    let project = write_project(&[
+        ("src/index.ts", "export {};\n"),
+        ("shared/util.ts", "const x = 1;\n"),
+    ]);
+    let root = project.path();
+    std::os::unix::fs::symlink(root.join("shared"), root.join("src/shared")).unwrap();
+    std::os::unix::fs::symlink(root.join("src"), root.join("shared/loop")).unwrap();
+
+    let output = run_sweepr_in(root, &["check", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("util.ts").count(), 1, "{}", stdout);
+
+    let output = run_sweepr_in(root, &["check", "--no-cache", "--follow-symlinks"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Found 2 files"), "{}", stdout);
+}
+
+#[test]
+fn test_minified_and_oversized_files_are_skipped_and_reported() {
+    let long_line = format!("var a={};\n", "1+".repeat(700) + "1");
+    let project = write_project(&[
+        ("sweepr.config.json", r#"{ "entry": ["src/index.ts"], "max_file_size": 2000 }"#),
+        ("src/index.ts", "export {};\n"),
+        ("src/vendor.min.js", "var a=1;\n"),
+        ("src/bundle.js", &long_line),
+        ("src/huge.ts", &"const x = 1;\n".repeat(200)),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let skipped: Vec<(String, String)> = json["skipped_files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|file| {
+            let path = file["path"].as_str().unwrap();
+            let name = path.rsplit('/').next().unwrap().to_string();
+            (name, file["reason"].as_str().unwrap().to_string())
+        })
+        .collect();
+
+    assert_eq!(skipped.len(), 3, "{:?}", skipped);
+    assert!(skipped.contains(&("vendor.min.js".into(), "minified".into())));
+    assert!(skipped.contains(&("bundle.js".into(), "minified".into())));
+    assert!(skipped.contains(&("huge.ts".into(), "oversized".into())));
+    assert!(json["unused_files"].as_array().unwrap().is_empty());
+}
+
+#[test]
+fn test_include_and_exclude_flags_restrict_the_scan() {
+    let project = write_project(&[
+        ("packages/web/index.ts", "export {};\n"),
+        ("packages/web/orphan.ts", "const x = 1;\n"),
+        ("packages/web/legacy/old.ts", "const x = 1;\n"),
+        ("packages/api/orphan.ts", "const x = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(
+        project.path(),
+        &[
+            "check",
+            "--entry",
+            "packages/web/index.ts",
+            "--include",
+            "packages/web/**",
+            "--exclude",
+            "**/legacy/**",
+            "--format",
+            "compact",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("packages/web/orphan.ts"), "{}", stdout);
+    assert!(!stdout.contains("packages/api"), "{}", stdout);
+    assert!(!stdout.contains("old.ts"), "{}", stdout);
+}
+
+#[test]
+fn test_multiple_roots_are_merged_into_one_graph() {
+    let workspace = write_project(&[
+        ("app/src/index.ts", "export {};\n"),
+        ("app/src/orphan.ts", "const x = 1;\n"),
+        ("app/scripts/build.ts", "const x = 1;\n"),
+        ("app/docs/example.ts", "const x = 1;\n"),
+        ("shared/package.json", r#"{ "name": "shared", "dependencies": { "left-pad": "1.0.0" } }"#),
+        ("shared/util.ts", "const x = 1;\n"),
+    ]);
+    let app = workspace.path().join("app");
+
+    let output = run_sweepr_in(
+        &app,
+        &["check", "src", "scripts", "src", "../shared", "--entry", "src/index.ts", "--format", "compact"],
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(stdout.matches("orphan.ts").count(), 1, "{}", stdout);
+    assert!(stdout.contains("build.ts"), "{}", stdout);
+    assert!(stdout.contains("shared/util.ts"), "{}", stdout);
+    assert!(stdout.contains("shared/package.json"), "{}", stdout);
+    assert!(!stdout.contains("example.ts"), "{}", stdout);
+}
+
+#[test]
+fn test_entry_points_are_detected_from_package_json_fields() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{
+                "name": "lib",
+                "main": "./lib/main.js",
+                "bin": { "lib-cli": "./bin/cli.js" },
+                "exports": {
+                    ".": { "import": "./lib/main.js", "require": "./lib/main.cjs" },
+                    "./feature": "./lib/feature.js",
+                    "./*": "./lib/*.js",
+                    "./package.json": "./package.json"
+                }
+            }"#,
+        ),
+        ("lib/main.js", "export {};\n"),
+        ("lib/main.cjs", "module.exports = {};\n"),
+        ("lib/feature.js", "export {};\n"),
+        ("bin/cli.js", "console.log(1);\n"),
+        ("lib/orphan.js", "const x = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.contains("lib/orphan.js"), "{}", stdout);
+    for entry in ["main.js", "main.cjs", "feature.js", "cli.js"] {
+        assert!(!stdout.contains(entry), "{} reported: {}", entry, stdout);
+    }
+}
+
+#[test]
+fn test_framework_route_files_are_entry_points() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "site", "dependencies": { "next": "14.0.0" } }"#),
+        ("pages/index.tsx", "export default function Home() {}\n"),
+        ("pages/api/hello.ts", "export default function handler() {}\n"),
+        ("app/dashboard/page.tsx", "export default function Page() {}\n"),
+        ("middleware.ts", "export function middleware() {}\n"),
+        ("lib/orphan.ts", "const x = 1;\n"),
+        ("apps/other/routes/x.ts", "const x = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused_files: Vec<&str> = stdout.lines().filter(|line| line.contains("unused-files")).collect();
+    let unused_files = unused_files.join("\n");
+
+    assert!(unused_files.contains("lib/orphan.ts"), "{}", stdout);
+    assert!(unused_files.contains("apps/other/routes/x.ts"), "{}", stdout);
+    for route in ["pages/index.tsx", "hello.ts", "page.tsx", "middleware.ts"] {
+        assert!(!unused_files.contains(route), "{} reported: {}", route, stdout);
+    }
+}
+
+#[test]
+fn test_next_conventional_exports_are_not_reported() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "site", "dependencies": { "next": "14.0.0" } }"#),
+        (
+            "pages/index.tsx",
+            "export default function Home() {}\nexport async function getServerSideProps() {}\nexport const helper = 1;\n",
+        ),
+        (
+            "app/blog/page.tsx",
+            "export const revalidate = 60;\nexport async function generateMetadata() {}\nexport { default } from '../../components/post';\n",
+        ),
+        ("app/api/route.ts", "export async function GET() {}\n"),
+        ("components/post.tsx", "export default function Post() {}\nexport const unusedPostHelper = 1;\n"),
+        ("next.config.js", "module.exports = {};\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.contains("unused-exports") || line.contains("unused-files"))
+        .collect();
+
+    assert_eq!(unused.len(), 2, "{}", stdout);
+    for name in ["helper", "unusedPostHelper"] {
+        let message = format!("Unused export '{}'", name);
+        assert!(unused.iter().any(|line| line.contains(&message)), "{}", stdout);
+    }
+}
+
+#[test]
+fn test_remix_route_module_exports_are_not_reported() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "site", "dependencies": { "@remix-run/react": "2.0.0" } }"#),
+        (
+            "app/root.tsx",
+            "export const links = () => [];\nexport function ErrorBoundary() {}\nexport default function App() {}\n",
+        ),
+        (
+            "app/routes/index.tsx",
+            "export async function loader() {}\nexport async function action() {}\nexport const meta = () => [];\nexport const handle = {};\nexport default function Index() {}\nexport const helper = 1;\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused: Vec<&str> = stdout.lines().filter(|line| line.contains("unused-exports")).collect();
+
+    assert_eq!(unused.len(), 1, "{}", stdout);
+    assert!(unused[0].contains("Unused export 'helper'"), "{}", stdout);
+}
+
+#[test]
+fn test_nuxt_conventions_are_entries_and_auto_imports_are_used() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "site", "dependencies": { "nuxt": "3.0.0" } }"#),
+        ("nuxt.config.ts", "export default defineNuxtConfig({});\n"),
+        ("composables/counter.ts", "export const useCounter = () => 1;\nexport function useDouble() {}\n"),
+        ("server/api/hello.ts", "export default defineEventHandler(() => 1);\nexport const stray = 1;\n"),
+        ("plugins/analytics.ts", "export default defineNuxtPlugin(() => {});\n"),
+        ("lib/orphan.ts", "export const x = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let findings: Vec<&str> = stdout.lines().filter(|line| !line.contains("unused-deps")).collect();
+
+    assert_eq!(findings.len(), 2, "{}", stdout);
+    assert!(findings[0].contains("Unused export 'stray'"), "{}", stdout);
+    assert!(findings[1].contains("lib/orphan.ts"), "{}", stdout);
+}
+
+#[test]
+fn test_nuxt_vue_pages_are_analyzed_through_their_scripts() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "site", "dependencies": { "nuxt": "3.0.0" } }"#),
+        (
+            "pages/index.vue",
+            "<script setup lang=\"ts\">\nimport Card from '../widgets/Card.vue';\nimport { format } from '../lib/format';\n</script>\n\n<template>\n  <Card :label=\"format(1)\" />\n</template>\n",
+        ),
+        (
+            "widgets/Card.vue",
+            "<script>\nexport default { props: ['label'] };\n</script>\n<template><p>{{ label }}</p></template>\n",
+        ),
+        ("widgets/Stale.vue", "<template><p>stale</p></template>\n"),
+        ("lib/format.ts", "export const format = (n: number) => `${n}`;\nexport const parse = Number;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let findings: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(findings.len(), 2, "{}", stdout);
+    assert!(findings[0].contains("Unused export 'parse'"), "{}", stdout);
+    assert!(findings[1].contains("widgets/Stale.vue"), "{}", stdout);
+}
+
+#[test]
+fn test_wrangler_main_and_durable_object_classes_are_used() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "worker" }"#),
+        (
+            "wrangler.toml",
+            "name = \"worker\"\nmain = \"src/worker.ts\"\n\n\
+             [durable_objects]\nbindings = [{ name = \"COUNTER\", class_name = \"Counter\" }]\n\n\
+             [[durable_objects.bindings]]\nname = \"ROOM\"\nclass_name = \"Room\"\n\n\
+             [[migrations]]\ntag = \"v1\"\nnew_classes = [\"Counter\", \"Room\", \"Lobby\"]\n",
+        ),
+        (
+            "src/worker.ts",
+            "export class Counter {}\nexport class Room {}\nexport class Lobby {}\nexport const helper = 1;\n\
+             export default { fetch: () => new Response('ok') };\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+
+    let unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(unused, vec!["helper"], "{}", report);
+}
+
+#[test]
+fn test_serverless_handlers_are_entry_points() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "api", "devDependencies": { "serverless": "3.0.0", "serverless-offline": "13.0.0", "left-pad": "1.0.0" } }"#,
+        ),
+        (
+            "serverless.yml",
+            "service: api\nplugins:\n  - serverless-offline\n\
+             functions: { create: { handler: src/handlers/user.create }, remove: { handler: src/handlers/user.remove }, \
+             report: { handler: reports/main.handler } }\n",
+        ),
+        (
+            "src/handlers/user.ts",
+            "import { db } from '../db';\nexport const create = () => db;\nexport const remove = () => db;\n\
+             export const update = () => db;\n",
+        ),
+        ("src/db.ts", "export const db = {};\n"),
+        ("reports/main.js", "export const handler = () => {};\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+
+    let unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(unused, vec!["update"], "{}", report);
+
+    // Plugins the config lists are used
+    let deps = report["unused_dependencies"].as_array().unwrap();
+    assert_eq!(deps.len(), 1, "{}", report);
+    assert_eq!(deps[0]["name"], "left-pad");
+}
+
+#[test]
+fn test_electron_forge_entries_and_ipc_handlers() {
+    let project = write_project(&[
+        (
+            "package.json",
+            r#"{ "name": "desktop", "main": "src/main.ts", "devDependencies": { "electron": "30.0.0", "@electron-forge/cli": "7.0.0" } }"#,
+        ),
+        (
+            "forge.config.js",
+            "module.exports = { plugins: [{ name: '@electron-forge/plugin-webpack', config: { renderer: { \
+             entryPoints: [{ html: './src/index.html', name: 'main_window', preload: { js: './src/preload.ts' } }] } } }] };\n",
+        ),
+        ("src/index.html", "<html><body><script type=\"module\" src=\"./renderer.ts\"></script></body></html>\n"),
+        ("src/main.ts", "import { app } from 'electron';\nconsole.log(app);\n"),
+        ("src/preload.ts", "import { contextBridge } from 'electron';\nconsole.log(contextBridge);\n"),
+        ("src/renderer.ts", "document.title = 'app';\n"),
+        ("src/ipc/files.ts", "import { ipcMain } from 'electron';\nipcMain.handle('files:list', () => []);\n"),
+        ("src/unused.ts", "export const unused = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused_files = report["unused_files"].as_array().unwrap();
+    assert_eq!(unused_files.len(), 1, "{}", report);
+    assert!(unused_files[0]["path"].as_str().unwrap().ends_with("unused.ts"));
+}
+
+#[test]
+fn test_sveltekit_routes_and_components() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "app", "devDependencies": { "@sveltejs/kit": "2.0.0" } }"#),
+        (
+            "src/routes/+page.svelte",
+            "<script lang=\"ts\">\n  import Card from './Card.svelte';\n  export let data: { title: string };\n</script>\n\
+             <h1>{data.title}</h1>\n<Card />\n",
+        ),
+        (
+            "src/routes/Card.svelte",
+            "<script context=\"module\">\n  export const meta = 1;\n</script>\n\
+             <script>\n  import { format } from './format';\n  export let x = format(1);\n</script>\n<div>{x}</div>\n",
+        ),
+        ("src/routes/format.ts", "export const format = (n: number) => `${n}`;\n"),
+        (
+            "src/routes/blog/+page.server.ts",
+            "export const load = () => ({});\nexport const actions = {};\nexport const helper = 1;\n",
+        ),
+        ("src/routes/orphan.ts", "export const old = 1;\n"),
+        ("src/hooks.server.ts", "export const handle = () => {};\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    // Only `+` files under src/routes are routes
+    let unused_files = report["unused_files"].as_array().unwrap();
+    assert_eq!(unused_files.len(), 1, "{}", report);
+    assert!(unused_files[0]["path"].as_str().unwrap().ends_with("orphan.ts"));
+
+    // Props are not exports, while the module script's exports are
+    let mut unused: Vec<&str> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|export| export["name"].as_str().unwrap())
+        .collect();
+    unused.sort();
+    assert_eq!(unused, vec!["helper", "meta"], "{}", report);
+}
+
+#[test]
+fn test_astro_frontmatter_imports_and_pages() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "site", "dependencies": { "astro": "4.0.0" } }"#),
+        ("astro.config.mjs", "import { defineConfig } from 'astro/config';\nexport default defineConfig({});\n"),
+        (
+            "src/pages/index.astro",
+            "---\nimport Card from '../components/Card.astro';\nimport { title } from '../lib';\n\
+             if (!title) return Astro.redirect('/404');\n---\n<h1>{title}</h1>\n<Card />\n",
+        ),
+        ("src/components/Card.astro", "<div>card</div>\n"),
+        ("src/components/Old.astro", "<div>old</div>\n"),
+        ("src/lib.ts", "export const title = 'Home';\nexport const subtitle = '';\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unused_files = report["unused_files"].as_array().unwrap();
+    assert_eq!(unused_files.len(), 1, "{}", report);
+    assert!(unused_files[0]["path"].as_str().unwrap().ends_with("Old.astro"));
+
+    let unused = report["unused_exports"].as_array().unwrap();
+    assert_eq!(unused.len(), 1, "{}", report);
+    assert_eq!(unused[0]["name"], "subtitle");
+}
+
+#[test]
+fn test_nest_providers_and_injected_types_are_referenced() {
+    let project = write_project(&[
+        ("package.json", r#"{ "name": "api", "main": "src/main.ts" }"#),
+        ("src/main.ts", "import { AppModule } from './app.module';\nconsole.log(AppModule);\n"),
+        (
+            "src/app.module.ts",
+            "import { Inject, Module } from '@nestjs/common';\n\
+             import { UsersController } from './users.controller';\n\
+             export const CONFIG = 'config';\n\
+             export class Logger {}\n\
+             export class UsersService {\n  constructor(private logger: Logger, @Inject(CONFIG) config: string) {}\n}\n\
+             export const stale = 1;\n\
+             @Module({ controllers: [UsersController], providers: [UsersService] })\n\
+             export class AppModule {}\n",
+        ),
+        (
+            "src/users.controller.ts",
+            "import { Controller } from '@nestjs/common';\nimport { UsersRepository } from './users.repository';\n\
+             @Controller('users')\nexport class UsersController {\n  constructor(private users: UsersRepository) {}\n}\n",
+        ),
+        ("src/users.repository.ts", "export class UsersRepository {}\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "json", "--no-cache"]);
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(report["unused_files"].as_array().unwrap().is_empty(), "{}", report);
+
+    // Exports only the module itself uses just need their `export` dropped
+    let mut unused: Vec<(&str, bool)> = report["unused_exports"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .chain(report["module_only_exports"].as_array().unwrap())
+        .map(|export| (export["name"].as_str().unwrap(), export["used_in_module"].as_bool().unwrap()))
+        .collect();
+    unused.sort();
+    assert_eq!(
+        unused,
+        vec![("CONFIG", true), ("Logger", true), ("UsersService", true), ("stale", false)],
+        "{}",
+        report
+    );
+}
+
+#[test]
+fn test_angular_entries_and_template_selectors_keep_components() {
+    let project = write_project(&[
+        (
+            "angular.json",
+            r#"{ "projects": { "site": { "root": "", "architect": {
+                "build": { "options": { "browser": "src/main.ts", "polyfills": ["zone.js"] } }
+            } } } }"#,
+        ),
+        ("src/main.ts", "import { AppComponent } from './app/app.component';\nbootstrap(AppComponent);\n"),
+        (
+            "src/app/app.component.ts",
+            "import { UnusedComponent } from './widgets';\n\
+             @Component({ selector: 'app-root', templateUrl: './app.component.html', imports: [UnusedComponent] })\n\
+             export class AppComponent {}\n",
+        ),
+        ("src/app/app.component.html", "<app-card [title]=\"x\"></app-card>\n<span *appTip>hi</span>\n"),
+        (
+            "src/app/widgets.ts",
+            "@Component({ selector: 'app-card', template: `<div></div>` })\nexport class CardComponent {}\n\
+             @Directive({ selector: 'span[appTip]' })\nexport class TipDirective {}\n\
+             @Component({ selector: 'app-unused' })\nexport class UnusedComponent {}\n\
+             @Component({ selector: 'app-stale' })\nexport class StaleComponent {}\n",
+        ),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused: Vec<&str> = stdout.lines().filter(|line| line.contains("unused-")).collect();
+
+    assert_eq!(unused.len(), 1, "{}", stdout);
+    assert!(unused[0].contains("Unused export 'StaleComponent'"), "{}", stdout);
+}
+
+#[test]
+fn test_sweeprignore_files_are_respected_at_every_level() {
+    let project = write_project(&[
+        (".sweeprignore", "generated/\n"),
+        ("src/index.ts", "export {};\n"),
+        ("src/orphan.ts", "const x = 1;\n"),
+        ("generated/client.ts", "const x = 1;\n"),
+        ("src/api/.sweeprignore", "*.gen.ts\n!keep.gen.ts\n"),
+        ("src/api/schema.gen.ts", "const x = 1;\n"),
+        ("src/api/keep.gen.ts", "const x = 1;\n"),
+    ]);
+
+    for args in [&["check", "--format", "compact"][..], &["check", "--format", "compact", "--include", "**/*.ts"]] {
+        let output = run_sweepr_in(project.path(), args);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert!(stdout.contains("orphan.ts"), "{}", stdout);
+        assert!(stdout.contains("keep.gen.ts"), "{}", stdout);
+        assert!(!stdout.contains("client.ts"), "{}", stdout);
+        assert!(!stdout.contains("schema.gen.ts"), "{}", stdout);
+    }
+}
+
+#[test]
+fn test_exports_are_only_used_when_imported_from_their_file() {
+    let project = write_project(&[
+        (
+            "src/index.ts",
+            "import { format } from './date';\nimport * as num from './number.js';\nimport './barrel';\nformat(num);\n",
+        ),
+        ("src/date.ts", "export function format() {}\nexport const parse = 1;\n"),
+        ("src/number.ts", "export function round() {}\n"),
+        ("src/text.ts", "export function format() {}\nfunction helper() {}\nhelper();\n"),
+        ("src/barrel.ts", "export { format } from './text';\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused_exports: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.contains("unused-exports"))
+        .collect();
+
+    assert!(unused_exports.iter().any(|line| line.contains("date.ts") && line.contains("'parse'")), "{}", stdout);
+    assert!(!unused_exports.iter().any(|line| line.contains("date.ts") && line.contains("'format'")), "{}", stdout);
+    assert!(!unused_exports.iter().any(|line| line.contains("number.ts")), "{}", stdout);
+    assert!(!unused_exports.iter().any(|line| line.contains("'helper'")), "{}", stdout);
+    // Only forwarded by a barrel whose own re-export is never imported
+    assert!(unused_exports.iter().any(|line| line.contains("text.ts")), "{}", stdout);
+    assert!(unused_exports.iter().any(|line| line.contains("barrel.ts")), "{}", stdout);
+    assert!(!stdout.contains("unused-files"), "{}", stdout);
+}
+
+#[test]
+fn test_usage_is_attributed_through_re_export_chains() {
+    let project = write_project(&[
+        (
+            "src/index.ts",
+            "import { helper, renamed, fromStar } from './lib';\nimport { missing } from './cycle-a';\nhelper(renamed, fromStar, missing);\n",
+        ),
+        ("src/lib/index.ts", "export { helper } from './impl';\nexport { inner as renamed } from './nested';\nexport * from './star';\n"),
+        ("src/lib/impl.ts", "export function helper() {}\nexport function unusedHelper() {}\n"),
+        ("src/lib/nested.ts", "export { inner } from './deep';\n"),
+        ("src/lib/deep.ts", "export const inner = 1;\nexport const other = 2;\n"),
+        ("src/lib/star.ts", "export const fromStar = 1;\nexport const notImported = 2;\n"),
+        ("src/cycle-a.ts", "export * from './cycle-b';\n"),
+        ("src/cycle-b.ts", "export * from './cycle-a';\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["check", "--format", "compact"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let unused: Vec<&str> = stdout.lines().filter(|line| line.contains("unused-exports")).collect();
+    let reported = |file: &str, name: &str| {
+        unused
+            .iter()
+            .any(|line| line.contains(file) && line.contains(&format!("'{}'", name)))
+    };
+
+    assert!(reported("impl.ts", "unusedHelper"), "{}", stdout);
+    assert!(reported("deep.ts", "other"), "{}", stdout);
+    assert!(reported("star.ts", "notImported"), "{}", stdout);
+    assert!(!reported("impl.ts", "helper"), "{}", stdout);
+    assert!(!reported("deep.ts", "inner"), "{}", stdout);
+    assert!(!reported("star.ts", "fromStar"), "{}", stdout);
+    assert!(!reported("lib/index.ts", "renamed"), "{}", stdout);
+}
+
+#[test]
+fn test_file_import_graph_tracks_reachability_cycles_and_dangling_targets() {
+    use sweepr::graph::{FileImportGraph, ParsedImport};
+
+    let edge = |from: &str, to: &str| ParsedImport {
+        from: PathBuf::from(from),
+        to: PathBuf::from(to),
+        specifier: format!("./{}", to),
+        span: (0, 0),
+        is_resolved: true,
+        imported_symbols: Vec::new(),
+        is_type_only: false,
+        forwarded: Vec::new(),
+    };
+
+    let mut graph = FileImportGraph::new();
+    for (file, is_entry_point) in [("index.ts", true), ("a.ts", false), ("b.ts", false), ("dead.ts", false)] {
+        graph.add_file(PathBuf::from(file), is_entry_point);
+    }
+    graph.add_import(edge("index.ts", "a.ts"));
+    graph.add_import(edge("a.ts", "b.ts"));
+    graph.add_import(edge("b.ts", "a.ts"));
+    graph.add_import(edge("a.ts", "missing.ts"));
+
+    let reachable = graph.reachable_files();
+    let id = |path: &str| graph.file_id(std::path::Path::new(path)).unwrap();
+    assert!(reachable.contains(&id("b.ts")));
+    assert!(!reachable.contains(&id("dead.ts")));
+    assert_eq!(graph.path(id("dead.ts")), std::path::Path::new("dead.ts"));
+    assert_eq!(graph.import_cycles(), vec![vec![PathBuf::from("a.ts"), PathBuf::from("b.ts")]]);
+    assert_eq!(graph.files().count(), 4);
+    assert!(!graph.contains_file(std::path::Path::new("missing.ts")));
+
+    // A removed file that is still imported remains only as a dangling target
+    graph.remove_file(std::path::Path::new("b.ts"));
+    assert!(!graph.contains_file(std::path::Path::new("b.ts")));
+    assert!(graph.import_cycles().is_empty());
+    assert_eq!(graph.imports_from(std::path::Path::new("a.ts")).count(), 2);
+}
+
+#[test]
+fn test_file_import_graph_releases_paths_and_names_of_removed_files() {
+    use sweepr::graph::{FileImportGraph, ParsedImport};
+
+    let mut graph = FileImportGraph::new();
+    graph.add_file(PathBuf::from("index.ts"), true);
+    graph.add_file(PathBuf::from("util.ts"), false);
+    graph.add_import(ParsedImport {
+        from: PathBuf::from("index.ts"),
+        to: PathBuf::from("util.ts"),
+        specifier: "./util".to_string(),
+        span: (0, 0),
+        is_resolved: true,
+        imported_symbols: vec!["helper".to_string()],
+        is_type_only: false,
+        forwarded: Vec::new(),
+    });
+    assert_eq!(graph.interners().paths.len(), 2);
+    assert_eq!(graph.name(graph.symbol_id("helper").unwrap()), "helper");
+
+    // Nothing refers to either file or the imported name once both are gone
+    graph.remove_file(std::path::Path::new("index.ts"));
+    graph.remove_file(std::path::Path::new("util.ts"));
+    assert!(graph.interners().paths.is_empty());
+    assert!(graph.symbol_id("helper").is_none());
+}
+
+#[test]
+fn test_why_explains_import_chains_and_export_usage() {
+    let project = write_project(&[
+        ("src/index.ts", "import { a } from './lib';\nconsole.log(a);\n"),
+        ("src/lib.ts", "export { a } from './a';\n"),
+        ("src/a.ts", "export const a = 1;\nexport const b = 2;\n"),
+        ("src/orphan.ts", "export const z = 1;\n"),
+    ]);
+
+    let output = run_sweepr_in(project.path(), &["why", "src/a.ts", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    let chain: Vec<&str> = stdout.lines().skip(1).map(str::trim).collect();
+    assert_eq!(chain, vec!["src/index.ts", "src/lib.ts", "src/a.ts"], "{}", stdout);
+
+    let output = run_sweepr_in(project.path(), &["why", "src/orphan.ts", "--no-cache"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("not reachable"));
+
+    let output = run_sweepr_in(project.path(), &["why", "a", "--no-cache"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("used by src/index.ts (via src/lib.ts)"), "{}", stdout);
+
+    let output = run_sweepr_in(project.path(), &["why", "b", "--no-cache"]);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("not imported anywhere"));
+
+    let output = run_sweepr_in(project.path(), &["why", "missing", "--no-cache"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_parse_cache_is_dropped_for_another_schema_sweepr_or_oxc_version() {
+    use sweepr::cache::{self, ParseCache};
+
+    let project = tempfile::tempdir().unwrap();
+    let file = project.path().join("src/index.ts");
+    let parsed = sweepr::parser::AstAnalyzer::parse_file_source(file.clone(), "export const a = 1;\n").unwrap();
+    let mut parse_cache = ParseCache::load(project.path());
+    parse_cache.insert("hash".to_string(), parsed);
+    parse_cache.save().unwrap();
+
+    let cache_file = cache::cache_dir(project.path()).join("parse.bin");
+    let written = std::fs::read(&cache_file).unwrap();
+    let is_reused = |content: &[u8]| {
+        std::fs::write(&cache_file, content).unwrap();
+        ParseCache::load(project.path()).get(&file, "hash").is_some()
+    };
+    assert!(is_reused(&written));
+
+    // The file starts with the schema version, then the sweepr and oxc
+    // versions, each a length followed by its bytes
+    let sweepr_version = 4 + 8;
+    let oxc_version = sweepr_version + env!("CARGO_PKG_VERSION").len() + 8;
+    assert_eq!(&written[oxc_version..oxc_version + cache::OXC_VERSION.len()], cache::OXC_VERSION.as_bytes());
+    for offset in [0, sweepr_version, oxc_version] {
+        let mut changed = written.clone();
+        changed[offset] = changed[offset].wrapping_add(1);
+        assert!(!is_reused(&changed), "cache reused with byte {} changed", offset);
+    }
+}
+
+#[test]
+fn test_oxc_version_of_the_cache_matches_cargo_toml() {
+    let manifest = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap();
+    let versions: Vec<&str> = manifest
+        .lines()
+        .filter(|line| line.starts_with("oxc_"))
+        .filter_map(|line| line.split('"').nth(1))
+        .collect();
+
+    assert!(!versions.is_empty());
+    assert!(versions.iter().all(|version| *version == sweepr::cache::OXC_VERSION), "{:?}", versions);
+}